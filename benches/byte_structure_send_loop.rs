@@ -0,0 +1,107 @@
+//! Benchmarks a 60 Hz agent send loop, serializing neuron data and a JSON status each tick,
+//! with a new allocation per structure against a `FeagiByteStructurePool`.
+//!
+//! Run with `cargo bench --bench byte_structure_send_loop`. Before timing, heap allocations
+//! per tick are counted with a global allocator for both approaches, and the benchmark panics
+//! if the pooled loop allocates at all once warmed up.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use serde_json::json;
+use feagi_core_data_structures_and_processing::genomic_structures::CorticalID;
+use feagi_core_data_structures_and_processing::io_data::FeagiJSON;
+use feagi_core_data_structures_and_processing::io_processing::byte_structures::{FeagiByteStructureCompatible, FeagiByteStructurePool};
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZP, NeuronXYZPArrays};
+
+/// System allocator counting every allocation and reallocation.
+struct CountingAllocator;
+
+static ALLOCATION_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const TICKS_PER_SECOND: usize = 60;
+
+/// Camera neurons of a 64x64 RGB frame, plus a small motor area.
+fn create_tick_neuron_data() -> CorticalMappedXYZPNeuronData {
+    let mut camera = NeuronXYZPArrays::with_capacity(64 * 64 * 3);
+    for x in 0..64 {
+        for y in 0..64 {
+            for z in 0..3 {
+                camera.push(&NeuronXYZP::new(x, y, z, ((x + y + z) % 256) as f32 / 255.0));
+            }
+        }
+    }
+    let mut motor = NeuronXYZPArrays::with_capacity(4);
+    for x in 0..4 {
+        motor.push(&NeuronXYZP::new(x, 0, 0, 1.0));
+    }
+    let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    neuron_data.insert(CorticalID::from_string("iic400".to_string()).unwrap(), camera);
+    neuron_data.insert(CorticalID::new_custom_cortical_area_id("cMOTOR".to_string()).unwrap(), motor);
+    neuron_data
+}
+
+fn send_tick_allocating(neuron_data: &CorticalMappedXYZPNeuronData, status: &FeagiJSON) -> usize {
+    let neurons = neuron_data.as_new_feagi_byte_structure().unwrap();
+    let json = status.as_new_feagi_byte_structure().unwrap();
+    neurons.borrow_data_as_slice().len() + json.borrow_data_as_slice().len()
+}
+
+fn send_tick_pooled(pool: &mut FeagiByteStructurePool, neuron_data: &CorticalMappedXYZPNeuronData, status: &FeagiJSON) -> usize {
+    let neurons = pool.take_serialized(neuron_data).unwrap();
+    let json = pool.take_serialized(status).unwrap();
+    let sent = neurons.borrow_data_as_slice().len() + json.borrow_data_as_slice().len();
+    pool.recycle(neurons);
+    pool.recycle(json);
+    sent
+}
+
+/// Counts the allocations made by one second of ticks, after one second of warm up.
+fn count_allocations_per_second(mut tick: impl FnMut() -> usize) -> usize {
+    for _ in 0..TICKS_PER_SECOND {
+        black_box(tick());
+    }
+    let before = ALLOCATION_COUNT.load(Ordering::Relaxed);
+    for _ in 0..TICKS_PER_SECOND {
+        black_box(tick());
+    }
+    ALLOCATION_COUNT.load(Ordering::Relaxed) - before
+}
+
+fn bench_send_loop(c: &mut Criterion) {
+    let neuron_data = create_tick_neuron_data();
+    let status = FeagiJSON::from_json_value(json!({"agent": "camera_0", "fps": 60, "healthy": true}));
+    let mut pool = FeagiByteStructurePool::new();
+
+    let allocating = count_allocations_per_second(|| send_tick_allocating(&neuron_data, &status));
+    let pooled = count_allocations_per_second(|| send_tick_pooled(&mut pool, &neuron_data, &status));
+    println!("heap allocations per {} ticks: allocating = {}, pooled = {}", TICKS_PER_SECOND, allocating, pooled);
+    assert_eq!(pooled, 0, "Pooled send loop should not allocate once warmed up!");
+
+    let mut group = c.benchmark_group("byte_structure_send_loop");
+    group.bench_function("allocating", |b| b.iter(|| send_tick_allocating(black_box(&neuron_data), black_box(&status))));
+    group.bench_function("pooled", |b| b.iter(|| send_tick_pooled(&mut pool, black_box(&neuron_data), black_box(&status))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_send_loop);
+criterion_main!(benches);
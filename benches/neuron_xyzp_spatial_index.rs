@@ -0,0 +1,64 @@
+//! Benchmarks NeuronXYZPSpatialIndex queries against linear scans of the same neurons.
+//!
+//! Run with `cargo bench --bench neuron_xyzp_spatial_index`. Each query is benchmarked both
+//! through the index and as a scan of every neuron. Building the index is benchmarked
+//! separately, as it only pays off once several queries are made against the same burst.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::{NeuronXYZP, NeuronXYZPArrays};
+
+/// A sparse area where a tenth of the neurons fire, with varying potentials.
+fn create_sparse_firing(width: u32, height: u32, depth: u32) -> NeuronXYZPArrays {
+    let mut neurons = NeuronXYZPArrays::new();
+    for x in 0..width {
+        for y in 0..height {
+            for z in 0..depth {
+                if (x * 31 + y * 17 + z * 7) % 10 == 0 {
+                    neurons.push(&NeuronXYZP::new(x, y, z, ((x + y + z) % 256) as f32 / 255.0));
+                }
+            }
+        }
+    }
+    neurons
+}
+
+fn distance_squared(neuron: &NeuronXYZP, center: (u32, u32, u32)) -> u64 {
+    (neuron.x.abs_diff(center.0) as u64).pow(2) + (neuron.y.abs_diff(center.1) as u64).pow(2) + (neuron.z.abs_diff(center.2) as u64).pow(2)
+}
+
+fn scan_radius(neurons: &NeuronXYZPArrays, center: (u32, u32, u32), radius: f32) -> Vec<usize> {
+    let radius_squared = (radius * radius) as u64;
+    neurons.enumerate().filter(|(_, neuron)| distance_squared(neuron, center) <= radius_squared).map(|(i, _)| i).collect()
+}
+
+fn scan_k_nearest(neurons: &NeuronXYZPArrays, center: (u32, u32, u32), k: usize) -> Vec<usize> {
+    let mut distances: Vec<(u64, usize)> = neurons.enumerate().map(|(i, neuron)| (distance_squared(&neuron, center), i)).collect();
+    distances.sort_unstable();
+    distances.into_iter().take(k).map(|(_, i)| i).collect()
+}
+
+fn bench_spatial_queries(c: &mut Criterion) {
+    let neurons = create_sparse_firing(256, 256, 16);
+    let index = neurons.build_spatial_index();
+    println!("{} neurons, cell size {}, {} occupied cells", neurons.len(), index.get_cell_size(), index.get_occupied_cell_count());
+
+    c.bench_function("neuron_xyzp_spatial_index/build", |b| b.iter(|| black_box(&neurons).build_spatial_index().get_occupied_cell_count()));
+
+    let mut group = c.benchmark_group("neuron_xyzp_spatial_index/box_16x16x4");
+    group.bench_function("scan", |b| b.iter(|| neurons.filter_neurons_by_location_bounds(black_box(100..=115), 100..=115, 4..=7).unwrap()));
+    group.bench_function("indexed", |b| b.iter(|| index.query_box(black_box(100..=115), 100..=115, 4..=7)));
+    group.finish();
+
+    let mut group = c.benchmark_group("neuron_xyzp_spatial_index/radius_6");
+    group.bench_function("scan", |b| b.iter(|| scan_radius(&neurons, black_box((128, 128, 8)), 6.0)));
+    group.bench_function("indexed", |b| b.iter(|| index.query_radius_indices(black_box((128, 128, 8)), 6.0)));
+    group.finish();
+
+    let mut group = c.benchmark_group("neuron_xyzp_spatial_index/k_nearest_8");
+    group.bench_function("scan", |b| b.iter(|| scan_k_nearest(&neurons, black_box((128, 128, 8)), 8)));
+    group.bench_function("indexed", |b| b.iter(|| index.query_k_nearest_indices(black_box((128, 128, 8)), 8)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_spatial_queries);
+criterion_main!(benches);
@@ -0,0 +1,84 @@
+//! Benchmarks the uncompressed (v1) neuron XYZP wire format against the compressed one.
+//!
+//! Run with `cargo bench --bench neuron_xyzp_wire_format`. Alongside the timings, the
+//! serialized size of each format is printed once per data set.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use feagi_core_data_structures_and_processing::genomic_structures::CorticalID;
+use feagi_core_data_structures_and_processing::io_processing::byte_structures::{FeagiByteStructure, FeagiByteStructureCompatible};
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZP, NeuronXYZPArrays, NeuronXYZPPotentialQuantization};
+
+/// A dense RGB camera frame, where every channel of every pixel is active with a varying potential.
+fn create_camera_frame(width: u32, height: u32) -> CorticalMappedXYZPNeuronData {
+    let mut neurons = NeuronXYZPArrays::with_capacity((width * height * 3) as usize);
+    for x in 0..width {
+        for y in 0..height {
+            for z in 0..3 {
+                let value = ((x * 7 + y * 13 + z * 29) % 256) as f32 / 255.0;
+                neurons.push(&NeuronXYZP::new(x, y, z, value));
+            }
+        }
+    }
+    let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    neuron_data.insert(CorticalID::from_string("iic400".to_string()).unwrap(), neurons);
+    neuron_data
+}
+
+/// A sparse area where a tenth of the neurons fire, all with the same potential.
+fn create_sparse_firing(width: u32, height: u32, depth: u32) -> CorticalMappedXYZPNeuronData {
+    let mut neurons = NeuronXYZPArrays::new();
+    for x in 0..width {
+        for y in 0..height {
+            for z in 0..depth {
+                if (x * 31 + y * 17 + z * 7) % 10 == 0 {
+                    neurons.push(&NeuronXYZP::new(x, y, z, 1.0));
+                }
+            }
+        }
+    }
+    let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    neuron_data.insert(CorticalID::new_custom_cortical_area_id("cSPARS".to_string()).unwrap(), neurons);
+    neuron_data
+}
+
+fn bench_wire_formats(c: &mut Criterion) {
+    let data_sets = [
+        ("camera_640x480", create_camera_frame(640, 480)),
+        ("sparse_128x128x16", create_sparse_firing(128, 128, 16)),
+    ];
+
+    for (name, neuron_data) in &data_sets {
+        let neuron_count: usize = neuron_data.iter().map(|neurons| neurons.len()).sum();
+        let v1_size = neuron_data.as_new_feagi_byte_structure().unwrap().borrow_data_as_slice().len();
+        println!("{}: {} neurons, v1 = {} bytes", name, neuron_count, v1_size);
+        for quantization in [NeuronXYZPPotentialQuantization::Float32, NeuronXYZPPotentialQuantization::U16, NeuronXYZPPotentialQuantization::U8] {
+            let size = neuron_data.as_new_feagi_byte_structure_compressed(quantization).unwrap().borrow_data_as_slice().len();
+            println!("{}: compressed {} = {} bytes ({:.1}% of v1)", name, quantization, size, size as f32 / v1_size as f32 * 100.0);
+        }
+
+        let mut group = c.benchmark_group(format!("neuron_xyzp_round_trip/{}", name));
+        group.throughput(Throughput::Elements(neuron_count as u64));
+        group.bench_function("v1", |b| b.iter(|| {
+            let bytes = black_box(neuron_data).as_new_feagi_byte_structure().unwrap();
+            CorticalMappedXYZPNeuronData::new_from_feagi_byte_structure(&bytes).unwrap()
+        }));
+        for quantization in [NeuronXYZPPotentialQuantization::Float32, NeuronXYZPPotentialQuantization::U8] {
+            group.bench_with_input(BenchmarkId::new("compressed", quantization), &quantization, |b, quantization| b.iter(|| {
+                let bytes = black_box(neuron_data).as_new_feagi_byte_structure_compressed(*quantization).unwrap();
+                CorticalMappedXYZPNeuronData::new_from_feagi_byte_structure(&bytes).unwrap()
+            }));
+        }
+        group.finish();
+
+        let v1_bytes: FeagiByteStructure = neuron_data.as_new_feagi_byte_structure().unwrap();
+        let compressed_bytes = neuron_data.as_new_feagi_byte_structure_compressed(NeuronXYZPPotentialQuantization::U8).unwrap();
+        let mut group = c.benchmark_group(format!("neuron_xyzp_decode/{}", name));
+        group.throughput(Throughput::Elements(neuron_count as u64));
+        group.bench_function("v1", |b| b.iter(|| CorticalMappedXYZPNeuronData::new_from_feagi_byte_structure(black_box(&v1_bytes)).unwrap()));
+        group.bench_function("compressed_u8", |b| b.iter(|| CorticalMappedXYZPNeuronData::new_from_feagi_byte_structure(black_box(&compressed_bytes)).unwrap()));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_wire_formats);
+criterion_main!(benches);
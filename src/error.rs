@@ -335,6 +335,17 @@ pub enum FeagiBytesError {
     /// - Mixing different protocol versions
     /// - Operating on wrong endianness
     IncompatibleByteUse(String),
+
+    /// Integrity or authentication check of sealed bytes failed.
+    ///
+    /// This variant is used when sealed byte data does not match its checksum or
+    /// authentication tag, and should be treated as corrupted or tampered with.
+    ///
+    /// Common cases include:
+    /// - CRC32 mismatch from corrupted or truncated data
+    /// - Authentication tag mismatch from tampering or a wrong key
+    /// - Missing authentication tag when one is required
+    IntegrityCheckFailed(String),
 }
 
 impl fmt::Display for FeagiBytesError {
@@ -344,6 +355,7 @@ impl fmt::Display for FeagiBytesError {
             FeagiBytesError::UnableToSerializeBytes(e) => write!(f, "Unable to serialize bytes: {}", e),
             FeagiBytesError::UnableToDeserializeBytes(e) => write!(f, "Unable to deserialize bytes: {}", e),
             FeagiBytesError::IncompatibleByteUse(e) => write!(f, "Incorrect of byte structure: {}", e),
+            FeagiBytesError::IntegrityCheckFailed(e) => write!(f, "Integrity check failed: {}", e),
         }
     }
 }
@@ -67,31 +67,29 @@ impl FeagiByteStructureCompatible for FeagiJSON {
     fn get_version(&self) -> u8 {Self:: BYTE_STRUCT_VERSION}
 
     fn overwrite_feagi_byte_structure_slice(&self, slice: &mut [u8]) -> Result<usize, FeagiDataProcessingError> {
-        
-        // doing this here instead of using the max_number_bytes_needed func so we can double-dip on data usage
-        let json_string = self.json.to_string();
-        let json_bytes = json_string.as_bytes();
-        
-        let num_bytes_needed: usize = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + json_bytes.len();
-        if slice.len() < num_bytes_needed {
-            return Err(IODataError::InvalidInplaceOperation(format!("Not enough space given to store JSON data! Need {} bytes but given {}!", num_bytes_needed, slice.len())).into());
+        if slice.len() < FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES {
+            return Err(IODataError::InvalidInplaceOperation(format!("Not enough space given to store JSON data! Need {} bytes but given {}!", self.max_number_bytes_needed(), slice.len())).into());
         }
         
         // Write the global header
         slice[0] = self.get_type() as u8;
         slice[1] = self.get_version();
         
-        // Write the JSON data as UTF-8 bytes
-        slice[FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES..FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + json_bytes.len()].copy_from_slice(json_bytes);
+        // Write the JSON data as UTF-8 bytes directly into the slice, without an intermediate String
+        let mut json_write_target = &mut slice[FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES..];
+        if serde_json::to_writer(&mut json_write_target, &self.json).is_err() {
+            return Err(IODataError::InvalidInplaceOperation(format!("Not enough space given to store JSON data! Need {} bytes but given {}!", self.max_number_bytes_needed(), slice.len())).into());
+        }
         
-        let wasted_space = slice.len() - num_bytes_needed;
-        Ok(wasted_space)
+        // What is left of the write target is wasted
+        Ok(json_write_target.len())
     }
 
     fn max_number_bytes_needed(&self) -> usize {
-        // Global header (2 bytes) + JSON data as UTF-8 bytes
-        // TODO this is pretty slow, any faster way to do this?
-        FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + self.json.to_string().as_bytes().len()
+        // Global header (2 bytes) + JSON data as UTF-8 bytes, counted without allocating a String
+        let mut byte_counter = ByteCounter { count: 0 };
+        _ = serde_json::to_writer(&mut byte_counter, &self.json); // Writing to a counter cannot fail
+        FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + byte_counter.count
     }
 
     fn new_from_feagi_byte_structure(feagi_byte_structure: &FeagiByteStructure) -> Result<Self, FeagiDataProcessingError>
@@ -210,3 +208,19 @@ impl FeagiJSON {
         &self.json
     }
 }
+
+/// Writer that only counts the bytes written to it.
+struct ByteCounter {
+    count: usize,
+}
+
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.count += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -290,7 +290,7 @@ impl CorticalType {
     /// - **Motor outputs**: May use signed normalized coders for bidirectional control
     /// - **Audio sensors**: Frequency domain or time domain coders
     /// - **Other I/O**: Type-specific encoding optimized for the data characteristics
-    pub(crate) fn try_get_coder_type(&self) -> Result<NeuronCoderVariantType, FeagiDataProcessingError> {
+    pub fn try_get_coder_type(&self) -> Result<NeuronCoderVariantType, FeagiDataProcessingError> {
         match self {
             Self::Custom => Err(IODataError::InvalidParameters("Custom Cortical Areas do not have coders!".into()).into()),
             Self::Memory => Err(IODataError::InvalidParameters("Memory Cortical Areas do not have coders!".into()).into()),
@@ -6,6 +6,7 @@
 
 use std::cmp;
 use std::ops::RangeInclusive;
+use serde::{Deserialize, Serialize};
 use crate::error::{FeagiDataProcessingError, IODataError};
 use crate::io_data::{ImageFrame, SegmentedImageFrame};
 
@@ -194,7 +195,7 @@ impl SegmentedImageFrameProperties {
 ///
 /// This structure stores the desired output resolution for each of the segments
 /// in a grid arrangement (3x3): corners, edges, and center.
-#[derive(PartialEq, Clone, Copy, Debug, Eq, Hash)]
+#[derive(PartialEq, Clone, Copy, Debug, Eq, Hash, Serialize, Deserialize)]
 pub struct SegmentedFrameTargetResolutions {
     /// Resolution for lower-left segment as (width, height)
     pub lower_left: (usize, usize),
@@ -576,7 +577,7 @@ impl GazeProperties {
 /// This enum defines the possible color spaces:
 /// - Linear: Linear color space
 /// - Gamma: Gamma-corrected color space
-#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash, Serialize, Deserialize)]
 pub enum ColorSpace {
     Linear,
     Gamma
@@ -598,7 +599,7 @@ impl std::fmt::Display for ColorSpace {
 /// - RG: Two channels (red, green)
 /// - RGB: Three channels (red, green, blue)
 /// - RGBA: Four channels (red, green, blue, alpha)
-#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash, Serialize, Deserialize)]
 pub enum ColorChannelLayout {
     GrayScale = 1, // R
     RG = 2,
@@ -7,7 +7,7 @@
 
 use ndarray::{Array3, ArrayView3};
 use crate::io_data::image::descriptors::{ColorChannelLayout, ColorSpace, MemoryOrderLayout};
-use crate::error::{FeagiDataProcessingError, IODataError};
+use crate::error::{FeagiDataProcessingError, IODataError, NeuronError};
 use crate::genomic_structures::{CorticalID, CorticalIOChannelIndex};
 use crate::io_data::image::descriptors::ImageFrameProperties;
 use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPArrays};
@@ -399,7 +399,7 @@ impl ImageFrame {
 
     //endregion
     
-    // region Outputting Neurons
+    // region Inputting / Outputting Neurons
     
     pub fn write_as_neuron_xyzp_data(&self, write_target: &mut CorticalMappedXYZPNeuronData, target_id: CorticalID, x_channel_offset: CorticalIOChannelIndex) -> Result<(), FeagiDataProcessingError> {
         const EPSILON: f32 = 0.0001; // avoid writing near zero vals
@@ -425,6 +425,47 @@ impl ImageFrame {
         
     }
     
+    /// Overwrites the pixels of this image with neuron data, as the inverse of `write_as_neuron_xyzp_data`.
+    ///
+    /// All pixels are zeroed first. Neurons belonging to other channels of the cortical area are ignored.
+    /// If the cortical area is not present in the neuron data, the image is left fully zeroed.
+    ///
+    /// # Arguments
+    ///
+    /// * `read_from` - The neuron data to read from
+    /// * `source_id` - The cortical area the image was written to
+    /// * `x_channel_offset` - The channel the image was written to
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a neuron within this channel falls outside the image resolution or color channel count.
+    pub fn overwrite_from_neuron_xyzp_data(&mut self, read_from: &CorticalMappedXYZPNeuronData, source_id: CorticalID, x_channel_offset: CorticalIOChannelIndex) -> Result<(), FeagiDataProcessingError> {
+        self.pixels.fill(0.0);
+        let neuron_data: &NeuronXYZPArrays = match read_from.get_neurons_of(&source_id) {
+            Some(neuron_data) => neuron_data,
+            None => return Ok(())
+        };
+        
+        let (height, width, channel_count) = self.get_internal_shape();
+        let y_flip_distance: u32 = height as u32;
+        let x_offset: u32 = *x_channel_offset * width as u32;
+        let x_range = x_offset..(x_offset + width as u32);
+        
+        for neuron in neuron_data.iter() {
+            if !x_range.contains(&neuron.x) {
+                continue; // belongs to another channel
+            }
+            if neuron.y == 0 || neuron.y > y_flip_distance || neuron.z as usize >= channel_count {
+                return Err(NeuronError::UnableToParseFromNeuronData(format!("Neuron at ({}, {}, {}) is outside the bounds of an image of resolution <{}, {}> with {} color channels!",
+                                                                            neuron.x, neuron.y, neuron.z, width, height, channel_count)).into());
+            }
+            let y: usize = (y_flip_distance - neuron.y) as usize; // flip y back
+            let x: usize = (neuron.x - x_offset) as usize;
+            self.pixels[(y, x, neuron.z as usize)] = neuron.p;
+        };
+        Ok(())
+    }
+    
     // endregion
 
     // region Internal Functions
@@ -0,0 +1,360 @@
+//! Byte structure serialization for [`ImageFrame`] and [`SegmentedImageFrame`].
+//!
+//! This allows raw camera frames to be recorded, replayed, or sent to a preprocessing node
+//! without first encoding them as neurons. Pixels are written in row-major (height, width,
+//! channel) order, either losslessly as f32, or quantized to u8 over the normalized 0 to 1
+//! range.
+//!
+//! # Binary Format
+//!
+//! ```text
+//! ImageFrame:
+//!     [Type=14 (1)][Version (1)][Width (u32)][Height (u32)][Channel Layout (u8)]
+//!     [Color Space (u8)][Pixel Quantization (u8)][Pixels...]
+//!
+//! SegmentedImageFrame:
+//!     [Type=15 (1)][Version (1)][Color Space (u8)][Center Channel Layout (u8)]
+//!     [Peripheral Channel Layout (u8)][Pixel Quantization (u8)]
+//!     [9 x (Width (u32), Height (u32))][9 x Pixels...]
+//! ```
+//!
+//! Segments are stored in cortical order, the same order as
+//! `SegmentedImageFrame::get_ordered_image_frame_references`.
+
+use byteorder::{ByteOrder, LittleEndian};
+use ndarray::Array3;
+use crate::error::{FeagiBytesError, FeagiDataProcessingError, IODataError};
+use crate::io_data::image::descriptors::{ColorChannelLayout, ColorSpace, SegmentedFrameTargetResolutions};
+use crate::io_data::{ImageFrame, SegmentedImageFrame};
+use crate::io_processing::byte_structures::{FeagiByteStructure, FeagiByteStructureCompatible, FeagiByteStructureType};
+
+/// How pixel values are stored when serializing image frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFramePixelQuantization {
+    /// Pixels are stored losslessly as 4 byte floats.
+    Float32,
+    /// Pixels are clamped to 0 to 1 and quantized to 256 steps, using a quarter of the space.
+    U8,
+}
+
+impl std::fmt::Display for ImageFramePixelQuantization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Float32 => "Float32",
+            Self::U8 => "U8",
+        };
+        write!(f, "ImageFramePixelQuantization({})", name)
+    }
+}
+
+impl ImageFramePixelQuantization {
+    const ID_FLOAT32: u8 = 0;
+    const ID_U8: u8 = 1;
+
+    fn id(&self) -> u8 {
+        match self {
+            Self::Float32 => Self::ID_FLOAT32,
+            Self::U8 => Self::ID_U8,
+        }
+    }
+
+    fn try_from_id(id: u8) -> Result<Self, FeagiBytesError> {
+        match id {
+            Self::ID_FLOAT32 => Ok(Self::Float32),
+            Self::ID_U8 => Ok(Self::U8),
+            _ => Err(FeagiBytesError::UnableToDeserializeBytes(format!("Unknown pixel quantization {} in image frame byte structure!", id))),
+        }
+    }
+
+    fn number_bytes_per_value(&self) -> usize {
+        match self {
+            Self::Float32 => size_of::<f32>(),
+            Self::U8 => size_of::<u8>(),
+        }
+    }
+
+    fn write_pixels(&self, pixels: &Array3<f32>, slice: &mut [u8]) {
+        match self {
+            Self::Float32 => {
+                for (value, target) in pixels.iter().zip(slice.chunks_exact_mut(size_of::<f32>())) {
+                    LittleEndian::write_f32(target, *value);
+                }
+            }
+            Self::U8 => {
+                for (value, target) in pixels.iter().zip(slice.iter_mut()) {
+                    *target = (value.clamp(0.0, 1.0) * u8::MAX as f32).round() as u8;
+                }
+            }
+        }
+    }
+
+    fn read_pixels(&self, bytes: &[u8], pixels: &mut Array3<f32>) {
+        match self {
+            Self::Float32 => {
+                for (target, value) in pixels.iter_mut().zip(bytes.chunks_exact(size_of::<f32>())) {
+                    *target = LittleEndian::read_f32(value);
+                }
+            }
+            Self::U8 => {
+                for (target, value) in pixels.iter_mut().zip(bytes.iter()) {
+                    *target = *value as f32 / u8::MAX as f32;
+                }
+            }
+        }
+    }
+}
+
+//region ImageFrame
+
+impl FeagiByteStructureCompatible for ImageFrame {
+    fn get_type(&self) -> FeagiByteStructureType { FeagiByteStructureType::ImageFrame }
+
+    fn get_version(&self) -> u8 { FeagiByteStructure::SUPPORTED_VERSION_IMAGE_FRAME }
+
+    fn overwrite_feagi_byte_structure_slice(&self, slice: &mut [u8]) -> Result<usize, FeagiDataProcessingError> {
+        self.overwrite_feagi_byte_structure_slice_quantized(slice, ImageFramePixelQuantization::Float32)
+    }
+
+    fn max_number_bytes_needed(&self) -> usize {
+        self.number_bytes_needed_quantized(ImageFramePixelQuantization::Float32)
+    }
+
+    fn new_from_feagi_byte_structure(feagi_byte_structure: &FeagiByteStructure) -> Result<Self, FeagiDataProcessingError> {
+        FeagiByteStructure::verify_matching_structure_type_and_version(feagi_byte_structure,
+                                                                       FeagiByteStructureType::ImageFrame,
+                                                                       FeagiByteStructure::SUPPORTED_VERSION_IMAGE_FRAME)?;
+        let bytes = feagi_byte_structure.borrow_data_as_slice();
+        if bytes.len() < ImageFrame::BYTE_STRUCT_HEADER_SIZE {
+            return Err(FeagiBytesError::UnableToDeserializeBytes("Image frame byte structure too short to contain its header!".into()).into());
+        }
+        let header_start = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES;
+        let width = LittleEndian::read_u32(&bytes[header_start..header_start + 4]) as usize;
+        let height = LittleEndian::read_u32(&bytes[header_start + 4..header_start + 8]) as usize;
+        let channel_layout = read_channel_layout(bytes[header_start + 8])?;
+        let color_space = read_color_space(bytes[header_start + 9])?;
+        let quantization = ImageFramePixelQuantization::try_from_id(bytes[header_start + 10])?;
+
+        // The resolution is untrusted, so the pixel count is checked before allocating the frame
+        let pixel_bytes = &bytes[ImageFrame::BYTE_STRUCT_HEADER_SIZE..];
+        let expected_pixel_bytes = number_pixel_bytes((width, height), channel_layout, quantization)
+            .ok_or_else(|| FeagiBytesError::UnableToDeserializeBytes(format!("Image frame byte structure has an impossibly large resolution of {}x{}!", width, height)))?;
+        if pixel_bytes.len() != expected_pixel_bytes {
+            return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Image frame byte structure should contain {} bytes of pixels but contains {}!", expected_pixel_bytes, pixel_bytes.len())).into());
+        }
+        let mut image_frame = ImageFrame::new(&channel_layout, &color_space, &(width, height))
+            .map_err(|_| FeagiBytesError::UnableToDeserializeBytes("Image frame byte structure has a resolution of 0!".into()))?;
+        quantization.read_pixels(pixel_bytes, image_frame.get_internal_data_mut());
+        Ok(image_frame)
+    }
+}
+
+impl ImageFrame {
+    /// Size of the header of an image frame byte structure, including the global header.
+    const BYTE_STRUCT_HEADER_SIZE: usize = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + 4 + 4 + 1 + 1 + 1;
+
+    /// Serializes this frame into a new byte structure with the given pixel quantization.
+    ///
+    /// # Example
+    /// ```rust
+    /// use feagi_core_data_structures_and_processing::io_data::{ImageFrame, ImageFramePixelQuantization};
+    /// use feagi_core_data_structures_and_processing::io_data::image_descriptors::{ColorChannelLayout, ColorSpace};
+    /// use feagi_core_data_structures_and_processing::io_processing::byte_structures::FeagiByteStructureCompatible;
+    ///
+    /// let frame = ImageFrame::new(&ColorChannelLayout::RGB, &ColorSpace::Gamma, &(64, 48)).unwrap();
+    /// let lossless = frame.as_new_feagi_byte_structure().unwrap();
+    /// let quantized = frame.as_new_feagi_byte_structure_quantized(ImageFramePixelQuantization::U8).unwrap();
+    /// assert!(quantized.borrow_data_as_slice().len() < lossless.borrow_data_as_slice().len());
+    ///
+    /// let received = ImageFrame::new_from_feagi_byte_structure(&quantized).unwrap();
+    /// assert_eq!(received.get_image_frame_properties(), frame.get_image_frame_properties());
+    /// ```
+    pub fn as_new_feagi_byte_structure_quantized(&self, pixel_quantization: ImageFramePixelQuantization) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
+        let mut bytes: Vec<u8> = vec![0; self.number_bytes_needed_quantized(pixel_quantization)];
+        _ = self.overwrite_feagi_byte_structure_slice_quantized(&mut bytes, pixel_quantization)?;
+        FeagiByteStructure::create_from_bytes(bytes)
+    }
+
+    /// Writes this frame into the given slice with the given pixel quantization.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - The number of bytes at the end of the slice left unused
+    /// * `Err(FeagiDataProcessingError)` - If the slice is too small
+    pub fn overwrite_feagi_byte_structure_slice_quantized(&self, slice: &mut [u8], pixel_quantization: ImageFramePixelQuantization) -> Result<usize, FeagiDataProcessingError> {
+        let num_bytes_needed = self.number_bytes_needed_quantized(pixel_quantization);
+        if slice.len() < num_bytes_needed {
+            return Err(IODataError::InvalidInplaceOperation(format!("Not enough space given to store image frame! Need {} bytes but given {}!", num_bytes_needed, slice.len())).into());
+        }
+        let (width, height) = self.get_cartesian_width_height();
+        let header_start = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES;
+        slice[0] = self.get_type() as u8;
+        slice[1] = self.get_version();
+        LittleEndian::write_u32(&mut slice[header_start..header_start + 4], width as u32);
+        LittleEndian::write_u32(&mut slice[header_start + 4..header_start + 8], height as u32);
+        slice[header_start + 8] = *self.get_channel_layout() as u8;
+        slice[header_start + 9] = color_space_id(self.get_color_space());
+        slice[header_start + 10] = pixel_quantization.id();
+        pixel_quantization.write_pixels(self.get_internal_data(), &mut slice[Self::BYTE_STRUCT_HEADER_SIZE..num_bytes_needed]);
+        Ok(slice.len() - num_bytes_needed)
+    }
+
+    /// Returns the exact number of bytes needed to serialize this frame with the given quantization.
+    pub fn number_bytes_needed_quantized(&self, pixel_quantization: ImageFramePixelQuantization) -> usize {
+        Self::BYTE_STRUCT_HEADER_SIZE + self.get_internal_data().len() * pixel_quantization.number_bytes_per_value()
+    }
+}
+
+//endregion
+
+//region SegmentedImageFrame
+
+impl FeagiByteStructureCompatible for SegmentedImageFrame {
+    fn get_type(&self) -> FeagiByteStructureType { FeagiByteStructureType::SegmentedImageFrame }
+
+    fn get_version(&self) -> u8 { FeagiByteStructure::SUPPORTED_VERSION_SEGMENTED_IMAGE_FRAME }
+
+    fn overwrite_feagi_byte_structure_slice(&self, slice: &mut [u8]) -> Result<usize, FeagiDataProcessingError> {
+        self.overwrite_feagi_byte_structure_slice_quantized(slice, ImageFramePixelQuantization::Float32)
+    }
+
+    fn max_number_bytes_needed(&self) -> usize {
+        self.number_bytes_needed_quantized(ImageFramePixelQuantization::Float32)
+    }
+
+    fn new_from_feagi_byte_structure(feagi_byte_structure: &FeagiByteStructure) -> Result<Self, FeagiDataProcessingError> {
+        FeagiByteStructure::verify_matching_structure_type_and_version(feagi_byte_structure,
+                                                                       FeagiByteStructureType::SegmentedImageFrame,
+                                                                       FeagiByteStructure::SUPPORTED_VERSION_SEGMENTED_IMAGE_FRAME)?;
+        let bytes = feagi_byte_structure.borrow_data_as_slice();
+        if bytes.len() < SegmentedImageFrame::BYTE_STRUCT_HEADER_SIZE {
+            return Err(FeagiBytesError::UnableToDeserializeBytes("Segmented image frame byte structure too short to contain its header!".into()).into());
+        }
+        let header_start = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES;
+        let color_space = read_color_space(bytes[header_start])?;
+        let center_channel_layout = read_channel_layout(bytes[header_start + 1])?;
+        let peripheral_channel_layout = read_channel_layout(bytes[header_start + 2])?;
+        let quantization = ImageFramePixelQuantization::try_from_id(bytes[header_start + 3])?;
+
+        let mut resolutions: [(usize, usize); 9] = [(0, 0); 9];
+        let mut resolution_read_index = header_start + 4;
+        for resolution in resolutions.iter_mut() {
+            *resolution = (
+                LittleEndian::read_u32(&bytes[resolution_read_index..resolution_read_index + 4]) as usize,
+                LittleEndian::read_u32(&bytes[resolution_read_index + 4..resolution_read_index + 8]) as usize,
+            );
+            resolution_read_index += 8;
+        }
+        // Stored in cortical order, see SegmentedFrameTargetResolutions::as_ordered_array
+        let [lower_left, lower_middle, lower_right, middle_left, center, middle_right, upper_left, upper_middle, upper_right] = resolutions;
+        let segment_resolutions = SegmentedFrameTargetResolutions::new(lower_left, middle_left, upper_left, upper_middle,
+                                                                      upper_right, middle_right, lower_right, lower_middle, center)
+            .map_err(|_| FeagiBytesError::UnableToDeserializeBytes("Segmented image frame byte structure has a segment resolution of 0!".into()))?;
+
+        // The resolutions are untrusted, so the pixel count is checked before allocating the segments
+        let pixel_bytes = &bytes[SegmentedImageFrame::BYTE_STRUCT_HEADER_SIZE..];
+        let expected_pixel_bytes = resolutions.iter().enumerate()
+            .try_fold(0usize, |total, (index, resolution)| {
+                let channel_layout = if index == 4 { center_channel_layout } else { peripheral_channel_layout };
+                total.checked_add(number_pixel_bytes(*resolution, channel_layout, quantization)?)
+            })
+            .ok_or_else(|| FeagiBytesError::UnableToDeserializeBytes("Segmented image frame byte structure has impossibly large segment resolutions!".into()))?;
+        if pixel_bytes.len() != expected_pixel_bytes {
+            return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Segmented image frame byte structure should contain {} bytes of pixels but contains {}!", expected_pixel_bytes, pixel_bytes.len())).into());
+        }
+        let mut segmented_image_frame = SegmentedImageFrame::new(&segment_resolutions, &color_space, &center_channel_layout, &peripheral_channel_layout)?;
+        let mut pixel_read_index: usize = 0;
+        for pixels in segmented_image_frame.get_image_internal_data_mut() {
+            let segment_length = pixels.len() * quantization.number_bytes_per_value();
+            quantization.read_pixels(&pixel_bytes[pixel_read_index..pixel_read_index + segment_length], pixels);
+            pixel_read_index += segment_length;
+        }
+        Ok(segmented_image_frame)
+    }
+}
+
+impl SegmentedImageFrame {
+    /// Size of the header of a segmented image frame byte structure, including the global header.
+    const BYTE_STRUCT_HEADER_SIZE: usize = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + 4 + (9 * 8);
+
+    /// Serializes this segmented frame into a new byte structure with the given pixel quantization.
+    pub fn as_new_feagi_byte_structure_quantized(&self, pixel_quantization: ImageFramePixelQuantization) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
+        let mut bytes: Vec<u8> = vec![0; self.number_bytes_needed_quantized(pixel_quantization)];
+        _ = self.overwrite_feagi_byte_structure_slice_quantized(&mut bytes, pixel_quantization)?;
+        FeagiByteStructure::create_from_bytes(bytes)
+    }
+
+    /// Writes this segmented frame into the given slice with the given pixel quantization.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - The number of bytes at the end of the slice left unused
+    /// * `Err(FeagiDataProcessingError)` - If the slice is too small
+    pub fn overwrite_feagi_byte_structure_slice_quantized(&self, slice: &mut [u8], pixel_quantization: ImageFramePixelQuantization) -> Result<usize, FeagiDataProcessingError> {
+        let num_bytes_needed = self.number_bytes_needed_quantized(pixel_quantization);
+        if slice.len() < num_bytes_needed {
+            return Err(IODataError::InvalidInplaceOperation(format!("Not enough space given to store segmented image frame! Need {} bytes but given {}!", num_bytes_needed, slice.len())).into());
+        }
+        let header_start = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES;
+        slice[0] = self.get_type() as u8;
+        slice[1] = self.get_version();
+        slice[header_start] = color_space_id(self.get_color_space());
+        slice[header_start + 1] = *self.get_center_channel_layout() as u8;
+        slice[header_start + 2] = *self.get_peripheral_channel_layout() as u8;
+        slice[header_start + 3] = pixel_quantization.id();
+
+        let ordered_frames = self.get_ordered_image_frame_references();
+        let mut resolution_write_index = header_start + 4;
+        let mut pixel_write_index = Self::BYTE_STRUCT_HEADER_SIZE;
+        for frame in ordered_frames {
+            let (width, height) = frame.get_cartesian_width_height();
+            LittleEndian::write_u32(&mut slice[resolution_write_index..resolution_write_index + 4], width as u32);
+            LittleEndian::write_u32(&mut slice[resolution_write_index + 4..resolution_write_index + 8], height as u32);
+            resolution_write_index += 8;
+
+            let segment_length = frame.get_internal_data().len() * pixel_quantization.number_bytes_per_value();
+            pixel_quantization.write_pixels(frame.get_internal_data(), &mut slice[pixel_write_index..pixel_write_index + segment_length]);
+            pixel_write_index += segment_length;
+        }
+        Ok(slice.len() - num_bytes_needed)
+    }
+
+    /// Returns the exact number of bytes needed to serialize this segmented frame with the given quantization.
+    pub fn number_bytes_needed_quantized(&self, pixel_quantization: ImageFramePixelQuantization) -> usize {
+        let number_values: usize = self.get_image_internal_data().iter().map(|pixels| pixels.len()).sum();
+        Self::BYTE_STRUCT_HEADER_SIZE + number_values * pixel_quantization.number_bytes_per_value()
+    }
+}
+
+//endregion
+
+//region Helpers
+
+const COLOR_SPACE_ID_LINEAR: u8 = 0;
+const COLOR_SPACE_ID_GAMMA: u8 = 1;
+
+fn color_space_id(color_space: &ColorSpace) -> u8 {
+    match color_space {
+        ColorSpace::Linear => COLOR_SPACE_ID_LINEAR,
+        ColorSpace::Gamma => COLOR_SPACE_ID_GAMMA,
+    }
+}
+
+fn read_color_space(id: u8) -> Result<ColorSpace, FeagiBytesError> {
+    match id {
+        COLOR_SPACE_ID_LINEAR => Ok(ColorSpace::Linear),
+        COLOR_SPACE_ID_GAMMA => Ok(ColorSpace::Gamma),
+        _ => Err(FeagiBytesError::UnableToDeserializeBytes(format!("Unknown color space {} in image byte structure!", id))),
+    }
+}
+
+fn read_channel_layout(id: u8) -> Result<ColorChannelLayout, FeagiBytesError> {
+    ColorChannelLayout::try_from(id as usize)
+        .map_err(|_| FeagiBytesError::UnableToDeserializeBytes(format!("Unknown color channel layout {} in image byte structure!", id)))
+}
+
+//endregion
+
+/// Number of bytes the pixels of an image of the given resolution take, or None on overflow.
+fn number_pixel_bytes(xy_resolution: (usize, usize), channel_layout: ColorChannelLayout, quantization: ImageFramePixelQuantization) -> Option<usize> {
+    xy_resolution.0.checked_mul(xy_resolution.1)?
+        .checked_mul(usize::from(channel_layout))?
+        .checked_mul(quantization.number_bytes_per_value())
+}
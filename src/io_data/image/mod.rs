@@ -18,6 +18,7 @@
 //! - Various channel layouts (Grayscale, RGB, RGBA)
 //! - Image operations (cropping, resizing, brightness/contrast)
 //! - Neural data conversion for FEAGI processing
+//! - Byte structure serialization of raw pixels, optionally quantized to u8
 //!
 //! ## Peripheral Vision Simulation
 //! - 3x3 segmented vision with center focus and peripheral regions
@@ -30,8 +31,10 @@ mod segmented_image_frame;
 pub mod descriptors;
 mod image_frame_transformer;
 mod image_frame_segmentator;
+mod image_frame_byte_structures;
 
 pub use segmented_image_frame::SegmentedImageFrame;
 pub use image_frame::ImageFrame;
 pub use image_frame_transformer::ImageFrameTransformer;
 pub use image_frame_segmentator::ImageFrameSegmentator;
+pub use image_frame_byte_structures::ImageFramePixelQuantization;
@@ -255,15 +255,17 @@ impl SegmentedImageFrame {
     }
 
     pub fn get_ordered_image_frame_references(&self) -> [&ImageFrame; 9] {
-        [&self.center, &self.lower_left, &self.middle_left, &self.upper_left, &self.upper_middle,
-            &self.upper_right, &self.middle_right, &self.lower_right,
-            &self.lower_middle]
+        // return in same order as cortical IDs
+        [&self.lower_left, &self.lower_middle, &self.lower_right, &self.middle_left, &self.center,
+            &self.middle_right, &self.upper_left, &self.upper_middle,
+            &self.upper_right]
     }
 
     pub fn get_mut_ordered_image_frame_references(&mut self) -> [&mut ImageFrame; 9] {
-        [&mut self.center, &mut self.lower_left, &mut self.middle_left, &mut self.upper_left, &mut self.upper_middle,
-            &mut self.upper_right, &mut self.middle_right, &mut self.lower_right,
-            &mut self.lower_middle]
+        // return in same order as cortical IDs
+        [&mut self.lower_left, &mut self.lower_middle, &mut self.lower_right, &mut self.middle_left, &mut self.center,
+            &mut self.middle_right, &mut self.upper_left, &mut self.upper_middle,
+            &mut self.upper_right]
     }
     
     pub(crate) fn get_image_internal_data_mut(&mut self) -> [&mut Array3<f32>; 9] {
@@ -283,7 +285,7 @@ impl SegmentedImageFrame {
     
     //endregion
     
-    //region neuron export
+    //region neuron export / import
     pub fn write_as_neuron_xyzp_data(&self, write_target: &mut CorticalMappedXYZPNeuronData, channel_index: CorticalIOChannelIndex, ordered_cortical_ids: &[CorticalID; 9]) -> Result<(), FeagiDataProcessingError> {
         let ordered_refs: [&ImageFrame; 9] = self.get_ordered_image_frame_references();
         for index in 0..9 {
@@ -292,6 +294,15 @@ impl SegmentedImageFrame {
         Ok(())
     }
     
+    pub fn overwrite_from_neuron_xyzp_data(&mut self, read_from: &CorticalMappedXYZPNeuronData, channel_index: CorticalIOChannelIndex, ordered_cortical_ids: &[CorticalID; 9]) -> Result<(), FeagiDataProcessingError> {
+        // Must pair segments with cortical IDs in the same order as write_as_neuron_xyzp_data
+        let ordered_refs: [&mut ImageFrame; 9] = self.get_mut_ordered_image_frame_references();
+        for (image_frame, cortical_id) in ordered_refs.into_iter().zip(ordered_cortical_ids.iter()) {
+            image_frame.overwrite_from_neuron_xyzp_data(read_from, *cortical_id, channel_index)?;
+        }
+        Ok(())
+    }
+    
     //endregion
     
     //region internal functions
@@ -6,10 +6,9 @@
 
 use byteorder::{ByteOrder, LittleEndian};
 use crate::error::{FeagiBytesError, FeagiDataProcessingError, IODataError};
-use crate::io_data::FeagiJSON;
-use crate::neuron_data::xyzp::CorticalMappedXYZPNeuronData; 
 use super::FeagiByteStructureType;
 use super::FeagiByteStructureCompatible;
+use super::FeagiByteStructurePayload;
 
 /// Core container for validated FEAGI byte structure data.
 ///
@@ -34,11 +33,15 @@ use super::FeagiByteStructureCompatible;
 ///
 /// ## Multi-Structure Container Format
 /// ```text
-/// [Type=9 (1)][Version (1)][Count (1)][Headers...][Data...]
+/// Version 1: [Type=9 (1)][Version=1 (1)][Count (1)][Headers...][Data...]
+/// Version 2: [Type=9 (1)][Version=2 (1)][Count (4)][Headers...][Data...]
 /// 
 /// Where Headers = [Start₁ (4)][Length₁ (4)][Start₂ (4)][Length₂ (4)]...
 /// And Data = [Structure₁][Structure₂]...
 /// ```
+///
+/// Version 2 containers may hold more than 255 structures, and may contain other multi-structure
+/// containers as children.
 /// 
 /// # Thread Safety
 ///
@@ -46,7 +49,7 @@ use super::FeagiByteStructureCompatible;
 /// (like capacity management) require exclusive access.
 #[derive(Clone)]
 pub struct FeagiByteStructure {
-    bytes: Vec<u8>,
+    pub(super) bytes: Vec<u8>,
 }
 
 impl FeagiByteStructure {
@@ -56,8 +59,17 @@ impl FeagiByteStructure {
     /// Minimum byte length required for a valid FEAGI byte structure.
     pub const MINIMUM_LENGTH_TO_BE_CONSIDERED_VALID: usize = 4;
     
-    /// Size of the structure count field in multi-structure containers.
+    /// Size of the structure count field in version 1 multi-structure containers.
     pub const MULTISTRUCT_STRUCT_COUNT_BYTE_SIZE: usize = 1;
+
+    /// Size of the structure count field in version 2 multi-structure containers.
+    pub const MULTISTRUCT_V2_STRUCT_COUNT_BYTE_SIZE: usize = 4;
+
+    /// Maximum number of structures a version 1 multi-structure container can hold.
+    pub const MULTISTRUCT_V1_MAX_STRUCT_COUNT: usize = u8::MAX as usize;
+
+    /// Maximum depth of nested multi-structure containers that will be walked.
+    pub const MULTISTRUCT_MAX_NESTING_DEPTH: usize = 32;
     
     /// Size of each structure's header entry in multi-structure containers (start position + length).
     pub const MULTISTRUCT_PER_STRUCT_HEADER_SIZE_IN_BYTES: usize = 8;
@@ -65,12 +77,24 @@ impl FeagiByteStructure {
     /// Currently supported version for JSON format structures.
     pub const SUPPORTED_VERSION_JSON: u8 = 1;
     
-    /// Currently supported version for multi-structure containers.
+    /// Currently supported version for multi-structure containers with up to 255 flattened structures.
     pub const SUPPORTED_VERSION_MULTI_STRUCT: u8 = 1;
+
+    /// Currently supported version for large and nested multi-structure containers.
+    pub const SUPPORTED_VERSION_MULTI_STRUCT_V2: u8 = 2;
     
     /// Currently supported version for neuron XYZP format structures.
     pub const SUPPORTED_VERSION_NEURON_XYZP: u8 = 1;
     
+    /// Currently supported version for compressed neuron XYZP format structures.
+    pub const SUPPORTED_VERSION_NEURON_XYZP_COMPRESSED: u8 = 1;
+
+    /// Currently supported version for image frame structures.
+    pub const SUPPORTED_VERSION_IMAGE_FRAME: u8 = 1;
+
+    /// Currently supported version for segmented image frame structures.
+    pub const SUPPORTED_VERSION_SEGMENTED_IMAGE_FRAME: u8 = 1;
+    
     //region Constructors
     
     /// Creates a new FeagiByteStructure from raw byte data with full validation.
@@ -88,12 +112,16 @@ impl FeagiByteStructure {
     ///   - Insufficient data length (< 4 bytes minimum)
     ///   - Invalid structure type identifier
     ///   - Version number of 0 (reserved/invalid)
+    ///   - CRC32 mismatch or authentication tag of a sealed structure (`FeagiBytesError::IntegrityCheckFailed`)
     ///   - Format-specific validation failures
     ///
     /// # Validation Performed
     /// - Minimum length check (at least 4 bytes)
     /// - Valid structure type identifier (byte 0)
     /// - Non-zero version number (byte 1)
+    /// - For `IntegritySealed` envelopes, the CRC32 of the wrapped structure. The wrapped
+    ///   structure is returned in place of the envelope. Envelopes carrying an authentication tag
+    ///   are rejected, as there is no key to verify it with, use `create_from_bytes_authenticated` for those
     /// - Additional format-specific validations may be added
     ///
     /// # Example
@@ -108,6 +136,9 @@ impl FeagiByteStructure {
         }
         _ = FeagiByteStructureType::try_from(bytes[0])?; // check if structure type is valid
         if bytes[1] == 0 {return Err(FeagiBytesError::UnableToValidateBytes("Byte structure cannot have version number of 0!".into()).into());}
+        if bytes[0] == FeagiByteStructureType::IntegritySealed as u8 {
+            return FeagiByteStructure::unseal(bytes, None);
+        }
         // NOTE: Other checks go here
         
         Ok(Self { bytes })
@@ -150,18 +181,21 @@ impl FeagiByteStructure {
     /// * `Ok(FeagiByteStructure)` - Multi-structure container with all inputs
     /// * `Err(FeagiDataProcessingError)` - If creation fails due to:
     ///   - Empty input vector (at least one structure required)
-    ///   - Too many structures (maximum 255 supported)
+    ///   - Too many structures (more than `u32::MAX`)
     ///   - Memory allocation failures
     ///
     /// # Behavior
     /// - **Single Input**: Returns a clone of the input (no container needed)
     /// - **Multiple Inputs**: Creates a new multi-structure container
-    /// - **Flattening**: Any input multi-structures are flattened into individual structures
+    /// - **Flattening**: Any input multi-structures, including nested ones, are flattened into
+    ///   their individual leaf structures
     /// - **Ordering**: Output order matches input order, with multi-structure contents expanded in place
     ///
-    /// # Container Limits
-    /// - Maximum of 255 individual structures in the final container
-    /// - This limit is enforced after flattening all input multi-structures
+    /// # Container Versions
+    /// - Up to 255 structures (after flattening) are written as a version 1 container, which
+    ///   older receivers can read
+    /// - More structures are written as a version 2 container
+    /// - Use `create_nested_from_multiple_existing` to keep input multi-structures intact
     pub fn create_from_multiple_existing(existing: Vec<&FeagiByteStructure>) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
         
         if existing.is_empty() {
@@ -173,23 +207,48 @@ impl FeagiByteStructure {
             return Ok(existing[0].clone());
         }
         
-        // Break apart any input multistructs, we don't want nesting. Assuming FeagiByteStructures are Valid
-        let mut slices: Vec<&[u8]> = Vec::new();
-        for input in existing {
-            if input.is_multistruct()? {
-                slices.extend(input.get_all_multistruct_internal_slices())
-            }
-            else {
-                slices.push(input.borrow_data_as_slice())
-            }
+        let slices = FeagiByteStructure::flatten_into_slices(&existing)?;
+        if slices.len() <= Self::MULTISTRUCT_V1_MAX_STRUCT_COUNT {
+            return FeagiByteStructure::build_multistruct_from_slices(slices, Self::SUPPORTED_VERSION_MULTI_STRUCT);
         }
+        FeagiByteStructure::build_multistruct_from_slices(slices, Self::SUPPORTED_VERSION_MULTI_STRUCT_V2)
+    }
 
-        if slices.len() > 255 {
-            // wtf are you doing
-            return Err(IODataError::InvalidParameters("The maximum number of structures that can exist in a multistruct is 255!".into()).into());
+    /// Creates a version 2 multi-structure container holding the given structures as is.
+    ///
+    /// Unlike `create_from_multiple_existing`, input multi-structures are not flattened but kept
+    /// as nested children, so for example a relay can batch the containers of many agents while
+    /// keeping them apart.
+    ///
+    /// # Arguments
+    /// * `existing` - Vector of references to structures to hold as children
+    ///
+    /// # Returns
+    /// * `Ok(FeagiByteStructure)` - Version 2 multi-structure container with one child per input
+    /// * `Err(FeagiDataProcessingError)` - If no structures were given, or too many
+    ///
+    /// # Example
+    /// ```rust
+    /// use feagi_core_data_structures_and_processing::io_processing::byte_structures::FeagiByteStructure;
+    ///
+    /// let json = FeagiByteStructure::create_from_bytes(vec![1, 1, 123, 125]).unwrap();
+    /// let agent_a = FeagiByteStructure::create_from_2_existing(&json, &json).unwrap();
+    /// let relay = FeagiByteStructure::create_nested_from_multiple_existing(vec![&agent_a, &json]).unwrap();
+    /// assert_eq!(relay.contained_structure_count().unwrap(), 2);
+    ///
+    /// let mut leaf_paths: Vec<Vec<usize>> = Vec::new();
+    /// relay.visit_leaf_structures(|path, _leaf| {
+    ///     leaf_paths.push(path.to_vec());
+    ///     Ok(())
+    /// }).unwrap();
+    /// assert_eq!(leaf_paths, vec![vec![0, 0], vec![0, 1], vec![1]]);
+    /// ```
+    pub fn create_nested_from_multiple_existing(existing: Vec<&FeagiByteStructure>) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
+        if existing.is_empty() {
+            return Err(IODataError::InvalidParameters("You must specify at least one byte structure to put into a multistruct!".into()).into());
         }
-        
-        Ok(FeagiByteStructure::build_multistruct_from_slices(slices))
+        let slices: Vec<&[u8]> = existing.iter().map(|input| input.borrow_data_as_slice()).collect();
+        FeagiByteStructure::build_multistruct_from_slices(slices, Self::SUPPORTED_VERSION_MULTI_STRUCT_V2)
     }
     
     /// Creates a FeagiByteStructure from any compatible object.
@@ -315,13 +374,56 @@ impl FeagiByteStructure {
         if !self.is_multistruct()? {
             return Ok(self.clone());
         }
-        if index > self.contained_structure_count()? {
+        if index >= self.contained_structure_count()? {
             return Err(IODataError::InvalidParameters(format!("Given struct index {} is out of bounds given this multistruct only contains {} elements!", index, self.contained_structure_count()?)).into());
         }
+        self.verify_valid_multistruct_internal_positionings_header()?;
         Ok(FeagiByteStructure::create_from_bytes(
             self.get_multistruct_specific_slice(index).to_vec()
         )?)
     }
+
+    /// Copies out the structure at the given path through nested multi-structure containers.
+    ///
+    /// Each element of the path is the index of a child in the container reached so far. An
+    /// empty path returns a copy of this structure.
+    ///
+    /// # Returns
+    /// * `Ok(FeagiByteStructure)` - Copy of the structure at the path
+    /// * `Err(FeagiDataProcessingError)` - If an index is out of bounds, or the path continues
+    ///   past a structure that is not a multi-structure container
+    pub fn copy_out_structure_at_path(&self, path: &[usize]) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
+        let mut slice: &[u8] = &self.bytes;
+        for (depth, index) in path.iter().enumerate() {
+            if slice.first() != Some(&(FeagiByteStructureType::MultiStructHolder as u8)) {
+                return Err(IODataError::InvalidParameters(format!("Path element {} cannot index into a structure that is not a multistruct!", depth)).into());
+            }
+            let children = Self::get_verified_multistruct_child_slices(slice)?;
+            slice = match children.get(*index) {
+                Some(child) => child,
+                None => return Err(IODataError::InvalidParameters(format!("Given struct index {} at path element {} is out of bounds given this multistruct only contains {} elements!", index, depth, children.len())).into()),
+            };
+        }
+        FeagiByteStructure::create_from_bytes(slice.to_vec())
+    }
+
+    /// Walks every leaf (non multi-structure) structure, recursing into nested containers.
+    ///
+    /// The visitor is called in order with the path of child indices leading to each leaf, and
+    /// a borrowed view of that leaf. A single structure is visited once with an empty path.
+    /// Returning an error from the visitor stops the walk and returns that error.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If every leaf was visited
+    /// * `Err(FeagiDataProcessingError)` - If a container is malformed, nesting exceeds
+    ///   `MULTISTRUCT_MAX_NESTING_DEPTH`, or the visitor returned an error
+    pub fn visit_leaf_structures<F>(&self, mut visitor: F) -> Result<(), FeagiDataProcessingError>
+    where
+        F: FnMut(&[usize], FeagiByteStructureLeaf) -> Result<(), FeagiDataProcessingError>,
+    {
+        let mut path: Vec<usize> = Vec::new();
+        Self::visit_leaf_slices(&self.bytes, &mut path, &mut visitor)
+    }
     
     /// Extracts the original object from a single (non-multi-structure) byte structure.
     ///
@@ -340,28 +442,16 @@ impl FeagiByteStructure {
     /// # Supported Format Types
     /// - `JSON` → `JsonStructure`
     /// - `NeuronCategoricalXYZP` → `CorticalMappedXYZPNeuronData`
+    /// - `NeuronCategoricalXYZPCompressed` → `CorticalMappedXYZPNeuronData`
+    /// - `IntegritySealed` → the type of the wrapped structure
+    /// - `ImageFrame` → `ImageFrame`
+    /// - `SegmentedImageFrame` → `SegmentedImageFrame`
+    /// - `Capabilities` → `FeagiByteStructureCapabilities`
+    ///
+    /// Use `FeagiByteStructurePayload` to get the object back with its concrete type.
     pub fn copy_out_single_object_from_single_struct(&self) -> Result<Box<dyn FeagiByteStructureCompatible>, FeagiDataProcessingError> {
-        let this_struct_type = self.try_get_structure_type()?;
-        if this_struct_type == FeagiByteStructureType::MultiStructHolder {
-            return Err(FeagiBytesError::IncompatibleByteUse("Cannot return a multistruct holding multiple structs as a single object!".into()).into())
-        }
-        
-        // Factory pattern to create the appropriate concrete type based on structure type
-        match this_struct_type {
-            FeagiByteStructureType::JSON => {
-                Ok(Box::new(FeagiJSON::new_from_feagi_byte_structure(self)?))
-            },
-            FeagiByteStructureType::NeuronCategoricalXYZP => {
-                Ok(Box::new(CorticalMappedXYZPNeuronData::new_from_feagi_byte_structure(self)?))
-            },
-            FeagiByteStructureType::MultiStructHolder => {
-                // This case is already handled above, but included for completeness
-                Err(FeagiBytesError::IncompatibleByteUse("Cannot return a multistruct holding multiple structs as a single object!".into()).into())
-            }
-            //_ => {
-            //    Err(FeagiDataProcessingError::InternalError(format!("Missing export definition for FBS object type {}!", this_struct_type)))
-            //}
-        }
+        // Dispatch by type is done in one place, in FeagiByteStructurePayload
+        Ok(FeagiByteStructurePayload::try_from_feagi_byte_structure(self)?.into_boxed_compatible())
     }
     
     pub fn copy_out_single_object_from_multistruct(&self, index: usize) -> Result<Box<dyn FeagiByteStructureCompatible>, FeagiDataProcessingError> {
@@ -384,36 +474,50 @@ impl FeagiByteStructure {
     // safety checks/
     
     fn verify_valid_multistruct_internal_count(&self) -> Result<(), FeagiDataProcessingError> {
-        let len = self.bytes.len();
+        Self::verify_valid_multistruct_slice_internal_count(&self.bytes)
+    }
+    
+    fn verify_valid_multistruct_internal_positionings_header(&self) -> Result<(), FeagiDataProcessingError> {
+        Self::verify_valid_multistruct_slice_internal_positionings_header(&self.bytes)
+    }
+
+    fn verify_valid_multistruct_slice_internal_count(bytes: &[u8]) -> Result<(), FeagiDataProcessingError> {
+        let len = bytes.len();
         if len < Self::MINIMUM_LENGTH_TO_BE_CONSIDERED_VALID {
             return Err(FeagiDataProcessingError::InternalError("byte structure too short!".into()))
         }
-        if self.bytes[0] != FeagiByteStructureType::MultiStructHolder as u8 { // faster header check
+        if bytes[0] != FeagiByteStructureType::MultiStructHolder as u8 { // faster header check
             return Err(FeagiBytesError::UnableToValidateBytes("Byte structure is not identified as a multistruct!".into()).into())
         }
-        if self.bytes[2] == 0 {
+        if bytes[1] != Self::SUPPORTED_VERSION_MULTI_STRUCT && bytes[1] != Self::SUPPORTED_VERSION_MULTI_STRUCT_V2 {
+            return Err(FeagiBytesError::UnableToValidateBytes(format!("Multistruct version {} is not supported!", bytes[1])).into())
+        }
+        if len < Self::get_multistruct_slice_headers_start(bytes) {
+            return Err(FeagiBytesError::UnableToValidateBytes("Multistruct too short to hold its structure count!".into()).into())
+        }
+        if Self::get_multistruct_slice_contained_count(bytes) == 0 {
             return Err(FeagiDataProcessingError::InternalError("Multistruct reports 0 contained structures!".into()))
         }
         Ok(())
     }
-    
-    fn verify_valid_multistruct_internal_positionings_header(&self) -> Result<(), FeagiDataProcessingError> {
+
+    fn verify_valid_multistruct_slice_internal_positionings_header(bytes: &[u8]) -> Result<(), FeagiDataProcessingError> {
         // We are assuming the internal structure count was already verified as existing and valid ( not 0)
-        let len = self.bytes.len();
-        let contained_struct_count = self.bytes[2] as usize;
+        let len = bytes.len();
+        let contained_struct_count = Self::get_multistruct_slice_contained_count(bytes);
         if contained_struct_count == 0 {
             return Err(FeagiDataProcessingError::InternalError("Multistruct reports 0 contained structures!".into())); // Explicitly check for this again because if we dont, we are going to underflow below
         }
-        let header_size_bytes = contained_struct_count * Self::MULTISTRUCT_PER_STRUCT_HEADER_SIZE_IN_BYTES;
-        if len <  Self::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + Self::MULTISTRUCT_STRUCT_COUNT_BYTE_SIZE + header_size_bytes {
+        let headers_start = Self::get_multistruct_slice_headers_start(bytes);
+        let header_size_bytes = contained_struct_count.saturating_mul(Self::MULTISTRUCT_PER_STRUCT_HEADER_SIZE_IN_BYTES);
+        if len < headers_start.saturating_add(header_size_bytes) {
             return Err(FeagiDataProcessingError::InternalError("Multi Struct too short to hold contained positionings header!".into()))
         } 
         
-        let len = len as u32;
-        let mut struct_header_start_index = Self::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + Self::MULTISTRUCT_STRUCT_COUNT_BYTE_SIZE;
+        let mut struct_header_start_index = headers_start;
         for _ in 0..contained_struct_count {
-            let struct_start_index = LittleEndian::read_u32(&self.bytes[struct_header_start_index..struct_header_start_index + 4]);
-            let struct_length = LittleEndian::read_u32(&self.bytes[struct_header_start_index + 4..struct_header_start_index + 8]);
+            let struct_start_index = LittleEndian::read_u32(&bytes[struct_header_start_index..struct_header_start_index + 4]) as usize;
+            let struct_length = LittleEndian::read_u32(&bytes[struct_header_start_index + 4..struct_header_start_index + 8]) as usize;
             if struct_start_index + struct_length > len {
                 return Err(FeagiDataProcessingError::InternalError("Multi Struct too short to hold all reported contained structures!".into()))
             }
@@ -498,7 +602,7 @@ impl FeagiByteStructure {
         }
 
         if self.bytes.capacity() < size {
-            //self.bytes.reserve(size - self.bytes.capacity());
+            self.bytes.reserve(size - self.bytes.len());
         }
         Ok(())
     }
@@ -516,17 +620,34 @@ impl FeagiByteStructure {
     //region Internals
     // WARNING: Most of these functions do not check for byte structure validity, be cautious
 
-    fn build_multistruct_from_slices(all_slices: Vec<&[u8]>) -> FeagiByteStructure {
+    pub(super) fn flatten_into_slices<'a>(existing: &[&'a FeagiByteStructure]) -> Result<Vec<&'a [u8]>, FeagiDataProcessingError> {
+        // Break apart any input multistructs down to their leaves, we don't want nesting
+        let mut slices: Vec<&[u8]> = Vec::new();
+        let mut path: Vec<usize> = Vec::new();
+        for input in existing {
+            Self::visit_leaf_slices(&input.bytes, &mut path, &mut |_path, leaf| {
+                slices.push(leaf.bytes);
+                Ok(())
+            })?;
+        }
+        Ok(slices)
+    }
+
+    pub(super) fn build_multistruct_from_slices(all_slices: Vec<&[u8]>, version: u8) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
         // NOTE: does not check if internal slices are sensible
         let slice_count = all_slices.len();
+        let count_byte_size = if version == Self::SUPPORTED_VERSION_MULTI_STRUCT { Self::MULTISTRUCT_STRUCT_COUNT_BYTE_SIZE } else { Self::MULTISTRUCT_V2_STRUCT_COUNT_BYTE_SIZE };
         let mut total_slices_byte_count: usize = 0;
         for slice in &all_slices {
             total_slices_byte_count += slice.len();
         };
-        let header_output_length = Self::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + Self::MULTISTRUCT_STRUCT_COUNT_BYTE_SIZE +
+        let header_output_length = Self::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + count_byte_size +
             (Self::MULTISTRUCT_PER_STRUCT_HEADER_SIZE_IN_BYTES * slice_count);
         
         let total_output_length = header_output_length + total_slices_byte_count;
+        if slice_count > u32::MAX as usize || total_output_length > u32::MAX as usize {
+            return Err(IODataError::InvalidParameters("Multistruct cannot exceed u32::MAX structures or bytes!".into()).into());
+        }
         
         // Write output data
         let mut output_bytes: Vec<u8> = Vec::with_capacity(total_output_length);
@@ -534,13 +655,17 @@ impl FeagiByteStructure {
         
         // global header
         output_bytes[0] = FeagiByteStructureType::MultiStructHolder as u8;
-        output_bytes[1] = Self::SUPPORTED_VERSION_MULTI_STRUCT;
+        output_bytes[1] = version;
         
         // struct count subheader
-        output_bytes[2] = slice_count as u8;
+        if version == Self::SUPPORTED_VERSION_MULTI_STRUCT {
+            output_bytes[2] = slice_count as u8;
+        } else {
+            LittleEndian::write_u32(&mut output_bytes[2..2 + Self::MULTISTRUCT_V2_STRUCT_COUNT_BYTE_SIZE], slice_count as u32);
+        }
         
         // subheader + data
-        let mut subheader_write_index: usize =  Self::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + Self::MULTISTRUCT_STRUCT_COUNT_BYTE_SIZE;
+        let mut subheader_write_index: usize =  Self::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + count_byte_size;
         let mut data_write_index: usize = header_output_length; // start right after header
         
         for slice in &all_slices {
@@ -564,36 +689,119 @@ impl FeagiByteStructure {
         };
         
         // Skip any checks and instantiate directly
-        FeagiByteStructure {bytes: output_bytes}
+        Ok(FeagiByteStructure {bytes: output_bytes})
     }
     
     fn get_multistruct_contained_count(&self) -> usize {
         // NOTE no safety checks, make sure your vector is a valid multistruct
-        self.bytes[2] as usize
+        Self::get_multistruct_slice_contained_count(&self.bytes)
     }
 
     fn get_multistruct_specific_slice(&self, index: usize) -> &[u8] {
         // WARNING: No boundary checks, be careful!
-        let reading_offset = Self::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + Self::MULTISTRUCT_STRUCT_COUNT_BYTE_SIZE +
-            (index * Self::MULTISTRUCT_PER_STRUCT_HEADER_SIZE_IN_BYTES);
-        let struct_start = LittleEndian::read_u32(&self.bytes[reading_offset..reading_offset + 4]) as usize;
-        let struct_length = LittleEndian::read_u32(&self.bytes[reading_offset + 4..reading_offset + 8]) as usize;
-        &self.bytes[struct_start..struct_start + struct_length]
+        Self::get_multistruct_slice_specific_slice(&self.bytes, index)
     }
     
     fn get_all_multistruct_internal_slices(&self) -> Vec<&[u8]> {
-        let mut output = Vec::with_capacity(self.get_multistruct_contained_count());
-        for i in 0..self.get_multistruct_contained_count() {
-            output.push(self.get_multistruct_specific_slice(i));
+        Self::get_all_multistruct_slice_internal_slices(&self.bytes)
+    }
+
+    fn get_multistruct_slice_headers_start(bytes: &[u8]) -> usize {
+        if bytes[1] == Self::SUPPORTED_VERSION_MULTI_STRUCT {
+            Self::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + Self::MULTISTRUCT_STRUCT_COUNT_BYTE_SIZE
+        } else {
+            Self::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + Self::MULTISTRUCT_V2_STRUCT_COUNT_BYTE_SIZE
+        }
+    }
+
+    fn get_multistruct_slice_contained_count(bytes: &[u8]) -> usize {
+        // NOTE no safety checks, make sure your slice is a valid multistruct
+        if bytes[1] == Self::SUPPORTED_VERSION_MULTI_STRUCT {
+            bytes[2] as usize
+        } else {
+            LittleEndian::read_u32(&bytes[2..2 + Self::MULTISTRUCT_V2_STRUCT_COUNT_BYTE_SIZE]) as usize
+        }
+    }
+
+    fn get_multistruct_slice_specific_slice(bytes: &[u8], index: usize) -> &[u8] {
+        // WARNING: No boundary checks, be careful!
+        let reading_offset = Self::get_multistruct_slice_headers_start(bytes) + (index * Self::MULTISTRUCT_PER_STRUCT_HEADER_SIZE_IN_BYTES);
+        let struct_start = LittleEndian::read_u32(&bytes[reading_offset..reading_offset + 4]) as usize;
+        let struct_length = LittleEndian::read_u32(&bytes[reading_offset + 4..reading_offset + 8]) as usize;
+        &bytes[struct_start..struct_start + struct_length]
+    }
+
+    fn get_all_multistruct_slice_internal_slices(bytes: &[u8]) -> Vec<&[u8]> {
+        let count = Self::get_multistruct_slice_contained_count(bytes);
+        let mut output = Vec::with_capacity(count);
+        for i in 0..count {
+            output.push(Self::get_multistruct_slice_specific_slice(bytes, i));
         }
         output
     }
+
+    fn get_verified_multistruct_child_slices(bytes: &[u8]) -> Result<Vec<&[u8]>, FeagiDataProcessingError> {
+        Self::verify_valid_multistruct_slice_internal_count(bytes)?;
+        Self::verify_valid_multistruct_slice_internal_positionings_header(bytes)?;
+        Ok(Self::get_all_multistruct_slice_internal_slices(bytes))
+    }
+
+    fn visit_leaf_slices<'a, F>(bytes: &'a [u8], path: &mut Vec<usize>, visitor: &mut F) -> Result<(), FeagiDataProcessingError>
+    where
+        F: FnMut(&[usize], FeagiByteStructureLeaf<'a>) -> Result<(), FeagiDataProcessingError>,
+    {
+        if bytes.len() < Self::MINIMUM_LENGTH_TO_BE_CONSIDERED_VALID {
+            return Err(FeagiBytesError::UnableToValidateBytes("Multi Struct contains a internal structure too small to be valid!".into()).into());
+        }
+        let structure_type = FeagiByteStructureType::try_from(bytes[0])?;
+        if structure_type != FeagiByteStructureType::MultiStructHolder {
+            return visitor(path, FeagiByteStructureLeaf { structure_type, bytes });
+        }
+        if path.len() >= Self::MULTISTRUCT_MAX_NESTING_DEPTH {
+            return Err(FeagiBytesError::UnableToValidateBytes(format!("Multistructs are nested deeper than the maximum of {}!", Self::MULTISTRUCT_MAX_NESTING_DEPTH)).into());
+        }
+        for (index, child) in Self::get_verified_multistruct_child_slices(bytes)?.into_iter().enumerate() {
+            path.push(index);
+            Self::visit_leaf_slices(child, path, visitor)?;
+            path.pop();
+        }
+        Ok(())
+    }
     
     
     //endregion
     
 }
 
+/// Borrowed leaf structure handed to the visitor of `FeagiByteStructure::visit_leaf_structures`.
+#[derive(Debug, Clone, Copy)]
+pub struct FeagiByteStructureLeaf<'a> {
+    structure_type: FeagiByteStructureType,
+    bytes: &'a [u8],
+}
+
+impl<'a> FeagiByteStructureLeaf<'a> {
+    /// Returns the format type of this leaf structure.
+    pub fn get_structure_type(&self) -> FeagiByteStructureType {
+        self.structure_type
+    }
+
+    /// Returns the format version of this leaf structure.
+    pub fn get_version(&self) -> u8 {
+        self.bytes[1]
+    }
+
+    /// Returns the complete bytes of this leaf structure, including its global header.
+    pub fn borrow_data_as_slice(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Copies this leaf out into its own validated byte structure.
+    pub fn copy_out_as_byte_structure(&self) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
+        FeagiByteStructure::create_from_bytes(self.bytes.to_vec())
+    }
+}
+
 /// Extracts the version number from raw byte data without full validation.
 ///
 /// This utility function reads the version number from the second byte of raw
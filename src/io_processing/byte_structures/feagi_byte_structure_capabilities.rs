@@ -0,0 +1,279 @@
+//! Capability exchange and version negotiation between FEAGI agents.
+//!
+//! Every structure is written at a single version, so an agent running a newer release of this
+//! crate may send structures an older peer cannot read. During a handshake, both sides send a
+//! `Capabilities` structure listing every `(FeagiByteStructureType, version)` pair they can read,
+//! along with the byte order they write in:
+//!
+//! ```text
+//! [Type=16 (1)][Version (1)][Byte Order Mark (u16, 0xFEFF in the sender's byte order)]
+//! [Pair Count (u16)][Pair Count x (Type (u8), Version (u8))]
+//! ```
+//!
+//! [`FeagiByteStructureCapabilities::negotiate`] then yields the capabilities both sides share,
+//! which are used to build outgoing structures the peer is guaranteed to read. Structure types
+//! unknown to this release are skipped when reading a peer's capabilities, so newer peers can
+//! still negotiate down to what both understand.
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use crate::error::{FeagiBytesError, FeagiDataProcessingError, IODataError};
+use super::{FeagiByteStructure, FeagiByteStructureCompatible, FeagiByteStructureType};
+
+/// The set of byte structure types and versions an agent can read.
+///
+/// # Example
+/// ```rust
+/// use feagi_core_data_structures_and_processing::io_processing::byte_structures::{FeagiByteStructure, FeagiByteStructureCapabilities, FeagiByteStructureCompatible, FeagiByteStructureType};
+///
+/// // An older peer, which only understands version 1 multistructs and JSON
+/// let mut remote = FeagiByteStructureCapabilities::new();
+/// remote.add_supported_version(FeagiByteStructureType::JSON, 1);
+/// remote.add_supported_version(FeagiByteStructureType::MultiStructHolder, 1);
+///
+/// // The handshake is itself a byte structure
+/// let handshake = remote.as_new_feagi_byte_structure().unwrap();
+/// let received = FeagiByteStructureCapabilities::new_from_feagi_byte_structure(&handshake).unwrap();
+///
+/// let agreed = FeagiByteStructureCapabilities::new_local().negotiate(&received).unwrap();
+/// assert_eq!(agreed.get_best_version(FeagiByteStructureType::MultiStructHolder), Some(1));
+/// assert_eq!(agreed.get_best_version(FeagiByteStructureType::NeuronCategoricalXYZPCompressed), None);
+///
+/// let json = FeagiByteStructure::create_from_bytes(vec![1, 1, 123, 125]).unwrap();
+/// let outgoing = agreed.create_outgoing_multistruct(vec![&json, &json]).unwrap();
+/// assert_eq!(outgoing.try_get_version().unwrap(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeagiByteStructureCapabilities {
+    /// Sorted by type ID then version, without duplicates.
+    supported: Vec<(FeagiByteStructureType, u8)>,
+    is_little_endian: bool,
+}
+
+impl Default for FeagiByteStructureCapabilities {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeagiByteStructureCapabilities {
+
+    /// Currently supported version for capability exchange structures.
+    pub const SUPPORTED_VERSION_CAPABILITIES: u8 = 1;
+
+    const BYTE_ORDER_MARK: u16 = 0xFEFF;
+    const BYTE_ORDER_MARK_OFFSET: usize = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES;
+    const PAIR_COUNT_OFFSET: usize = Self::BYTE_ORDER_MARK_OFFSET + 2;
+    const PAIRS_OFFSET: usize = Self::PAIR_COUNT_OFFSET + 2;
+    const PER_PAIR_SIZE_IN_BYTES: usize = 2;
+
+    /// Creates an empty set of capabilities, of a little endian agent.
+    pub fn new() -> Self {
+        FeagiByteStructureCapabilities {
+            supported: Vec::new(),
+            is_little_endian: true,
+        }
+    }
+
+    /// Creates the capabilities of this release of the crate.
+    pub fn new_local() -> Self {
+        let mut capabilities = Self::new();
+        capabilities.add_supported_version(FeagiByteStructureType::JSON, FeagiByteStructure::SUPPORTED_VERSION_JSON);
+        capabilities.add_supported_version(FeagiByteStructureType::MultiStructHolder, FeagiByteStructure::SUPPORTED_VERSION_MULTI_STRUCT);
+        capabilities.add_supported_version(FeagiByteStructureType::MultiStructHolder, FeagiByteStructure::SUPPORTED_VERSION_MULTI_STRUCT_V2);
+        capabilities.add_supported_version(FeagiByteStructureType::NeuronCategoricalXYZP, FeagiByteStructure::SUPPORTED_VERSION_NEURON_XYZP);
+        capabilities.add_supported_version(FeagiByteStructureType::NeuronCategoricalXYZPCompressed, FeagiByteStructure::SUPPORTED_VERSION_NEURON_XYZP_COMPRESSED);
+        capabilities.add_supported_version(FeagiByteStructureType::IntegritySealed, FeagiByteStructure::SUPPORTED_VERSION_INTEGRITY_SEALED);
+        capabilities.add_supported_version(FeagiByteStructureType::ImageFrame, FeagiByteStructure::SUPPORTED_VERSION_IMAGE_FRAME);
+        capabilities.add_supported_version(FeagiByteStructureType::SegmentedImageFrame, FeagiByteStructure::SUPPORTED_VERSION_SEGMENTED_IMAGE_FRAME);
+        capabilities.add_supported_version(FeagiByteStructureType::Capabilities, Self::SUPPORTED_VERSION_CAPABILITIES);
+        capabilities
+    }
+
+    /// Marks the given structure type and version as readable. Adding an existing pair does nothing.
+    pub fn add_supported_version(&mut self, structure_type: FeagiByteStructureType, version: u8) {
+        let key = (structure_type as u8, version);
+        if let Err(index) = self.supported.binary_search_by_key(&key, |(t, v)| (*t as u8, *v)) {
+            self.supported.insert(index, (structure_type, version));
+        }
+    }
+
+    /// Returns true if the given structure type and version is readable.
+    pub fn is_supported(&self, structure_type: FeagiByteStructureType, version: u8) -> bool {
+        self.supported.contains(&(structure_type, version))
+    }
+
+    /// Returns all readable versions of the given structure type, in ascending order.
+    pub fn get_supported_versions(&self, structure_type: FeagiByteStructureType) -> Vec<u8> {
+        self.supported.iter().filter(|(t, _)| *t == structure_type).map(|(_, v)| *v).collect()
+    }
+
+    /// Returns the highest readable version of the given structure type, if any.
+    pub fn get_best_version(&self, structure_type: FeagiByteStructureType) -> Option<u8> {
+        self.get_supported_versions(structure_type).last().copied()
+    }
+
+    /// Returns all readable structure type and version pairs, sorted by type ID then version.
+    pub fn get_supported_pairs(&self) -> &[(FeagiByteStructureType, u8)] {
+        &self.supported
+    }
+
+    /// Returns true if the agent these capabilities belong to writes little endian structures.
+    pub fn is_little_endian(&self) -> bool {
+        self.is_little_endian
+    }
+
+    /// Finds the capabilities shared by this agent and a remote peer.
+    ///
+    /// # Arguments
+    /// * `remote` - The capabilities received from the peer
+    ///
+    /// # Returns
+    /// * `Ok(FeagiByteStructureCapabilities)` - Every pair readable by both sides
+    /// * `Err(FeagiDataProcessingError)` - If either side does not use little endian, which is
+    ///   the only byte order structures are currently written in
+    pub fn negotiate(&self, remote: &FeagiByteStructureCapabilities) -> Result<FeagiByteStructureCapabilities, FeagiDataProcessingError> {
+        if !self.is_little_endian || !remote.is_little_endian {
+            return Err(FeagiBytesError::IncompatibleByteUse("Cannot negotiate with a big endian agent, byte structures are only written in little endian!".into()).into());
+        }
+        let supported = self.supported.iter().filter(|pair| remote.supported.contains(pair)).copied().collect();
+        Ok(FeagiByteStructureCapabilities {
+            supported,
+            is_little_endian: true,
+        })
+    }
+
+    /// Checks that a structure can be read by an agent with these capabilities.
+    ///
+    /// The top level structure and every leaf structure (within multistructs) are checked.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If every checked type and version is supported
+    /// * `Err(FeagiDataProcessingError)` - `FeagiBytesError::IncompatibleByteUse` naming the first
+    ///   unsupported structure
+    pub fn verify_structure_is_supported(&self, feagi_byte_structure: &FeagiByteStructure) -> Result<(), FeagiDataProcessingError> {
+        self.verify_pair_is_supported(feagi_byte_structure.try_get_structure_type()?, feagi_byte_structure.try_get_version()?)?;
+        feagi_byte_structure.visit_leaf_structures(|_path, leaf| {
+            self.verify_pair_is_supported(leaf.get_structure_type(), leaf.get_version())
+        })
+    }
+
+    /// Serializes an object for a peer with these (negotiated) capabilities.
+    ///
+    /// # Returns
+    /// * `Ok(FeagiByteStructure)` - The serialized object
+    /// * `Err(FeagiDataProcessingError)` - If the peer cannot read the type and version the object
+    ///   serializes to, or serialization fails
+    pub fn create_outgoing(&self, object: &dyn FeagiByteStructureCompatible) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
+        self.verify_pair_is_supported(object.get_type(), object.get_version())?;
+        object.as_new_feagi_byte_structure()
+    }
+
+    /// Combines structures into a multistruct of the best container version for a peer with
+    /// these (negotiated) capabilities.
+    ///
+    /// Inputs are flattened down to their leaf structures as with
+    /// `FeagiByteStructure::create_from_multiple_existing`, and each leaf is checked to be readable
+    /// by the peer. Input multistructs are always repacked, so the output never contains nested
+    /// containers or a container version the peer cannot read.
+    ///
+    /// # Returns
+    /// * `Ok(FeagiByteStructure)` - The combined structure, or a copy of the input if only one
+    ///   non multistruct was given
+    /// * `Err(FeagiDataProcessingError)` - If no inputs were given, the peer cannot read any leaf
+    ///   or any multistruct version, or there are more structures than the best version can hold
+    pub fn create_outgoing_multistruct(&self, existing: Vec<&FeagiByteStructure>) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
+        if existing.is_empty() {
+            return Err(IODataError::InvalidParameters("You must specify at least one byte structure to put into a multistruct!".into()).into());
+        }
+        for input in &existing {
+            input.visit_leaf_structures(|_path, leaf| {
+                self.verify_pair_is_supported(leaf.get_structure_type(), leaf.get_version())
+            })?;
+        }
+        if existing.len() == 1 && !existing[0].is_multistruct()? {
+            return Ok(existing[0].clone());
+        }
+
+        let version = match self.get_best_version(FeagiByteStructureType::MultiStructHolder) {
+            Some(version) if version >= FeagiByteStructure::SUPPORTED_VERSION_MULTI_STRUCT_V2 => FeagiByteStructure::SUPPORTED_VERSION_MULTI_STRUCT_V2,
+            Some(FeagiByteStructure::SUPPORTED_VERSION_MULTI_STRUCT) => FeagiByteStructure::SUPPORTED_VERSION_MULTI_STRUCT,
+            _ => return Err(FeagiBytesError::IncompatibleByteUse("Peer does not support any known multistruct version!".into()).into()),
+        };
+        let slices = FeagiByteStructure::flatten_into_slices(&existing)?;
+        if version == FeagiByteStructure::SUPPORTED_VERSION_MULTI_STRUCT && slices.len() > FeagiByteStructure::MULTISTRUCT_V1_MAX_STRUCT_COUNT {
+            return Err(FeagiBytesError::IncompatibleByteUse(format!("Peer only supports version 1 multistructs, which cannot hold {} structures!", slices.len())).into());
+        }
+        FeagiByteStructure::build_multistruct_from_slices(slices, version)
+    }
+
+    fn verify_pair_is_supported(&self, structure_type: FeagiByteStructureType, version: u8) -> Result<(), FeagiDataProcessingError> {
+        if self.is_supported(structure_type, version) {
+            return Ok(());
+        }
+        Err(FeagiBytesError::IncompatibleByteUse(format!("Peer cannot read version {} of {} structures! Supported versions: {:?}",
+                                                         version, structure_type, self.get_supported_versions(structure_type))).into())
+    }
+}
+
+impl FeagiByteStructureCompatible for FeagiByteStructureCapabilities {
+    fn get_type(&self) -> FeagiByteStructureType { FeagiByteStructureType::Capabilities }
+
+    fn get_version(&self) -> u8 { Self::SUPPORTED_VERSION_CAPABILITIES }
+
+    fn overwrite_feagi_byte_structure_slice(&self, slice: &mut [u8]) -> Result<usize, FeagiDataProcessingError> {
+        let num_bytes_needed = self.max_number_bytes_needed();
+        if slice.len() < num_bytes_needed {
+            return Err(IODataError::InvalidInplaceOperation(format!("Not enough space given to store capabilities! Need {} bytes but given {}!", num_bytes_needed, slice.len())).into());
+        }
+        slice[0] = self.get_type() as u8;
+        slice[1] = self.get_version();
+        // Only little endian is written by this release
+        LittleEndian::write_u16(&mut slice[Self::BYTE_ORDER_MARK_OFFSET..Self::PAIR_COUNT_OFFSET], Self::BYTE_ORDER_MARK);
+        LittleEndian::write_u16(&mut slice[Self::PAIR_COUNT_OFFSET..Self::PAIRS_OFFSET], self.supported.len() as u16);
+        let mut write_index = Self::PAIRS_OFFSET;
+        for (structure_type, version) in &self.supported {
+            slice[write_index] = *structure_type as u8;
+            slice[write_index + 1] = *version;
+            write_index += Self::PER_PAIR_SIZE_IN_BYTES;
+        }
+        Ok(slice.len() - num_bytes_needed)
+    }
+
+    fn max_number_bytes_needed(&self) -> usize {
+        Self::PAIRS_OFFSET + self.supported.len() * Self::PER_PAIR_SIZE_IN_BYTES
+    }
+
+    fn new_from_feagi_byte_structure(feagi_byte_structure: &FeagiByteStructure) -> Result<Self, FeagiDataProcessingError> {
+        FeagiByteStructure::verify_matching_structure_type_and_version(feagi_byte_structure,
+                                                                       FeagiByteStructureType::Capabilities,
+                                                                       Self::SUPPORTED_VERSION_CAPABILITIES)?;
+        let bytes = feagi_byte_structure.borrow_data_as_slice();
+        if bytes.len() < Self::PAIRS_OFFSET {
+            return Err(FeagiBytesError::UnableToDeserializeBytes("Capabilities byte structure too short to contain its header!".into()).into());
+        }
+        let is_little_endian = match LittleEndian::read_u16(&bytes[Self::BYTE_ORDER_MARK_OFFSET..Self::PAIR_COUNT_OFFSET]) {
+            Self::BYTE_ORDER_MARK => true,
+            mark if mark == Self::BYTE_ORDER_MARK.swap_bytes() => false,
+            mark => return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Capabilities byte structure has invalid byte order mark {:#06x}!", mark)).into()),
+        };
+        let pair_count = if is_little_endian {
+            LittleEndian::read_u16(&bytes[Self::PAIR_COUNT_OFFSET..Self::PAIRS_OFFSET])
+        } else {
+            BigEndian::read_u16(&bytes[Self::PAIR_COUNT_OFFSET..Self::PAIRS_OFFSET])
+        } as usize;
+        let expected_length = Self::PAIRS_OFFSET + pair_count * Self::PER_PAIR_SIZE_IN_BYTES;
+        if bytes.len() != expected_length {
+            return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Capabilities byte structure with {} pairs should be {} bytes long but is {}!", pair_count, expected_length, bytes.len())).into());
+        }
+
+        let mut capabilities = FeagiByteStructureCapabilities::new();
+        capabilities.is_little_endian = is_little_endian;
+        for pair in bytes[Self::PAIRS_OFFSET..].chunks_exact(Self::PER_PAIR_SIZE_IN_BYTES) {
+            // Types from newer releases are skipped, as they cannot be sent to the peer anyway
+            if let Ok(structure_type) = FeagiByteStructureType::try_from(pair[0]) {
+                capabilities.add_supported_version(structure_type, pair[1]);
+            }
+        }
+        Ok(capabilities)
+    }
+}
@@ -142,4 +142,44 @@ pub trait FeagiByteStructureCompatible {
         FeagiByteStructure::create_from_bytes(bytes)
     }
 
+    /// Serializes this object into an existing FeagiByteStructure, reusing its allocation.
+    ///
+    /// The previous contents of `target` are discarded, and its length is trimmed to exactly
+    /// the bytes written. No heap allocation occurs if `target` already has a capacity of at
+    /// least `max_number_bytes_needed()`, so a send loop reusing the same structure (or a
+    /// `FeagiByteStructurePool`) reaches a steady state without allocating.
+    ///
+    /// # Arguments
+    /// * `target` - The structure to overwrite
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the object was serialized into `target`
+    /// * `Err(FeagiDataProcessingError)` - If serialization fails, in which case `target` is left empty
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_json::json;
+    /// use feagi_core_data_structures_and_processing::io_data::FeagiJSON;
+    /// use feagi_core_data_structures_and_processing::io_processing::byte_structures::{FeagiByteStructure, FeagiByteStructureCompatible};
+    ///
+    /// let mut target = FeagiByteStructure::create_from_bytes(vec![1, 1, 123, 125]).unwrap();
+    /// FeagiJSON::from_json_value(json!({"tick": 1})).overwrite_feagi_byte_structure(&mut target).unwrap();
+    /// assert_eq!(&target.borrow_data_as_slice()[2..], br#"{"tick":1}"#);
+    /// ```
+    fn overwrite_feagi_byte_structure(&self, target: &mut FeagiByteStructure) -> Result<(), FeagiDataProcessingError> {
+        let bytes = &mut target.bytes;
+        bytes.clear();
+        bytes.resize(self.max_number_bytes_needed(), 0);
+        match self.overwrite_feagi_byte_structure_slice(bytes) {
+            Ok(wasted_bytes) => {
+                bytes.truncate(bytes.len() - wasted_bytes);
+                Ok(())
+            }
+            Err(err) => {
+                bytes.clear();
+                Err(err)
+            }
+        }
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,157 @@
+//! Integrity checksum and authentication envelope for FEAGI byte structures.
+//!
+//! The global header of a byte structure is only `[type][version]`, so a corrupted structure
+//! whose lengths happen to line up deserializes without complaint. Sealing a structure wraps it
+//! in an `IntegritySealed` envelope carrying a CRC32 of the wrapped bytes, and optionally an
+//! HMAC-SHA256 tag keyed with a secret shared between sender and receiver:
+//!
+//! ```text
+//! [Type=13 (1)][Version (1)][Flags (1)][CRC32 (4)][HMAC-SHA256 (32, if flagged)][Wrapped structure...]
+//! ```
+//!
+//! [`FeagiByteStructure::create_from_bytes`] verifies the checksum of sealed bytes and returns the
+//! wrapped structure, so receivers need no changes to benefit from sealing. As it has no key, it
+//! rejects envelopes carrying an authentication tag instead of skipping their verification.
+//! [`FeagiByteStructure::create_from_bytes_authenticated`] requires and verifies the tag.
+
+use byteorder::{ByteOrder, LittleEndian};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use crate::error::{FeagiBytesError, FeagiDataProcessingError};
+use super::{FeagiByteStructure, FeagiByteStructureType};
+
+type HmacSha256 = Hmac<Sha256>;
+
+impl FeagiByteStructure {
+
+    /// Currently supported version for integrity sealed envelopes.
+    pub const SUPPORTED_VERSION_INTEGRITY_SEALED: u8 = 1;
+
+    const SEALED_FLAGS_OFFSET: usize = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES;
+    const SEALED_CRC_OFFSET: usize = Self::SEALED_FLAGS_OFFSET + 1;
+    const SEALED_CRC_SIZE_IN_BYTES: usize = 4;
+    const SEALED_MAC_OFFSET: usize = Self::SEALED_CRC_OFFSET + Self::SEALED_CRC_SIZE_IN_BYTES;
+    const SEALED_MAC_SIZE_IN_BYTES: usize = 32;
+    const SEALED_FLAG_HAS_MAC: u8 = 0b0000_0001;
+
+    /// Wraps a byte structure in an `IntegritySealed` envelope.
+    ///
+    /// # Arguments
+    /// * `feagi_byte_structure` - The structure to seal
+    /// * `mac_key` - If given, an HMAC-SHA256 tag is computed with this key and included
+    ///
+    /// # Returns
+    /// * `Ok(FeagiByteStructure)` - The sealed envelope, ready to be transmitted
+    /// * `Err(FeagiDataProcessingError)` - If the given structure is already sealed
+    ///
+    /// # Example
+    /// ```rust
+    /// use feagi_core_data_structures_and_processing::io_processing::byte_structures::{FeagiByteStructure, FeagiByteStructureType};
+    ///
+    /// let structure = FeagiByteStructure::create_from_bytes(vec![1, 1, 123, 125]).unwrap();
+    /// let sealed = FeagiByteStructure::create_sealed(&structure, Some(b"shared secret")).unwrap();
+    /// assert_eq!(sealed.try_get_structure_type().unwrap(), FeagiByteStructureType::IntegritySealed);
+    ///
+    /// let received = FeagiByteStructure::create_from_bytes_authenticated(sealed.copy_out_as_byte_vector(), b"shared secret").unwrap();
+    /// assert_eq!(received.borrow_data_as_slice(), structure.borrow_data_as_slice());
+    /// assert!(FeagiByteStructure::create_from_bytes_authenticated(sealed.copy_out_as_byte_vector(), b"wrong secret").is_err());
+    /// ```
+    pub fn create_sealed(feagi_byte_structure: &FeagiByteStructure, mac_key: Option<&[u8]>) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
+        if feagi_byte_structure.try_get_structure_type()? == FeagiByteStructureType::IntegritySealed {
+            return Err(FeagiBytesError::IncompatibleByteUse("Cannot seal an already sealed byte structure!".into()).into());
+        }
+        let wrapped = feagi_byte_structure.borrow_data_as_slice();
+        let header_size = Self::sealed_header_size(mac_key.is_some());
+        let mut bytes: Vec<u8> = vec![0; header_size + wrapped.len()];
+        bytes[0] = FeagiByteStructureType::IntegritySealed as u8;
+        bytes[1] = Self::SUPPORTED_VERSION_INTEGRITY_SEALED;
+        bytes[Self::SEALED_FLAGS_OFFSET] = if mac_key.is_some() { Self::SEALED_FLAG_HAS_MAC } else { 0 };
+        LittleEndian::write_u32(&mut bytes[Self::SEALED_CRC_OFFSET..Self::SEALED_MAC_OFFSET], crc32fast::hash(wrapped));
+        if let Some(mac_key) = mac_key {
+            let tag = Self::create_mac(mac_key, wrapped).finalize().into_bytes();
+            bytes[Self::SEALED_MAC_OFFSET..header_size].copy_from_slice(&tag);
+        }
+        bytes[header_size..].copy_from_slice(wrapped);
+        Ok(FeagiByteStructure { bytes })
+    }
+
+    /// Creates a byte structure from sealed bytes, requiring a valid authentication tag.
+    ///
+    /// Unlike [`create_from_bytes`](Self::create_from_bytes), unsealed bytes and sealed bytes
+    /// without an authentication tag are rejected, so a tampered structure cannot pass by
+    /// having its tag stripped.
+    ///
+    /// # Arguments
+    /// * `bytes` - Raw bytes of an `IntegritySealed` envelope
+    /// * `mac_key` - The key the envelope was sealed with
+    ///
+    /// # Returns
+    /// * `Ok(FeagiByteStructure)` - The verified wrapped structure
+    /// * `Err(FeagiDataProcessingError)` - `FeagiBytesError::IntegrityCheckFailed` if the bytes
+    ///   are unsealed, untagged, corrupted, or were sealed with another key
+    pub fn create_from_bytes_authenticated(bytes: Vec<u8>, mac_key: &[u8]) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
+        if bytes.first() != Some(&(FeagiByteStructureType::IntegritySealed as u8)) {
+            return Err(FeagiBytesError::IntegrityCheckFailed("Byte structure is not sealed, and thus cannot be authenticated!".into()).into());
+        }
+        Self::unseal(bytes, Some(mac_key))
+    }
+
+    /// Verifies a sealed envelope and returns the structure it wraps.
+    ///
+    /// Without a `mac_key`, envelopes carrying an authentication tag are rejected rather than
+    /// accepted on their checksum alone, as the checksum can be recomputed by anyone.
+    pub(crate) fn unseal(mut bytes: Vec<u8>, mac_key: Option<&[u8]>) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
+        if bytes.len() <= Self::SEALED_FLAGS_OFFSET {
+            return Err(FeagiBytesError::IntegrityCheckFailed("Sealed byte structure is too short to hold its flags!".into()).into());
+        }
+        if bytes[1] != Self::SUPPORTED_VERSION_INTEGRITY_SEALED {
+            return Err(FeagiBytesError::UnableToValidateBytes(format!("Sealed byte structure has unsupported version {}!", bytes[1])).into());
+        }
+        let has_mac = bytes[Self::SEALED_FLAGS_OFFSET] & Self::SEALED_FLAG_HAS_MAC != 0;
+        let header_size = Self::sealed_header_size(has_mac);
+        if bytes.len() < header_size {
+            return Err(FeagiBytesError::IntegrityCheckFailed(format!("Sealed byte structure needs at least {} bytes for its header, but is only {} bytes long!", header_size, bytes.len())).into());
+        }
+
+        let wrapped = &bytes[header_size..];
+        let expected_crc = LittleEndian::read_u32(&bytes[Self::SEALED_CRC_OFFSET..Self::SEALED_MAC_OFFSET]);
+        let calculated_crc = crc32fast::hash(wrapped);
+        if expected_crc != calculated_crc {
+            return Err(FeagiBytesError::IntegrityCheckFailed(format!("Sealed byte structure CRC32 mismatch! Expected {:#010x} but calculated {:#010x}!", expected_crc, calculated_crc)).into());
+        }
+        match (mac_key, has_mac) {
+            (Some(mac_key), true) => {
+                Self::create_mac(mac_key, wrapped).verify_slice(&bytes[Self::SEALED_MAC_OFFSET..header_size])
+                    .map_err(|_| FeagiBytesError::IntegrityCheckFailed("Sealed byte structure authentication tag does not match!".into()))?;
+            }
+            (Some(_), false) => {
+                return Err(FeagiBytesError::IntegrityCheckFailed("Sealed byte structure carries no authentication tag!".into()).into());
+            }
+            (None, true) => {
+                return Err(FeagiBytesError::IntegrityCheckFailed("Sealed byte structure carries an authentication tag, but no key was given to verify it! Use create_from_bytes_authenticated".into()).into());
+            }
+            (None, false) => {}
+        }
+
+        if wrapped.first() == Some(&(FeagiByteStructureType::IntegritySealed as u8)) {
+            return Err(FeagiBytesError::UnableToValidateBytes("Sealed byte structures cannot be nested!".into()).into());
+        }
+        bytes.drain(0..header_size);
+        FeagiByteStructure::create_from_bytes(bytes)
+    }
+
+    fn sealed_header_size(has_mac: bool) -> usize {
+        if has_mac {
+            Self::SEALED_MAC_OFFSET + Self::SEALED_MAC_SIZE_IN_BYTES
+        } else {
+            Self::SEALED_MAC_OFFSET
+        }
+    }
+
+    fn create_mac(mac_key: &[u8], wrapped: &[u8]) -> HmacSha256 {
+        // HMAC accepts keys of any length, so this cannot fail
+        let mut mac = <HmacSha256 as Mac>::new_from_slice(mac_key).unwrap();
+        mac.update(wrapped);
+        mac
+    }
+}
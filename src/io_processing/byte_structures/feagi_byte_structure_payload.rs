@@ -0,0 +1,177 @@
+//! Typed deserialization of FEAGI byte structures.
+//!
+//! `FeagiByteStructure::copy_out_single_object_from_single_struct` returns a
+//! `Box<dyn FeagiByteStructureCompatible>`, which cannot be downcast back into its concrete type.
+//! [`FeagiByteStructurePayload`] instead holds the deserialized object as an enum variant, so it
+//! can be matched on directly, and [`FeagiByteStructure::decode_all`] decodes every structure
+//! within a (possibly nested) multistruct at once.
+
+use crate::error::{FeagiBytesError, FeagiDataProcessingError};
+use crate::io_data::{FeagiJSON, ImageFrame, SegmentedImageFrame};
+use crate::neuron_data::xyzp::CorticalMappedXYZPNeuronData;
+use super::{FeagiByteStructure, FeagiByteStructureCapabilities, FeagiByteStructureCompatible, FeagiByteStructureType};
+
+/// A deserialized FEAGI byte structure, typed by what it contains.
+///
+/// Every `FeagiByteStructureType` that holds data maps to a variant here. Adding a new structure
+/// type causes a compile error in [`try_from_feagi_byte_structure`](Self::try_from_feagi_byte_structure)
+/// until its deserialization is added.
+///
+/// # Example
+/// ```rust
+/// use serde_json::json;
+/// use feagi_core_data_structures_and_processing::io_data::FeagiJSON;
+/// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZP, NeuronXYZPArrays};
+/// use feagi_core_data_structures_and_processing::genomic_structures::CorticalID;
+/// use feagi_core_data_structures_and_processing::io_processing::byte_structures::{FeagiByteStructure, FeagiByteStructureCompatible, FeagiByteStructurePayload};
+///
+/// let mut neurons = NeuronXYZPArrays::with_capacity(1);
+/// neurons.push(&NeuronXYZP::new(1, 2, 3, 0.5));
+/// let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+/// neuron_data.insert(CorticalID::from_bytes(b"cAAAAA").unwrap(), neurons);
+///
+/// let json = FeagiJSON::from_json_value(json!({"hello": "world"})).as_new_feagi_byte_structure().unwrap();
+/// let neurons = neuron_data.as_new_feagi_byte_structure().unwrap();
+/// let combined = FeagiByteStructure::create_from_2_existing(&json, &neurons).unwrap();
+///
+/// for payload in combined.decode_all().unwrap() {
+///     match payload {
+///         FeagiByteStructurePayload::Json(json) => assert_eq!(json.borrow_json_value()["hello"], "world"),
+///         FeagiByteStructurePayload::NeuronXYZP(neuron_data) => assert_eq!(neuron_data.len(), 1),
+///         _ => unreachable!(),
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub enum FeagiByteStructurePayload {
+    /// JSON data, from a `JSON` structure.
+    Json(FeagiJSON),
+    /// Neuron data, from either a `NeuronCategoricalXYZP` or `NeuronCategoricalXYZPCompressed` structure.
+    NeuronXYZP(CorticalMappedXYZPNeuronData),
+    /// A single image frame, from an `ImageFrame` structure.
+    ImageFrame(ImageFrame),
+    /// A segmented vision frame, from a `SegmentedImageFrame` structure. Boxed as it holds 9 frames.
+    SegmentedImageFrame(Box<SegmentedImageFrame>),
+    /// Supported structure types and versions of a peer, from a `Capabilities` structure.
+    Capabilities(FeagiByteStructureCapabilities),
+}
+
+impl FeagiByteStructurePayload {
+
+    /// Deserializes a single (non multi-structure) byte structure into its typed payload.
+    ///
+    /// Sealed structures are verified and the structure they wrap is deserialized.
+    ///
+    /// # Returns
+    /// * `Ok(FeagiByteStructurePayload)` - The deserialized payload
+    /// * `Err(FeagiDataProcessingError)` - If the structure is a multistruct, or cannot be deserialized
+    pub fn try_from_feagi_byte_structure(feagi_byte_structure: &FeagiByteStructure) -> Result<Self, FeagiDataProcessingError> {
+        // NOTE: No wildcard arm on purpose, new structure types must be handled here
+        match feagi_byte_structure.try_get_structure_type()? {
+            FeagiByteStructureType::JSON => {
+                Ok(FeagiByteStructurePayload::Json(FeagiJSON::new_from_feagi_byte_structure(feagi_byte_structure)?))
+            },
+            FeagiByteStructureType::NeuronCategoricalXYZP | FeagiByteStructureType::NeuronCategoricalXYZPCompressed => {
+                Ok(FeagiByteStructurePayload::NeuronXYZP(CorticalMappedXYZPNeuronData::new_from_feagi_byte_structure(feagi_byte_structure)?))
+            },
+            FeagiByteStructureType::ImageFrame => {
+                Ok(FeagiByteStructurePayload::ImageFrame(ImageFrame::new_from_feagi_byte_structure(feagi_byte_structure)?))
+            },
+            FeagiByteStructureType::SegmentedImageFrame => {
+                Ok(FeagiByteStructurePayload::SegmentedImageFrame(Box::new(SegmentedImageFrame::new_from_feagi_byte_structure(feagi_byte_structure)?)))
+            },
+            FeagiByteStructureType::Capabilities => {
+                Ok(FeagiByteStructurePayload::Capabilities(FeagiByteStructureCapabilities::new_from_feagi_byte_structure(feagi_byte_structure)?))
+            },
+            FeagiByteStructureType::IntegritySealed => {
+                let unsealed = FeagiByteStructure::unseal(feagi_byte_structure.copy_out_as_byte_vector(), None)?;
+                FeagiByteStructurePayload::try_from_feagi_byte_structure(&unsealed)
+            },
+            FeagiByteStructureType::MultiStructHolder => {
+                Err(FeagiBytesError::IncompatibleByteUse("Cannot return a multistruct holding multiple structs as a single object! Use decode_all instead!".into()).into())
+            },
+        }
+    }
+
+    /// Returns the byte structure type this payload serializes to by default.
+    pub fn get_structure_type(&self) -> FeagiByteStructureType {
+        self.as_compatible().get_type()
+    }
+
+    /// Borrows the contained object through the serialization trait.
+    pub fn as_compatible(&self) -> &dyn FeagiByteStructureCompatible {
+        match self {
+            FeagiByteStructurePayload::Json(json) => json,
+            FeagiByteStructurePayload::NeuronXYZP(neuron_data) => neuron_data,
+            FeagiByteStructurePayload::ImageFrame(image_frame) => image_frame,
+            FeagiByteStructurePayload::SegmentedImageFrame(segmented_image_frame) => segmented_image_frame.as_ref(),
+            FeagiByteStructurePayload::Capabilities(capabilities) => capabilities,
+        }
+    }
+
+    /// Converts this payload into a boxed trait object, discarding its concrete type.
+    pub fn into_boxed_compatible(self) -> Box<dyn FeagiByteStructureCompatible> {
+        match self {
+            FeagiByteStructurePayload::Json(json) => Box::new(json),
+            FeagiByteStructurePayload::NeuronXYZP(neuron_data) => Box::new(neuron_data),
+            FeagiByteStructurePayload::ImageFrame(image_frame) => Box::new(image_frame),
+            FeagiByteStructurePayload::SegmentedImageFrame(segmented_image_frame) => segmented_image_frame,
+            FeagiByteStructurePayload::Capabilities(capabilities) => Box::new(capabilities),
+        }
+    }
+
+    /// Serializes the contained object into a new byte structure.
+    pub fn as_new_feagi_byte_structure(&self) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
+        self.as_compatible().as_new_feagi_byte_structure()
+    }
+}
+
+impl From<FeagiJSON> for FeagiByteStructurePayload {
+    fn from(json: FeagiJSON) -> Self {
+        FeagiByteStructurePayload::Json(json)
+    }
+}
+
+impl From<CorticalMappedXYZPNeuronData> for FeagiByteStructurePayload {
+    fn from(neuron_data: CorticalMappedXYZPNeuronData) -> Self {
+        FeagiByteStructurePayload::NeuronXYZP(neuron_data)
+    }
+}
+
+impl From<ImageFrame> for FeagiByteStructurePayload {
+    fn from(image_frame: ImageFrame) -> Self {
+        FeagiByteStructurePayload::ImageFrame(image_frame)
+    }
+}
+
+impl From<SegmentedImageFrame> for FeagiByteStructurePayload {
+    fn from(segmented_image_frame: SegmentedImageFrame) -> Self {
+        FeagiByteStructurePayload::SegmentedImageFrame(Box::new(segmented_image_frame))
+    }
+}
+
+impl From<FeagiByteStructureCapabilities> for FeagiByteStructurePayload {
+    fn from(capabilities: FeagiByteStructureCapabilities) -> Self {
+        FeagiByteStructurePayload::Capabilities(capabilities)
+    }
+}
+
+impl FeagiByteStructure {
+
+    /// Deserializes every structure held within this byte structure into typed payloads.
+    ///
+    /// A single structure yields one payload. Multistructs yield one payload per leaf structure,
+    /// recursing into nested multistructs, in the order `visit_leaf_structures` walks them.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<FeagiByteStructurePayload>)` - All contained payloads, in order
+    /// * `Err(FeagiDataProcessingError)` - If any contained structure cannot be deserialized
+    pub fn decode_all(&self) -> Result<Vec<FeagiByteStructurePayload>, FeagiDataProcessingError> {
+        let mut output: Vec<FeagiByteStructurePayload> = Vec::new();
+        self.visit_leaf_structures(|_path, leaf| {
+            output.push(FeagiByteStructurePayload::try_from_feagi_byte_structure(&leaf.copy_out_as_byte_structure()?)?);
+            Ok(())
+        })?;
+        Ok(output)
+    }
+}
@@ -0,0 +1,109 @@
+//! Pool of reusable FEAGI byte structure buffers.
+//!
+//! `FeagiByteStructureCompatible::as_new_feagi_byte_structure` allocates a new buffer every
+//! call. A send loop instead takes serialized structures from a [`FeagiByteStructurePool`] and
+//! recycles them once sent, so after the first few ticks every buffer already has the capacity
+//! it needs, and serializing no longer touches the heap.
+
+use crate::error::FeagiDataProcessingError;
+use super::{FeagiByteStructure, FeagiByteStructureCompatible};
+
+/// A pool of byte structure buffers, reused between serializations.
+///
+/// # Example
+/// ```rust
+/// use serde_json::json;
+/// use feagi_core_data_structures_and_processing::io_data::FeagiJSON;
+/// use feagi_core_data_structures_and_processing::io_processing::byte_structures::FeagiByteStructurePool;
+///
+/// let mut pool = FeagiByteStructurePool::new();
+/// for tick in 0..60 {
+///     let structure = pool.take_serialized(&FeagiJSON::from_json_value(json!({"tick": tick}))).unwrap();
+///     // ... send structure.borrow_data_as_slice() ...
+///     pool.recycle(structure);
+/// }
+/// assert_eq!(pool.get_pooled_count(), 1);
+/// ```
+pub struct FeagiByteStructurePool {
+    pooled: Vec<FeagiByteStructure>,
+    max_pooled_count: usize,
+}
+
+impl Default for FeagiByteStructurePool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeagiByteStructurePool {
+
+    /// Default number of buffers kept around for reuse.
+    pub const DEFAULT_MAX_POOLED_COUNT: usize = 8;
+
+    /// Creates an empty pool keeping up to `DEFAULT_MAX_POOLED_COUNT` buffers.
+    pub fn new() -> Self {
+        Self::new_with_max_pooled_count(Self::DEFAULT_MAX_POOLED_COUNT)
+    }
+
+    /// Creates an empty pool keeping up to the given number of buffers.
+    ///
+    /// This should be at least the number of structures in flight at once (for example, taken
+    /// but not yet recycled by a send loop), otherwise buffers are dropped and reallocated.
+    pub fn new_with_max_pooled_count(max_pooled_count: usize) -> Self {
+        FeagiByteStructurePool {
+            pooled: Vec::with_capacity(max_pooled_count),
+            max_pooled_count,
+        }
+    }
+
+    /// Serializes an object into a pooled buffer, or a new one if the pool is empty.
+    ///
+    /// # Returns
+    /// * `Ok(FeagiByteStructure)` - The serialized object, to be given back with `recycle` once used
+    /// * `Err(FeagiDataProcessingError)` - If serialization fails, in which case the buffer stays pooled
+    pub fn take_serialized(&mut self, object: &dyn FeagiByteStructureCompatible) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
+        let mut structure = self.take_empty();
+        match object.overwrite_feagi_byte_structure(&mut structure) {
+            Ok(()) => Ok(structure),
+            Err(err) => {
+                self.recycle(structure);
+                Err(err)
+            }
+        }
+    }
+
+    /// Takes an empty buffer from the pool, or a new one if the pool is empty.
+    ///
+    /// The returned structure is not valid until written into, for example with
+    /// `FeagiByteStructureCompatible::overwrite_feagi_byte_structure`.
+    pub fn take_empty(&mut self) -> FeagiByteStructure {
+        match self.pooled.pop() {
+            Some(structure) => structure,
+            None => FeagiByteStructure { bytes: Vec::new() },
+        }
+    }
+
+    /// Gives a structure back to the pool for reuse. If the pool is full, it is dropped instead.
+    pub fn recycle(&mut self, mut structure: FeagiByteStructure) {
+        if self.pooled.len() >= self.max_pooled_count {
+            return;
+        }
+        structure.reset_write_index();
+        self.pooled.push(structure);
+    }
+
+    /// Returns the number of buffers currently waiting in the pool.
+    pub fn get_pooled_count(&self) -> usize {
+        self.pooled.len()
+    }
+
+    /// Returns the maximum number of buffers kept in the pool.
+    pub fn get_max_pooled_count(&self) -> usize {
+        self.max_pooled_count
+    }
+
+    /// Drops all pooled buffers, releasing their memory.
+    pub fn clear(&mut self) {
+        self.pooled.clear();
+    }
+}
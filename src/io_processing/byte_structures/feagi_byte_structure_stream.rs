@@ -0,0 +1,293 @@
+//! Streaming of FEAGI byte structures over `std::io` readers and writers.
+//!
+//! A `FeagiByteStructure` must be fully in memory, so even a large multistruct has to be built
+//! whole before it can be sent. To send an unbounded sequence of structures through a socket,
+//! pipe, or file, each structure is instead written as its own length prefixed frame:
+//!
+//! ```text
+//! [Frame length in bytes (u32 LE)][Type (1)][Version (1)][Type-specific payload...]
+//! ```
+//!
+//! [`FeagiByteStructureWriter`] writes such frames, and [`FeagiByteStructureReader`] parses them
+//! back incrementally, keeping any partial frame buffered until the rest of its bytes arrive.
+
+use std::io::{ErrorKind, Read, Write};
+use byteorder::{ByteOrder, LittleEndian};
+use crate::error::{FeagiBytesError, FeagiDataProcessingError};
+use super::{FeagiByteStructure, FeagiByteStructureCompatible, FeagiByteStructureType};
+
+/// Size of the length prefix preceding each streamed structure.
+const FRAME_LENGTH_PREFIX_SIZE_IN_BYTES: usize = 4;
+
+/// Minimum number of bytes requested from the underlying reader per read call.
+const MINIMUM_READ_CHUNK_SIZE_IN_BYTES: usize = 4096;
+
+/// Writes FEAGI byte structures to a `std::io::Write` as length prefixed frames.
+///
+/// # Example
+/// ```rust
+/// use feagi_core_data_structures_and_processing::io_processing::byte_structures::{FeagiByteStructure, FeagiByteStructureReader, FeagiByteStructureWriter};
+///
+/// let structure = FeagiByteStructure::create_from_bytes(vec![1, 1, 123, 125]).unwrap();
+/// let mut writer = FeagiByteStructureWriter::new(Vec::new());
+/// writer.write_structure(&structure).unwrap();
+/// writer.write_structure(&structure).unwrap();
+///
+/// let stream = writer.into_inner();
+/// let mut reader = FeagiByteStructureReader::new(stream.as_slice());
+/// assert_eq!(reader.read_next().unwrap().unwrap().borrow_data_as_slice(), structure.borrow_data_as_slice());
+/// assert!(reader.read_next().unwrap().is_some());
+/// assert!(reader.read_next().unwrap().is_none());
+/// ```
+#[derive(Debug)]
+pub struct FeagiByteStructureWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> FeagiByteStructureWriter<W> {
+
+    /// Creates a new writer outputting frames to the given `std::io::Write`.
+    pub fn new(writer: W) -> Self {
+        FeagiByteStructureWriter { writer }
+    }
+
+    /// Writes a single byte structure as a length prefixed frame.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the whole frame was written
+    /// * `Err(FeagiDataProcessingError)` - If the structure is too large for the length prefix,
+    ///   or the underlying writer failed
+    pub fn write_structure(&mut self, feagi_byte_structure: &FeagiByteStructure) -> Result<(), FeagiDataProcessingError> {
+        let bytes = feagi_byte_structure.borrow_data_as_slice();
+        if bytes.len() > u32::MAX as usize {
+            return Err(FeagiBytesError::UnableToSerializeBytes(format!("Byte structure of {} bytes is too large to be streamed!", bytes.len())).into());
+        }
+        let mut length_prefix = [0u8; FRAME_LENGTH_PREFIX_SIZE_IN_BYTES];
+        LittleEndian::write_u32(&mut length_prefix, bytes.len() as u32);
+        self.writer.write_all(&length_prefix).map_err(Self::io_error)?;
+        self.writer.write_all(bytes).map_err(Self::io_error)?;
+        Ok(())
+    }
+
+    /// Serializes a compatible object and writes it as a length prefixed frame.
+    pub fn write_compatible(&mut self, object: &dyn FeagiByteStructureCompatible) -> Result<(), FeagiDataProcessingError> {
+        let feagi_byte_structure = object.as_new_feagi_byte_structure()?;
+        self.write_structure(&feagi_byte_structure)
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<(), FeagiDataProcessingError> {
+        self.writer.flush().map_err(Self::io_error)
+    }
+
+    /// Borrows the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Mutably borrows the underlying writer. Writing to it directly will corrupt the stream.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consumes this writer, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn io_error(err: std::io::Error) -> FeagiDataProcessingError {
+        FeagiBytesError::UnableToSerializeBytes(format!("Unable to write byte structure to stream: {}", err)).into()
+    }
+}
+
+/// Reads length prefixed FEAGI byte structures from a `std::io::Read`.
+///
+/// Bytes are buffered internally, so frames may arrive split over any number of reads. When the
+/// underlying reader is non-blocking and has no more data yet, [`read_next`](Self::read_next)
+/// returns `Ok(None)` and keeps the partial frame, resuming once called again. Bytes received by
+/// other means (for example from an async runtime) can be handed over with
+/// [`push_bytes`](Self::push_bytes) and parsed with [`try_take_buffered`](Self::try_take_buffered).
+///
+/// The type and version header of each frame is verified as soon as it arrives, so a corrupted
+/// or misaligned stream is rejected before its claimed length is waited on. Sealed structures
+/// are verified and unwrapped as with `FeagiByteStructure::create_from_bytes`, and if an
+/// authentication key is set, every structure must be sealed with it.
+#[derive(Debug)]
+pub struct FeagiByteStructureReader<R: Read> {
+    reader: R,
+    buffer: Vec<u8>,
+    max_frame_length: usize,
+    reached_end_of_stream: bool,
+    authentication_key: Option<Vec<u8>>,
+}
+
+impl<R: Read> FeagiByteStructureReader<R> {
+
+    /// Default upper limit of a single frame's length, in bytes.
+    pub const DEFAULT_MAX_FRAME_LENGTH_IN_BYTES: usize = 256 * 1024 * 1024;
+
+    /// Creates a new reader parsing frames from the given `std::io::Read`.
+    pub fn new(reader: R) -> Self {
+        FeagiByteStructureReader {
+            reader,
+            buffer: Vec::new(),
+            max_frame_length: Self::DEFAULT_MAX_FRAME_LENGTH_IN_BYTES,
+            reached_end_of_stream: false,
+            authentication_key: None,
+        }
+    }
+
+    /// Sets the key every received structure must be sealed with, or `None` to accept
+    /// unauthenticated structures.
+    pub fn set_authentication_key(&mut self, authentication_key: Option<Vec<u8>>) {
+        self.authentication_key = authentication_key;
+    }
+
+    /// Sets the largest frame length accepted before the stream is considered invalid.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the limit was set
+    /// * `Err(FeagiDataProcessingError)` - If the limit is smaller than the minimum valid structure length
+    pub fn set_max_frame_length(&mut self, max_frame_length: usize) -> Result<(), FeagiDataProcessingError> {
+        if max_frame_length < FeagiByteStructure::MINIMUM_LENGTH_TO_BE_CONSIDERED_VALID {
+            return Err(FeagiBytesError::IncompatibleByteUse(format!("Max frame length must be at least {} bytes!", FeagiByteStructure::MINIMUM_LENGTH_TO_BE_CONSIDERED_VALID)).into());
+        }
+        self.max_frame_length = max_frame_length;
+        Ok(())
+    }
+
+    /// Reads the next byte structure from the stream.
+    ///
+    /// # Returns
+    /// * `Ok(Some(FeagiByteStructure))` - The next complete structure
+    /// * `Ok(None)` - If the stream ended cleanly, or a non-blocking reader has no data yet.
+    ///   Use [`is_end_of_stream`](Self::is_end_of_stream) to tell these apart
+    /// * `Err(FeagiDataProcessingError)` - If a frame header is invalid, the stream ended
+    ///   inside a frame, or the underlying reader failed
+    pub fn read_next(&mut self) -> Result<Option<FeagiByteStructure>, FeagiDataProcessingError> {
+        loop {
+            if let Some(feagi_byte_structure) = self.try_take_buffered()? {
+                return Ok(Some(feagi_byte_structure));
+            }
+            if self.reached_end_of_stream {
+                return self.end_of_stream_result();
+            }
+
+            let read_size = self.number_bytes_missing_from_current_frame().max(MINIMUM_READ_CHUNK_SIZE_IN_BYTES);
+            let previous_length = self.buffer.len();
+            self.buffer.resize(previous_length + read_size, 0);
+            match self.reader.read(&mut self.buffer[previous_length..]) {
+                Ok(number_bytes_read) => {
+                    self.buffer.truncate(previous_length + number_bytes_read);
+                    if number_bytes_read == 0 {
+                        self.reached_end_of_stream = true;
+                    }
+                }
+                Err(err) => {
+                    self.buffer.truncate(previous_length);
+                    match err.kind() {
+                        ErrorKind::Interrupted => continue,
+                        ErrorKind::WouldBlock => return Ok(None),
+                        _ => return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Unable to read byte structure from stream: {}", err)).into()),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Appends bytes received from outside the underlying reader to the internal buffer.
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Takes the next complete byte structure out of the internal buffer, without reading.
+    ///
+    /// # Returns
+    /// * `Ok(Some(FeagiByteStructure))` - If a complete frame was buffered
+    /// * `Ok(None)` - If more bytes are needed to complete the frame
+    /// * `Err(FeagiDataProcessingError)` - If the buffered frame header is invalid
+    pub fn try_take_buffered(&mut self) -> Result<Option<FeagiByteStructure>, FeagiDataProcessingError> {
+        let frame_length = match self.verify_buffered_frame_header()? {
+            Some(frame_length) => frame_length,
+            None => return Ok(None),
+        };
+        let frame_end = FRAME_LENGTH_PREFIX_SIZE_IN_BYTES + frame_length;
+        if self.buffer.len() < frame_end {
+            return Ok(None);
+        }
+        let remaining = self.buffer.split_off(frame_end);
+        let mut frame = std::mem::replace(&mut self.buffer, remaining);
+        frame.drain(0..FRAME_LENGTH_PREFIX_SIZE_IN_BYTES);
+        match &self.authentication_key {
+            Some(authentication_key) => Ok(Some(FeagiByteStructure::create_from_bytes_authenticated(frame, authentication_key)?)),
+            None => Ok(Some(FeagiByteStructure::create_from_bytes(frame)?)),
+        }
+    }
+
+    /// Returns true once the underlying reader has reported the end of the stream.
+    pub fn is_end_of_stream(&self) -> bool {
+        self.reached_end_of_stream
+    }
+
+    /// Returns the number of bytes buffered that do not yet form a complete frame.
+    pub fn get_buffered_byte_count(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Borrows the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Mutably borrows the underlying reader. Reading from it directly will corrupt the stream.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Consumes this reader, returning the underlying reader. Any buffered bytes are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    //region Internal Functions
+
+    /// Verifies as much of the buffered frame header as has arrived, returning the frame
+    /// length once it is known.
+    fn verify_buffered_frame_header(&self) -> Result<Option<usize>, FeagiDataProcessingError> {
+        if self.buffer.len() < FRAME_LENGTH_PREFIX_SIZE_IN_BYTES {
+            return Ok(None);
+        }
+        let frame_length = LittleEndian::read_u32(&self.buffer[0..FRAME_LENGTH_PREFIX_SIZE_IN_BYTES]) as usize;
+        if frame_length < FeagiByteStructure::MINIMUM_LENGTH_TO_BE_CONSIDERED_VALID {
+            return Err(FeagiBytesError::UnableToValidateBytes(format!("Streamed frame claims a length of {} bytes, which is too short to be a byte structure!", frame_length)).into());
+        }
+        if frame_length > self.max_frame_length {
+            return Err(FeagiBytesError::UnableToValidateBytes(format!("Streamed frame claims a length of {} bytes, which exceeds the max frame length of {} bytes!", frame_length, self.max_frame_length)).into());
+        }
+        let structure_bytes = &self.buffer[FRAME_LENGTH_PREFIX_SIZE_IN_BYTES..];
+        if !structure_bytes.is_empty() {
+            _ = FeagiByteStructureType::try_get_type_from_bytes(structure_bytes)?;
+        }
+        if structure_bytes.len() > 1 && structure_bytes[1] == 0 {
+            return Err(FeagiBytesError::UnableToValidateBytes("Streamed byte structure cannot have version number of 0!".into()).into());
+        }
+        Ok(Some(frame_length))
+    }
+
+    fn number_bytes_missing_from_current_frame(&self) -> usize {
+        if self.buffer.len() < FRAME_LENGTH_PREFIX_SIZE_IN_BYTES {
+            return FRAME_LENGTH_PREFIX_SIZE_IN_BYTES - self.buffer.len();
+        }
+        let frame_length = LittleEndian::read_u32(&self.buffer[0..FRAME_LENGTH_PREFIX_SIZE_IN_BYTES]) as usize;
+        (FRAME_LENGTH_PREFIX_SIZE_IN_BYTES + frame_length).saturating_sub(self.buffer.len())
+    }
+
+    fn end_of_stream_result(&self) -> Result<Option<FeagiByteStructure>, FeagiDataProcessingError> {
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+        Err(FeagiBytesError::UnableToDeserializeBytes(format!("Stream ended with {} bytes of an incomplete byte structure frame!", self.buffer.len())).into())
+    }
+
+    //endregion
+}
@@ -34,7 +34,7 @@ use crate::error::{FeagiBytesError, FeagiDataProcessingError};
 /// Byte 2+: Format-specific data
 /// ```
 #[repr(u8)]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FeagiByteStructureType {
     /// JSON serialization format (human-readable text)
     JSON = 1,
@@ -49,7 +49,35 @@ pub enum FeagiByteStructureType {
     /// 
     /// Binary format specifically designed for neuron data
     /// with X, Y, Z coordinates and potential (P) values.
-    NeuronCategoricalXYZP = 11
+    NeuronCategoricalXYZP = 11,
+    
+    /// Compressed binary format for neuron categorical XYZP data.
+    ///
+    /// Holds the same data as `NeuronCategoricalXYZP`, but with sorted, delta and varint
+    /// encoded coordinates and optionally quantized potentials.
+    NeuronCategoricalXYZPCompressed = 12,
+
+    /// Integrity envelope around another structure.
+    ///
+    /// Holds a CRC32 and optional HMAC-SHA256 tag of the wrapped structure, which is
+    /// verified and unwrapped when the bytes are loaded.
+    IntegritySealed = 13,
+
+    /// Binary format for a single image frame.
+    ///
+    /// Holds the frame properties and its pixels, either as floats or quantized to bytes.
+    ImageFrame = 14,
+
+    /// Binary format for a segmented (peripheral vision) image frame.
+    ///
+    /// Holds the shared properties and the resolutions and pixels of all 9 segments.
+    SegmentedImageFrame = 15,
+
+    /// Capability exchange format.
+    ///
+    /// Lists the structure types and versions an agent can read, sent during a handshake
+    /// so peers on different releases can negotiate what to send each other.
+    Capabilities = 16
 }
 
 impl Display for FeagiByteStructureType {
@@ -58,6 +86,11 @@ impl Display for FeagiByteStructureType {
             FeagiByteStructureType::JSON => "JSON",
             FeagiByteStructureType::MultiStructHolder => "MultiStructHolder",
             FeagiByteStructureType::NeuronCategoricalXYZP => "NeuronCategoricalXYZP",
+            FeagiByteStructureType::NeuronCategoricalXYZPCompressed => "NeuronCategoricalXYZPCompressed",
+            FeagiByteStructureType::IntegritySealed => "IntegritySealed",
+            FeagiByteStructureType::ImageFrame => "ImageFrame",
+            FeagiByteStructureType::SegmentedImageFrame => "SegmentedImageFrame",
+            FeagiByteStructureType::Capabilities => "Capabilities",
         };
         write!(f, "{name}")
     }
@@ -81,11 +114,21 @@ impl FeagiByteStructureType {
     /// - `1` → JSON format
     /// - `9` → MultiStructHolder format  
     /// - `11` → NeuronCategoricalXYZP format
+    /// - `12` → NeuronCategoricalXYZPCompressed format
+    /// - `13` → IntegritySealed envelope
+    /// - `14` → ImageFrame format
+    /// - `15` → SegmentedImageFrame format
+    /// - `16` → Capabilities format
     pub fn try_from(value: u8) -> Result<Self, FeagiDataProcessingError> {
         match value {
             1 => Ok(FeagiByteStructureType::JSON),
             9 => Ok(FeagiByteStructureType::MultiStructHolder),
             11 => Ok(FeagiByteStructureType::NeuronCategoricalXYZP),
+            12 => Ok(FeagiByteStructureType::NeuronCategoricalXYZPCompressed),
+            13 => Ok(FeagiByteStructureType::IntegritySealed),
+            14 => Ok(FeagiByteStructureType::ImageFrame),
+            15 => Ok(FeagiByteStructureType::SegmentedImageFrame),
+            16 => Ok(FeagiByteStructureType::Capabilities),
             _ => Err(FeagiBytesError::UnableToDeserializeBytes(format!("Unknown FeagiByteStructure type {}", value)).into())
         }
     }
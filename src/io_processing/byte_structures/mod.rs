@@ -7,11 +7,12 @@
 //!
 //! # Architecture
 //!
-//! The framework consists of three main components:
+//! The framework consists of following main components:
 //!
 //! - **FeagiByteStructureType**: Enumeration of supported serialization formats
 //! - **FeagiByteStructure**: Core container for serialized byte data with validation
 //! - **FeagiByteStructureCompatible**: Trait for types that can be serialized/deserialized
+//! - **FeagiByteStructurePayload**: Enum of every deserialized type, for typed dispatch
 //!
 //! # Binary Format
 //!
@@ -20,18 +21,51 @@
 //! [Type (1 byte)][Version (1 byte)][Format-specific data...]
 //! ```
 //!
-//! Multi-structure containers add additional headers for indexing multiple structures.
+//! Multi-structure containers add additional headers for indexing multiple structures. Version 2
+//! containers widen the structure count and may be nested, with
+//! `FeagiByteStructure::visit_leaf_structures` walking every leaf along with its path.
+//!
+//! # Streaming
+//!
+//! `FeagiByteStructureWriter` and `FeagiByteStructureReader` send any number of structures
+//! through `std::io` streams by prefixing each with its length in bytes.
+//!
+//! # Buffer Reuse
+//!
+//! `FeagiByteStructureCompatible::overwrite_feagi_byte_structure` serializes into an existing
+//! structure, reusing its allocation, and `FeagiByteStructurePool` keeps such buffers around so
+//! high rate send loops reach a steady state without heap allocations.
+//!
+//! # Version Negotiation
+//!
+//! `FeagiByteStructureCapabilities` lists the structure types and versions an agent can read.
+//! Peers exchange these during a handshake, and build outgoing structures from the capabilities
+//! both share, so agents on different releases can interoperate during rolling upgrades.
 //!
 //! # Supported Formats
 //!
 //! - **JSON**: Human-readable text serialization
 //! - **NeuronCategoricalXYZP**: Optimized binary format for neuron spatial data  
+//! - **NeuronCategoricalXYZPCompressed**: Delta / varint compressed variant of the above, for large areas
 //! - **MultiStructHolder**: Container format for multiple structures
+//! - **IntegritySealed**: CRC32 and optional HMAC-SHA256 envelope around another structure
+//! - **ImageFrame**: Raw image pixels, as floats or quantized bytes
+//! - **SegmentedImageFrame**: Raw pixels of all 9 segments of a segmented vision frame
+//! - **Capabilities**: Supported structure types and versions, for handshakes
 //! 
 mod feagi_byte_structure_type;
 mod feagi_byte_structure;
 mod feagi_byte_structure_compatible;
+mod feagi_byte_structure_stream;
+mod feagi_byte_structure_integrity;
+mod feagi_byte_structure_payload;
+mod feagi_byte_structure_capabilities;
+mod feagi_byte_structure_pool;
 
 pub use feagi_byte_structure_type::FeagiByteStructureType;
 pub use feagi_byte_structure_compatible::FeagiByteStructureCompatible;
-pub use feagi_byte_structure::FeagiByteStructure;
\ No newline at end of file
+pub use feagi_byte_structure::{FeagiByteStructure, FeagiByteStructureLeaf};
+pub use feagi_byte_structure_stream::{FeagiByteStructureReader, FeagiByteStructureWriter};
+pub use feagi_byte_structure_payload::FeagiByteStructurePayload;
+pub use feagi_byte_structure_capabilities::FeagiByteStructureCapabilities;
+pub use feagi_byte_structure_pool::FeagiByteStructurePool;
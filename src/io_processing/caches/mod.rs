@@ -1,4 +1,6 @@
 mod sensory_cache;
+mod motor_cache;
 pub(crate) mod hashmap_helpers;
 
-pub use sensory_cache::SensorCache;
\ No newline at end of file
+pub use sensory_cache::SensorCache;
+pub use motor_cache::MotorCache;
\ No newline at end of file
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+use std::time::Instant;
+use crate::error::{FeagiDataProcessingError, IODataError};
+use crate::genomic_structures::{CorticalGroupingIndex, CorticalID, CorticalIOChannelIndex, MotorCorticalType};
+use crate::io_data::{IOTypeData, IOTypeVariant};
+use crate::io_processing::caches::hashmap_helpers::{FullChannelCacheKey, CorticalAreaMetadataKey};
+use crate::io_processing::motor_channel_stream_cache::MotorChannelStreamCache;
+use crate::io_processing::processors::LinearScaleFromM1And1Processor;
+use crate::io_processing::{CallbackSubscriberID, StreamCacheProcessor};
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronCoderParameters, NeuronCoderRegistry, NeuronXYZPDecoder};
+
+pub struct MotorCache {
+    channel_caches: HashMap<FullChannelCacheKey, MotorChannelStreamCache>, // (cortical type, grouping index, channel) -> motor data cache, the main lookup
+    cortical_area_metadata: HashMap<CorticalAreaMetadataKey, CorticalAreaCacheDetails>, // (cortical type, grouping index) -> (Vec<FullChannelCacheKey>, neuron_decoder), defines all channel caches for a cortical area, and its neuron decoder
+    last_decoded: Instant, // when neuron data was last decoded
+    coder_registry: NeuronCoderRegistry, // constructs the neuron decoders used by the by-type registrations
+}
+
+impl Default for MotorCache {
+    fn default() -> Self {
+        MotorCache::new()
+    }
+}
+
+impl MotorCache {
+    pub fn new() -> MotorCache {
+        MotorCache {
+            channel_caches: HashMap::new(),
+            cortical_area_metadata: HashMap::new(),
+            last_decoded: Instant::now(),
+            coder_registry: NeuronCoderRegistry::new(),
+        }
+    }
+
+    /// Returns the registry used to construct neuron decoders for the built-in registration calls.
+    pub fn get_coder_registry(&self) -> &NeuronCoderRegistry {
+        &self.coder_registry
+    }
+
+    /// Returns the registry used to construct neuron decoders for the built-in registration calls.
+    /// Replacing a built-in decoder here changes the decoder used by all following registrations.
+    pub fn get_coder_registry_mut(&mut self) -> &mut NeuronCoderRegistry {
+        &mut self.coder_registry
+    }
+
+    //region Registration
+
+    //region macro
+
+    pub fn register_cortical_group_for_rotary_motor(&mut self, cortical_group: CorticalGroupingIndex,
+                                                    number_of_channels: usize,
+                                                    neuron_resolution: usize,
+                                                    lower_bound: f32,
+                                                    upper_bound: f32) -> Result<(), FeagiDataProcessingError> {
+
+        self.register_cortical_area_f32_normalized_m1_to_1_split_sign_divided(MotorCorticalType::RotaryMotor,
+                                                                              cortical_group, number_of_channels,
+                                                                              neuron_resolution, lower_bound,
+                                                                              upper_bound)
+    }
+
+    //endregion
+
+    /// Registers a cortical area with a user supplied neuron decoder.
+    ///
+    /// Each element of `initial_processor_chains` is the processor chain of one channel, so the
+    /// number of chains defines the number of channels. The input of every chain must match
+    /// the data type produced by the decoder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no chains are given, if any chain does not line up with the decoder,
+    /// or if the cortical area is already registered.
+    pub fn register_cortical_area_with_decoder(&mut self, motor_cortical_type: MotorCorticalType,
+                                               cortical_group: CorticalGroupingIndex,
+                                               neuron_decoder: Box<dyn NeuronXYZPDecoder + Sync + Send>,
+                                               initial_processor_chains: Vec<Vec<Box<dyn StreamCacheProcessor + Sync + Send>>>) -> Result<(), FeagiDataProcessingError> {
+        self.register_cortical_area_and_channels(motor_cortical_type, cortical_group, neuron_decoder, initial_processor_chains)
+    }
+
+    pub fn register_callback(&mut self, cortical_motor_type: MotorCorticalType, cortical_grouping_index: CorticalGroupingIndex,
+                             device_channel: CorticalIOChannelIndex, callback: Box<dyn Fn(&IOTypeData) + Send + Sync>) -> Result<CallbackSubscriberID, FeagiDataProcessingError> {
+        let channel_cache = self.get_channel_cache_mut(cortical_motor_type, cortical_grouping_index, device_channel)?;
+        Ok(channel_cache.register_callback(callback))
+    }
+
+    pub fn deregister_callback(&mut self, cortical_motor_type: MotorCorticalType, cortical_grouping_index: CorticalGroupingIndex,
+                               device_channel: CorticalIOChannelIndex, callback_id: CallbackSubscriberID) -> Result<bool, FeagiDataProcessingError> {
+        let channel_cache = self.get_channel_cache_mut(cortical_motor_type, cortical_grouping_index, device_channel)?;
+        Ok(channel_cache.deregister_callback(callback_id))
+    }
+
+    //endregion
+
+
+
+    //region Read Data
+
+    //region macro
+
+    pub fn read_data_for_rotary_motor(&self, cortical_grouping_index: CorticalGroupingIndex, device_channel: CorticalIOChannelIndex) -> Result<f32, FeagiDataProcessingError> {
+        let motor_type = MotorCorticalType::RotaryMotor;
+        let val = self.get_most_recent_value_by_channel(motor_type, cortical_grouping_index, device_channel)?;
+        f32::try_from(val)
+    }
+
+    //endregion
+
+    /// Decodes all registered motor cortical areas present in the given neuron data.
+    ///
+    /// Each registered channel of a cortical area that appears in `neurons_to_decode_from` is
+    /// decoded, run through its processor chain, and its callbacks are fired. Cortical areas
+    /// not present in the neuron data are skipped and keep their previous values.
+    pub fn decode_from_neurons(&mut self, neurons_to_decode_from: &CorticalMappedXYZPNeuronData) -> Result<(), FeagiDataProcessingError> {
+        for cortical_area_details in self.cortical_area_metadata.values() {
+            let neuron_decoder = &cortical_area_details.neuron_decoder;
+            let is_area_present = neuron_decoder.get_cortical_id_read_destinations().iter()
+                .any(|cortical_id| neurons_to_decode_from.contains_cortical_id(cortical_id));
+            if !is_area_present {
+                continue;
+            }
+            for channel_cache_key in &cortical_area_details.relevant_channel_lookups {
+                let motor_cache = self.channel_caches.get_mut(channel_cache_key).unwrap();
+                _ = motor_cache.decode_from_neurons(neurons_to_decode_from, neuron_decoder.as_ref())?;
+            }
+        }
+        self.last_decoded = Instant::now();
+        Ok(())
+    }
+
+    pub fn get_most_recent_value_by_channel(&self, cortical_motor_type: MotorCorticalType, cortical_grouping_index: CorticalGroupingIndex, device_channel: CorticalIOChannelIndex) -> Result<&IOTypeData, FeagiDataProcessingError> {
+        let cortical_type = cortical_motor_type.into();
+        match self.channel_caches.get(&FullChannelCacheKey::new(cortical_type, cortical_grouping_index, device_channel)) {
+            Some(channel_stream_cache) => Ok(channel_stream_cache.get_most_recent_motor_value()),
+            None => Err(IODataError::InvalidParameters(format!("Unable to find Cortical Type {:?}, Group Index {:?}, Channel {:?}!", cortical_type, cortical_grouping_index, device_channel)).into())
+        }
+    }
+
+    pub fn get_last_decode_time(&self) -> Instant {
+        self.last_decoded
+    }
+
+    //endregion
+
+
+
+    //region Internal Functions
+
+    //region By-Type Registration
+
+    fn register_cortical_area_f32_normalized_m1_to_1_split_sign_divided(&mut self, motor_cortical_type: MotorCorticalType,
+                                                                         cortical_group: CorticalGroupingIndex,
+                                                                         number_of_channels: usize,
+                                                                         neuron_resolution: usize,
+                                                                         lower_bound: f32,
+                                                                         upper_bound: f32) -> Result<(), FeagiDataProcessingError> {
+        if neuron_resolution == 0 {
+            return Err(IODataError::InvalidParameters("Unable to define a neuron resolution of 0!".into()).into())
+        }
+        if upper_bound <= lower_bound {
+            return Err(IODataError::InvalidParameters("Upper bound must not be less than lower bound!".into()).into())
+        }
+        self.verify_number_channels(number_of_channels)?;
+
+        let cortical_id = CorticalID::new_motor_cortical_area_id(motor_cortical_type, cortical_group)?;
+        let coder_parameters = NeuronCoderParameters::new(vec![cortical_id], IOTypeVariant::F32NormalizedM1To1, neuron_resolution as u32)?;
+        let neuron_decoder = self.coder_registry.create_decoder_for_cortical_type(&motor_cortical_type.into(), &coder_parameters)?;
+        let resting_value = lower_bound + ((upper_bound - lower_bound) * 0.5);
+        let mut processors: Vec<Vec<Box<dyn StreamCacheProcessor + Sync + Send>>> = Vec::with_capacity(number_of_channels);
+        for _ in 0..number_of_channels {
+            processors.push(vec![Box::new(LinearScaleFromM1And1Processor::new(lower_bound, upper_bound, resting_value)?)]);
+        };
+
+        self.register_cortical_area_and_channels(motor_cortical_type, cortical_group, neuron_decoder, processors)?;
+        Ok(())
+    }
+
+    //endregion
+
+
+    fn register_cortical_area_and_channels(&mut self, motor_cortical_type: MotorCorticalType, cortical_group: CorticalGroupingIndex,
+                                           neuron_decoder: Box<dyn NeuronXYZPDecoder + Sync + Send>,
+                                           initial_processor_chains: Vec<Vec<Box<dyn StreamCacheProcessor + Sync + Send>>>) -> Result<(), FeagiDataProcessingError> {
+        // NOTE: initial_processor_chains is a vector of vectors, meaning each channel gets a vector of processors
+
+        let number_supported_channels = initial_processor_chains.len() as u32;
+        let cortical_type = motor_cortical_type.into();
+        let cortical_metadata = CorticalAreaMetadataKey::new(cortical_type, cortical_group);
+
+        if number_supported_channels == 0 {
+            return Err(IODataError::InvalidParameters("A cortical area cannot be registered with 0 channels!".into()).into())
+        }
+        if self.cortical_area_metadata.contains_key(&cortical_metadata) {
+            return Err(FeagiDataProcessingError::InternalError("Cortical area already registered!".into()))
+        }
+
+        // Build every channel cache before inserting any, so a bad chain leaves nothing half registered
+        let mut motor_caches: Vec<(FullChannelCacheKey, MotorChannelStreamCache)> = Vec::with_capacity(number_supported_channels as usize);
+        for (i, processor_chain) in initial_processor_chains.into_iter().enumerate() {
+
+            let channel: CorticalIOChannelIndex = (i as u32).into();
+            let motor_key: FullChannelCacheKey = FullChannelCacheKey::new(cortical_type, cortical_group, channel);
+            let motor_cache: MotorChannelStreamCache = MotorChannelStreamCache::new(processor_chain, channel)?;
+            if motor_cache.get_input_data_type() != neuron_decoder.get_decoded_data_type() {
+                return Err(IODataError::InvalidParameters(format!("Processor chain for channel {} accepts {} but the neuron decoder produces {}!",
+                                                                  channel, motor_cache.get_input_data_type(), neuron_decoder.get_decoded_data_type())).into())
+            }
+            motor_caches.push((motor_key, motor_cache));
+        }
+
+        let mut cache_keys: Vec<FullChannelCacheKey> = Vec::with_capacity(number_supported_channels as usize);
+        for (motor_key, motor_cache) in motor_caches {
+            _ = self.channel_caches.insert(motor_key.clone(), motor_cache);
+            cache_keys.push(motor_key);
+        }
+
+        let cortical_cache_details = CorticalAreaCacheDetails::new(cache_keys, neuron_decoder);
+        _ = self.cortical_area_metadata.insert(cortical_metadata, cortical_cache_details);
+
+        Ok(())
+    }
+
+    fn get_channel_cache_mut(&mut self, cortical_motor_type: MotorCorticalType, cortical_grouping_index: CorticalGroupingIndex, device_channel: CorticalIOChannelIndex) -> Result<&mut MotorChannelStreamCache, FeagiDataProcessingError> {
+        let cortical_type = cortical_motor_type.into();
+        match self.channel_caches.get_mut(&FullChannelCacheKey::new(cortical_type, cortical_grouping_index, device_channel)) {
+            Some(channel_stream_cache) => Ok(channel_stream_cache),
+            None => Err(IODataError::InvalidParameters(format!("Unable to find Cortical Type {:?}, Group Index {:?}, Channel {:?}!", cortical_type, cortical_grouping_index, device_channel)).into())
+        }
+    }
+
+    fn verify_number_channels(&self, number_of_channels: usize) -> Result<(), FeagiDataProcessingError> {
+        if number_of_channels == 0 {
+            return Err(IODataError::InvalidParameters("Number of channels must not be zero!".into()).into())
+        }
+        Ok(())
+    }
+
+    //endregion
+
+}
+
+
+
+struct CorticalAreaCacheDetails {
+    relevant_channel_lookups: Vec<FullChannelCacheKey>,
+    neuron_decoder: Box<dyn NeuronXYZPDecoder + Sync + Send>
+}
+
+impl CorticalAreaCacheDetails {
+    pub(crate) fn new(relevant_channel_lookups: Vec<FullChannelCacheKey>, neuron_decoder: Box<dyn NeuronXYZPDecoder + Sync + Send>) -> Self {
+        CorticalAreaCacheDetails{
+            relevant_channel_lookups,
+            neuron_decoder
+        }
+    }
+}
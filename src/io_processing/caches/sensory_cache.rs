@@ -8,14 +8,15 @@ use crate::io_processing::caches::hashmap_helpers::{FullChannelCacheKey, Cortica
 use crate::io_processing::processors::{IdentitySegmentedImageFrameProcessor, ImageFrameSegmentatorProcessor, ImageFrameTransformerProcessor, LinearScaleTo0And1Processor};
 use crate::io_processing::sensory_channel_stream_cache::SensoryChannelStreamCache;
 use crate::io_processing::StreamCacheProcessor;
-use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPEncoder};
-use crate::neuron_data::xyzp::encoders::{F32LinearNeuronXYZPEncoder, ImageFrameNeuronXYZPEncoder, SegmentedImageFrameNeuronXYZPEncoder};
+use crate::io_processing::stream_cache_processors::verify_sensor_chain_and_encoder;
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronCoderParameters, NeuronCoderRegistry, NeuronCoderVariantType, NeuronXYZPEncoder};
 
 pub struct SensorCache {
     channel_caches: HashMap<FullChannelCacheKey, SensoryChannelStreamCache>, // (cortical type, grouping index, channel) -> sensory data cache, the main lookup
     cortical_area_metadata: HashMap<CorticalAreaMetadataKey, CorticalAreaCacheDetails>, // (cortical type, grouping index) -> (Vec<FullChannelCacheKey>, number_channels, neuron_encoder), defines all channel caches for a cortical area, and its neuron encoder
     agent_key_proxy: HashMap<AccessAgentLookupKey, Vec<FullChannelCacheKey>>, // (CorticalType, AgentDeviceIndex) -> Vec<FullChannelCacheKey>, allows users to map any channel of a cortical type to an agent device ID
-    neuron_data: CorticalMappedXYZPNeuronData // cached neuron data
+    neuron_data: CorticalMappedXYZPNeuronData, // cached neuron data
+    coder_registry: NeuronCoderRegistry, // constructs the neuron encoders used by the by-type registrations
 }
 
 impl SensorCache {
@@ -25,8 +26,20 @@ impl SensorCache {
             cortical_area_metadata: HashMap::new(),
             agent_key_proxy: HashMap::new(),
             neuron_data: CorticalMappedXYZPNeuronData::new(),
+            coder_registry: NeuronCoderRegistry::new(),
         }
     }
+
+    /// Returns the registry used to construct neuron encoders for the built-in registration calls.
+    pub fn get_coder_registry(&self) -> &NeuronCoderRegistry {
+        &self.coder_registry
+    }
+
+    /// Returns the registry used to construct neuron encoders for the built-in registration calls.
+    /// Replacing a built-in encoder here changes the encoder used by all following registrations.
+    pub fn get_coder_registry_mut(&mut self) -> &mut NeuronCoderRegistry {
+        &mut self.coder_registry
+    }
     
     //region Registration
 
@@ -80,7 +93,8 @@ impl SensorCache {
         }; // ensure no cortical ID is used already
         
         let segmentator = ImageFrameSegmentator::new(input_image_properties, output_image_properties, segmentation_center_properties)?;
-        let neuron_encoder = Box::new(SegmentedImageFrameNeuronXYZPEncoder::new(cortical_ids, output_image_properties)?);
+        let coder_parameters = NeuronCoderParameters::new(cortical_ids.to_vec(), IOTypeVariant::SegmentedImageFrame(Some(output_image_properties)), 1)?;
+        let neuron_encoder = self.coder_registry.create_encoder(&NeuronCoderVariantType::SegmentedImageFrame.into(), &coder_parameters)?;
         let mut processors: Vec<Vec<Box<dyn StreamCacheProcessor + Sync + Send>>> = Vec::with_capacity(number_of_channels);
         for i in 0..number_of_channels {
             processors.push(vec![Box::new(ImageFrameSegmentatorProcessor::new(input_image_properties, output_image_properties, segmentator.clone()))]);
@@ -92,6 +106,24 @@ impl SensorCache {
 
     }
 
+    /// Registers a cortical area with a user supplied neuron encoder.
+    ///
+    /// Each element of `initial_processor_chains` is the processor chain of one channel, so the
+    /// number of chains defines the number of channels. The output of every chain must match
+    /// the data type accepted by the encoder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no chains are given, if any chain does not line up with the encoder,
+    /// or if the cortical area is already registered.
+    pub fn register_cortical_area_with_encoder(&mut self, sensor_cortical_type: SensorCorticalType,
+                                               cortical_group: CorticalGroupingIndex,
+                                               neuron_encoder: Box<dyn NeuronXYZPEncoder + Sync + Send>,
+                                               initial_processor_chains: Vec<Vec<Box<dyn StreamCacheProcessor + Sync + Send>>>,
+                                               allow_stale_data: bool) -> Result<(), FeagiDataProcessingError> {
+        self.register_cortical_area_and_channels(sensor_cortical_type, cortical_group, neuron_encoder, initial_processor_chains, allow_stale_data)
+    }
+
     //endregion
 
     fn register_agent_device_index(&mut self, agent_device_index: AgentDeviceIndex, cortical_sensor_type: SensorCorticalType,
@@ -129,6 +161,35 @@ impl SensorCache {
     
     //endregion
     
+    //region Processor Chains
+
+    /// Replaces the processor chain of a registered channel while the cache is live, such as to
+    /// change thresholds or processing steps without registering the cortical area again.
+    ///
+    /// The new chain must accept the same data type as the channel does now, and output the type
+    /// accepted by the neuron encoder of the cortical area. The swap only happens once the chain is
+    /// verified, and the channel keeps its most recent value until the next update arrives.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the channel is not registered, or if the chain is empty or its data
+    /// types do not line up with the channel and encoder. The current chain is kept in that case.
+    pub fn set_channel_processors(&mut self, cortical_sensor_type: SensorCorticalType, cortical_grouping_index: CorticalGroupingIndex,
+                                  device_channel: CorticalIOChannelIndex,
+                                  processor_chain: Vec<Box<dyn StreamCacheProcessor + Sync + Send>>) -> Result<(), FeagiDataProcessingError> {
+        let cortical_type = cortical_sensor_type.into();
+        let cortical_area_details = self.cortical_area_metadata.get(&CorticalAreaMetadataKey::new(cortical_type, cortical_grouping_index));
+        let channel_cache = self.channel_caches.get_mut(&FullChannelCacheKey::new(cortical_type, cortical_grouping_index, device_channel));
+        let (cortical_area_details, channel_cache) = match (cortical_area_details, channel_cache) {
+            (Some(cortical_area_details), Some(channel_cache)) => (cortical_area_details, channel_cache),
+            _ => return Err(IODataError::InvalidParameters(format!("Unable to find Cortical Type {:?}, Group Index {:?}, Channel {:?}!", cortical_type, cortical_grouping_index, device_channel)).into())
+        };
+        verify_sensor_chain_and_encoder(&processor_chain, &cortical_area_details.neuron_encoder.get_encodable_data_type())?;
+        channel_cache.replace_processors(processor_chain)
+    }
+
+    //endregion
+    
     
     
     //region Send Data
@@ -171,6 +232,42 @@ impl SensorCache {
         }
         Ok(())
     }
+
+    /// Encodes every cortical area in parallel, giving the same output as `encode_to_neurons`.
+    ///
+    /// Each cortical area is encoded on the rayon thread pool into its own neuron data, which
+    /// is then moved into `neurons_to_encode_to` in the same area order as the serial path. As
+    /// with the built-in encoders, encoders must clear the neuron arrays they write to (for
+    /// example with `ensure_clear_and_borrow_mut`) for the result to match the serial path.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first failing cortical area in serial order, with all areas
+    /// before it (and any channels of it encoded before the failure) written, as `encode_to_neurons` would.
+    #[cfg(feature = "parallel")]
+    pub fn encode_to_neurons_parallel(&self, _past_send_time: Instant, neurons_to_encode_to: &mut CorticalMappedXYZPNeuronData) -> Result<(), FeagiDataProcessingError> {
+        use rayon::prelude::*;
+
+        let cortical_areas: Vec<&CorticalAreaCacheDetails> = self.cortical_area_metadata.values().collect();
+        let encoded_areas: Vec<(CorticalMappedXYZPNeuronData, Result<(), FeagiDataProcessingError>)> = cortical_areas.par_iter()
+            .map(|cortical_area_details| {
+                let mut area_neuron_data = CorticalMappedXYZPNeuronData::new();
+                let result = cortical_area_details.relevant_channel_lookups.iter().try_for_each(|channel_cache_key| {
+                    let sensor_cache = self.channel_caches.get(channel_cache_key).unwrap();
+                    sensor_cache.encode_to_neurons(&mut area_neuron_data, &cortical_area_details.neuron_encoder)
+                });
+                (area_neuron_data, result)
+            })
+            .collect();
+
+        for (area_neuron_data, result) in encoded_areas {
+            for (cortical_id, neurons) in area_neuron_data.mappings {
+                _ = neurons_to_encode_to.insert(cortical_id, neurons);
+            }
+            result?;
+        }
+        Ok(())
+    }
     
     //endregion
     
@@ -204,7 +301,8 @@ impl SensorCache {
         
         
         let cortical_id = CorticalID::new_sensor_cortical_area_id(sensor_cortical_type, cortical_group)?;
-        let neuron_encoder = Box::new(F32LinearNeuronXYZPEncoder::new(cortical_id, neuron_resolution as u32)?);
+        let coder_parameters = NeuronCoderParameters::new(vec![cortical_id], IOTypeVariant::F32Normalized0To1, neuron_resolution as u32)?;
+        let neuron_encoder = self.coder_registry.create_encoder_for_cortical_type(&sensor_cortical_type.into(), &coder_parameters)?;
         let mut processors: Vec<Vec<Box<dyn StreamCacheProcessor + Sync + Send>>> = Vec::with_capacity(number_of_channels);
         for i in 0..number_of_channels {
             processors.push(vec![Box::new(LinearScaleTo0And1Processor::new(lower_bound, upper_bound, 0.0)?)]);
@@ -227,7 +325,8 @@ impl SensorCache {
         let image_transformer_definition = ImageFrameTransformer::new_from_input_output_properties(&input_image_properties, &output_image_properties)?;
         
         let cortical_id = CorticalID::new_sensor_cortical_area_id(sensor_cortical_type, cortical_group)?;
-        let neuron_encoder = Box::new(ImageFrameNeuronXYZPEncoder::new(cortical_id, &output_image_properties)?);
+        let coder_parameters = NeuronCoderParameters::new(vec![cortical_id], IOTypeVariant::ImageFrame(Some(output_image_properties)), 1)?;
+        let neuron_encoder = self.coder_registry.create_encoder_for_cortical_type(&sensor_cortical_type.into(), &coder_parameters)?;
         let mut processors: Vec<Vec<Box<dyn StreamCacheProcessor + Sync + Send>>> = Vec::with_capacity(number_of_channels);
         for i in 0..number_of_channels {
             processors.push(vec![Box::new(ImageFrameTransformerProcessor::new(image_transformer_definition)?)]);
@@ -246,7 +345,7 @@ impl SensorCache {
     
     fn register_cortical_area_and_channels(&mut self, sensor_cortical_type: SensorCorticalType, cortical_group: CorticalGroupingIndex,
                                            neuron_encoder: Box<dyn NeuronXYZPEncoder + Sync + Send>,
-                                           mut initial_processor_chains: Vec<Vec<Box<dyn StreamCacheProcessor + Sync + Send>>>,
+                                           initial_processor_chains: Vec<Vec<Box<dyn StreamCacheProcessor + Sync + Send>>>,
                                            allow_stale_data: bool) -> Result<(), FeagiDataProcessingError> {
         // NOTE: initial_processor_chains is a vector of vectors, meaning each channel gets a vector of processors
         
@@ -261,20 +360,28 @@ impl SensorCache {
         if self.cortical_area_metadata.contains_key(&cortical_metadata) {
             return Err(FeagiDataProcessingError::InternalError("Cortical area already registered!".into()).into())
         }
-
+        let encodable_data_type = neuron_encoder.get_encodable_data_type();
+        for processor_chain in &initial_processor_chains {
+            verify_sensor_chain_and_encoder(processor_chain, &encodable_data_type)?;
+        }
         
         
-        let mut cache_keys: Vec<FullChannelCacheKey> = Vec::with_capacity(number_supported_channels as usize);
-        for i in 0..number_supported_channels {
+        // Build every channel cache before inserting any, so a bad chain leaves nothing half registered
+        let mut sensor_caches: Vec<(FullChannelCacheKey, SensoryChannelStreamCache)> = Vec::with_capacity(number_supported_channels as usize);
+        for (i, processor_chain) in initial_processor_chains.into_iter().enumerate() {
             
-            let channel: CorticalIOChannelIndex = i.into();
+            let channel: CorticalIOChannelIndex = (i as u32).into();
             let sensor_key: FullChannelCacheKey = FullChannelCacheKey::new(cortical_type, cortical_group, channel);
             let sensor_cache: SensoryChannelStreamCache = SensoryChannelStreamCache::new(
-                initial_processor_chains.pop().unwrap(),
+                processor_chain,
                 channel,
                 allow_stale_data
             )?;
-            
+            sensor_caches.push((sensor_key, sensor_cache));
+        }
+        
+        let mut cache_keys: Vec<FullChannelCacheKey> = Vec::with_capacity(number_supported_channels as usize);
+        for (sensor_key, sensor_cache) in sensor_caches {
             _ = self.channel_caches.insert(sensor_key.clone(), sensor_cache);
             cache_keys.push(sensor_key);
         }
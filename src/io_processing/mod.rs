@@ -1,11 +1,14 @@
 mod callback_manager;
 mod stream_cache_processors;
 pub mod byte_structures;
+pub mod recording;
 mod sensory_channel_stream_cache;
+mod motor_channel_stream_cache;
 mod caches;
 
 pub use callback_manager::{CallBackManager, CallbackSubscriberID};
 
-pub use stream_cache_processors::{StreamCacheProcessor, processors};
+pub use stream_cache_processors::{StreamCacheProcessor, ProcessorRunner, ProcessorGraphRunner, ProcessorGraphNodeID, ProcessorGraphCombinator,
+                                  ProcessorDefinition, ProcessorChainBuilder, ImagePropertiesDefinition, SegmentedImagePropertiesDefinition, GazeDefinition, WindowLengthDefinition, processors};
 
-pub use caches::SensorCache;
+pub use caches::{SensorCache, MotorCache};
@@ -0,0 +1,101 @@
+//! Channel-level stream caching for motor data processing.
+//!
+//! This module provides per-channel caching/processing mechanisms for motor output streams
+//! in FEAGI's neural processing system.
+
+use std::time::{Instant};
+use crate::error::{FeagiDataProcessingError};
+use crate::genomic_structures::{CorticalIOChannelIndex};
+use crate::io_data::{IOTypeData, IOTypeVariant};
+use crate::io_processing::{CallBackManager, CallbackSubscriberID, StreamCacheProcessor};
+use crate::io_processing::stream_cache_processors::ProcessorRunner;
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData};
+use crate::neuron_data::xyzp::{NeuronXYZPDecoder};
+
+// TODO add callback for only on change
+
+/// Per-channel cache for motor output data streams.
+///
+/// `MotorChannelStreamCache` is the motor-side counterpart to `SensoryChannelStreamCache`.
+/// It decodes neuron data for a single I/O channel, applies a chain of stream processors
+/// to the decoded value, and notifies any registered callbacks of the processed result.
+///
+/// # Key Features
+///
+/// - **Neural Decoding**: Direct conversion from neural representations to I/O data
+/// - **Stream Processing**: Applies configurable processor chains to decoded data
+/// - **Callbacks**: Notifies subscribers every time a new value is decoded
+/// - **Temporal Tracking**: Monitors when data was last updated for freshness checks
+/// - **Type Safety**: Tracks input and output data types through the processing chain
+pub(crate) struct MotorChannelStreamCache {
+    processor_runner: ProcessorRunner,
+    channel: CorticalIOChannelIndex,
+    last_updated: Instant,
+    callbacks_all_bursts: CallBackManager,
+}
+
+impl MotorChannelStreamCache {
+
+    /// Creates a new motor channel stream cache with specified processing chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache_processors` - Vector of processors to apply to decoded data in order
+    /// * `channel` - The cortical I/O channel index this cache represents
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MotorChannelStreamCache)` - Successfully created cache
+    /// * `Err(FeagiDataProcessingError)` - If processor chain setup fails
+    pub fn new(cache_processors: Vec<Box<dyn StreamCacheProcessor + Sync + Send>>,
+               channel: CorticalIOChannelIndex) -> Result<Self, FeagiDataProcessingError> {
+
+        let processor_runner = ProcessorRunner::new(cache_processors)?;
+        let callbacks_all_bursts = CallBackManager::new(processor_runner.get_output_data_type());
+        Ok(MotorChannelStreamCache {
+            processor_runner,
+            channel,
+            last_updated: Instant::now(),
+            callbacks_all_bursts,
+        })
+    }
+
+    /// Decodes this channel from neuron data, processes it, and notifies all callbacks.
+    ///
+    /// # Arguments
+    ///
+    /// * `cortical_mapped_neuron_data` - Neuron data to read from
+    /// * `decoder` - Decoder that converts neural patterns to I/O data
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(&IOTypeData)` - The processed output of the chain
+    /// * `Err(FeagiDataProcessingError)` - If decoding or processing fails
+    pub fn decode_from_neurons(&mut self, cortical_mapped_neuron_data: &CorticalMappedXYZPNeuronData, decoder: &(dyn NeuronXYZPDecoder + Sync + Send)) -> Result<&IOTypeData, FeagiDataProcessingError> {
+        let decoded_value: IOTypeData = decoder.read_neuron_data_single_channel(self.channel, cortical_mapped_neuron_data)?;
+        self.last_updated = Instant::now();
+        let processed_value = self.processor_runner.update_value(&decoded_value, self.last_updated)?;
+        self.callbacks_all_bursts.emit(processed_value);
+        Ok(processed_value)
+    }
+
+    /// Returns the most recently processed motor value.
+    pub fn get_most_recent_motor_value(&self) -> &IOTypeData {
+        self.processor_runner.get_most_recent_output()
+    }
+
+    /// Registers a callback to be called with the processed value every time this channel is decoded.
+    pub fn register_callback(&mut self, callback: Box<dyn Fn(&IOTypeData) + Send + Sync>) -> CallbackSubscriberID {
+        self.callbacks_all_bursts.register(callback)
+    }
+
+    /// Deregisters a callback. Returns false if no such callback was registered.
+    pub fn deregister_callback(&mut self, callback_id: CallbackSubscriberID) -> bool {
+        self.callbacks_all_bursts.deregister(callback_id)
+    }
+
+    /// Returns the input data type expected by the processor chain (the decoder output).
+    pub fn get_input_data_type(&self) -> IOTypeVariant {
+        self.processor_runner.get_input_data_type()
+    }
+}
@@ -0,0 +1,227 @@
+//! Frames of a recorded session, and their binary layout within the recording file.
+
+use std::time::Duration;
+use byteorder::{ByteOrder, LittleEndian};
+use crate::error::{FeagiBytesError, FeagiDataProcessingError};
+use crate::genomic_structures::{CorticalGroupingIndex, CorticalID, CorticalIOChannelIndex, CorticalType, SensorCorticalType};
+use crate::io_data::{IOTypeData, ImageFrame, SegmentedImageFrame};
+use crate::io_processing::byte_structures::{FeagiByteStructure, FeagiByteStructureCompatible};
+use crate::io_processing::SensorCache;
+use crate::neuron_data::xyzp::CorticalMappedXYZPNeuronData;
+
+/// Magic bytes at the start of every session recording.
+pub(super) const RECORDING_MAGIC: [u8; 8] = *b"FEAGIREC";
+/// Version of the recording container format.
+pub(super) const RECORDING_FORMAT_VERSION: u8 = 1;
+/// Header size: magic (8), version (1), reserved (7), index offset (u64), frame count (u64).
+pub(super) const RECORDING_HEADER_SIZE_IN_BYTES: usize = 32;
+/// Offset of the index offset field within the header.
+pub(super) const RECORDING_HEADER_INDEX_OFFSET_POSITION: usize = 16;
+/// Size of the length prefix preceding each frame.
+pub(super) const FRAME_LENGTH_PREFIX_SIZE_IN_BYTES: usize = 4;
+/// Frame body header size: timestamp in microseconds (u64) and frame kind (u8).
+pub(super) const FRAME_BODY_HEADER_SIZE_IN_BYTES: usize = 9;
+/// Index entry size: frame offset (u64), timestamp in microseconds (u64) and frame kind (u8).
+pub(super) const INDEX_ENTRY_SIZE_IN_BYTES: usize = 17;
+
+pub(super) const FRAME_KIND_NEURON_DATA: u8 = 1;
+pub(super) const FRAME_KIND_SENSOR_INPUT: u8 = 2;
+
+const SENSOR_VALUE_F32: u8 = 0;
+const SENSOR_VALUE_F32_NORMALIZED_0_TO_1: u8 = 1;
+const SENSOR_VALUE_F32_NORMALIZED_M1_TO_1: u8 = 2;
+const SENSOR_VALUE_IMAGE_FRAME: u8 = 3;
+const SENSOR_VALUE_SEGMENTED_IMAGE_FRAME: u8 = 4;
+
+/// Sensor input header size: sensor type as a group 0 cortical ID (6), group (u8), channel (u32), value type (u8).
+const SENSOR_INPUT_HEADER_SIZE_IN_BYTES: usize = CorticalID::CORTICAL_ID_LENGTH + 6;
+
+/// What a recorded frame holds.
+#[derive(Debug, Clone)]
+pub enum FeagiSessionFrameContent {
+    /// A burst of neuron data.
+    NeuronData(CorticalMappedXYZPNeuronData),
+    /// A raw value sent to one channel of a sensor, as given to `SensorCache::update_value_by_channel`.
+    SensorInput {
+        sensor_cortical_type: SensorCorticalType,
+        cortical_grouping_index: CorticalGroupingIndex,
+        device_channel: CorticalIOChannelIndex,
+        /// Boxed, as image values are far larger than neuron data.
+        value: Box<IOTypeData>,
+    },
+}
+
+/// A single timestamped frame of a recorded session.
+#[derive(Debug, Clone)]
+pub struct FeagiSessionFrame {
+    timestamp: Duration,
+    content: FeagiSessionFrameContent,
+}
+
+impl FeagiSessionFrame {
+
+    /// Creates a frame from its time since the start of the recording, and its content.
+    pub fn new(timestamp: Duration, content: FeagiSessionFrameContent) -> Self {
+        FeagiSessionFrame { timestamp, content }
+    }
+
+    /// Returns the time since the start of the recording this frame was recorded at.
+    pub fn get_timestamp(&self) -> Duration {
+        self.timestamp
+    }
+
+    /// Borrows the content of this frame.
+    pub fn get_content(&self) -> &FeagiSessionFrameContent {
+        &self.content
+    }
+
+    /// Consumes this frame, returning its content.
+    pub fn into_content(self) -> FeagiSessionFrameContent {
+        self.content
+    }
+
+    /// Sends a recorded sensor input to the matching channel of a sensor cache.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If this frame was a sensor input, and was sent
+    /// * `Ok(false)` - If this frame holds neuron data, which is not sent anywhere
+    /// * `Err(FeagiDataProcessingError)` - If the sensor cache has no such channel, or it
+    ///   expects a different type of value
+    pub fn apply_to_sensor_cache(&self, sensor_cache: &mut SensorCache) -> Result<bool, FeagiDataProcessingError> {
+        match &self.content {
+            FeagiSessionFrameContent::NeuronData(_) => Ok(false),
+            FeagiSessionFrameContent::SensorInput { sensor_cortical_type, cortical_grouping_index, device_channel, value } => {
+                sensor_cache.update_value_by_channel(value.as_ref().clone(), *sensor_cortical_type, *cortical_grouping_index, *device_channel)?;
+                Ok(true)
+            }
+        }
+    }
+
+    //region Internal Functions
+
+    /// Returns the kind byte stored for this frame in the recording index.
+    pub(super) fn get_kind(&self) -> u8 {
+        match self.content {
+            FeagiSessionFrameContent::NeuronData(_) => FRAME_KIND_NEURON_DATA,
+            FeagiSessionFrameContent::SensorInput { .. } => FRAME_KIND_SENSOR_INPUT,
+        }
+    }
+
+    /// Appends the body of this frame (everything but its length prefix) to a buffer.
+    pub(super) fn write_body(&self, buffer: &mut Vec<u8>) -> Result<(), FeagiDataProcessingError> {
+        match &self.content {
+            FeagiSessionFrameContent::NeuronData(neuron_data) => write_neuron_data_body(buffer, self.timestamp, neuron_data),
+            FeagiSessionFrameContent::SensorInput { sensor_cortical_type, cortical_grouping_index, device_channel, value } =>
+                write_sensor_input_body(buffer, self.timestamp, value, *sensor_cortical_type, *cortical_grouping_index, *device_channel),
+        }
+    }
+
+    /// Parses a frame from its body (everything but its length prefix).
+    pub(super) fn new_from_body(body: &[u8]) -> Result<Self, FeagiDataProcessingError> {
+        if body.len() < FRAME_BODY_HEADER_SIZE_IN_BYTES {
+            return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Recorded frame of {} bytes is too short to hold a frame header!", body.len())).into());
+        }
+        let timestamp = Duration::from_micros(LittleEndian::read_u64(&body[0..8]));
+        let payload = &body[FRAME_BODY_HEADER_SIZE_IN_BYTES..];
+
+        let content = match body[8] {
+            FRAME_KIND_NEURON_DATA => {
+                let feagi_byte_structure = FeagiByteStructure::create_from_bytes(payload.to_vec())?;
+                FeagiSessionFrameContent::NeuronData(CorticalMappedXYZPNeuronData::new_from_feagi_byte_structure(&feagi_byte_structure)?)
+            }
+            FRAME_KIND_SENSOR_INPUT => read_sensor_input(payload)?,
+            unknown_kind => return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Unknown recorded frame kind {}!", unknown_kind)).into()),
+        };
+        Ok(FeagiSessionFrame { timestamp, content })
+    }
+
+    //endregion
+}
+
+pub(super) fn duration_to_micros(duration: Duration) -> u64 {
+    duration.as_micros().min(u64::MAX as u128) as u64
+}
+
+/// Appends the body of a neuron data frame to a buffer, serializing the neuron data directly into it.
+pub(super) fn write_neuron_data_body(buffer: &mut Vec<u8>, timestamp: Duration, neuron_data: &CorticalMappedXYZPNeuronData) -> Result<(), FeagiDataProcessingError> {
+    write_body_header(buffer, timestamp, FRAME_KIND_NEURON_DATA);
+    write_byte_structure(buffer, neuron_data)
+}
+
+/// Appends the body of a sensor input frame to a buffer, serializing the value directly into it.
+pub(super) fn write_sensor_input_body(buffer: &mut Vec<u8>, timestamp: Duration, value: &IOTypeData, sensor_cortical_type: SensorCorticalType,
+                                      cortical_grouping_index: CorticalGroupingIndex, device_channel: CorticalIOChannelIndex) -> Result<(), FeagiDataProcessingError> {
+    write_body_header(buffer, timestamp, FRAME_KIND_SENSOR_INPUT);
+    buffer.extend_from_slice(sensor_cortical_type.to_cortical_id(0.into()).as_bytes());
+    buffer.push(*cortical_grouping_index);
+    let mut channel_bytes = [0u8; 4];
+    LittleEndian::write_u32(&mut channel_bytes, *device_channel);
+    buffer.extend_from_slice(&channel_bytes);
+    match value {
+        IOTypeData::F32(float) => write_f32_value(buffer, SENSOR_VALUE_F32, *float),
+        IOTypeData::F32Normalized0To1(float) => write_f32_value(buffer, SENSOR_VALUE_F32_NORMALIZED_0_TO_1, *float),
+        IOTypeData::F32NormalizedM1To1(float) => write_f32_value(buffer, SENSOR_VALUE_F32_NORMALIZED_M1_TO_1, *float),
+        IOTypeData::ImageFrame(frame) => {
+            buffer.push(SENSOR_VALUE_IMAGE_FRAME);
+            write_byte_structure(buffer, frame)?;
+        }
+        IOTypeData::SegmentedImageFrame(frame) => {
+            buffer.push(SENSOR_VALUE_SEGMENTED_IMAGE_FRAME);
+            write_byte_structure(buffer, frame)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_body_header(buffer: &mut Vec<u8>, timestamp: Duration, kind: u8) {
+    let mut body_header = [0u8; FRAME_BODY_HEADER_SIZE_IN_BYTES];
+    LittleEndian::write_u64(&mut body_header[0..8], duration_to_micros(timestamp));
+    body_header[8] = kind;
+    buffer.extend_from_slice(&body_header);
+}
+
+fn write_byte_structure<T: FeagiByteStructureCompatible>(buffer: &mut Vec<u8>, structure: &T) -> Result<(), FeagiDataProcessingError> {
+    let start = buffer.len();
+    buffer.resize(start + structure.max_number_bytes_needed(), 0);
+    let wasted_bytes = structure.overwrite_feagi_byte_structure_slice(&mut buffer[start..])?;
+    buffer.truncate(buffer.len() - wasted_bytes);
+    Ok(())
+}
+
+fn write_f32_value(buffer: &mut Vec<u8>, value_type: u8, value: f32) {
+    buffer.push(value_type);
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+fn read_sensor_input(payload: &[u8]) -> Result<FeagiSessionFrameContent, FeagiDataProcessingError> {
+    if payload.len() < SENSOR_INPUT_HEADER_SIZE_IN_BYTES {
+        return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Recorded sensor input of {} bytes is too short to hold its header!", payload.len())).into());
+    }
+    let mut cortical_id_bytes = [0u8; CorticalID::CORTICAL_ID_LENGTH];
+    cortical_id_bytes.copy_from_slice(&payload[0..CorticalID::CORTICAL_ID_LENGTH]);
+    let sensor_cortical_type = match CorticalID::from_bytes(&cortical_id_bytes)?.get_cortical_type() {
+        CorticalType::Sensory(sensor_cortical_type) => sensor_cortical_type,
+        cortical_type => return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Recorded sensor input is for non sensor type {}!", cortical_type)).into()),
+    };
+    let header = &payload[CorticalID::CORTICAL_ID_LENGTH..SENSOR_INPUT_HEADER_SIZE_IN_BYTES];
+    let cortical_grouping_index: CorticalGroupingIndex = header[0].into();
+    let device_channel: CorticalIOChannelIndex = LittleEndian::read_u32(&header[1..5]).into();
+    let value_bytes = &payload[SENSOR_INPUT_HEADER_SIZE_IN_BYTES..];
+
+    let read_f32 = || -> Result<f32, FeagiDataProcessingError> {
+        if value_bytes.len() != 4 {
+            return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Recorded float sensor value must be 4 bytes, not {}!", value_bytes.len())).into());
+        }
+        Ok(LittleEndian::read_f32(value_bytes))
+    };
+    let value = match header[5] {
+        SENSOR_VALUE_F32 => IOTypeData::F32(read_f32()?),
+        SENSOR_VALUE_F32_NORMALIZED_0_TO_1 => IOTypeData::F32Normalized0To1(read_f32()?),
+        SENSOR_VALUE_F32_NORMALIZED_M1_TO_1 => IOTypeData::F32NormalizedM1To1(read_f32()?),
+        SENSOR_VALUE_IMAGE_FRAME => IOTypeData::ImageFrame(ImageFrame::new_from_feagi_byte_structure(&FeagiByteStructure::create_from_bytes(value_bytes.to_vec())?)?),
+        SENSOR_VALUE_SEGMENTED_IMAGE_FRAME => IOTypeData::SegmentedImageFrame(SegmentedImageFrame::new_from_feagi_byte_structure(&FeagiByteStructure::create_from_bytes(value_bytes.to_vec())?)?),
+        unknown_type => return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Unknown recorded sensor value type {}!", unknown_type)).into()),
+    };
+
+    Ok(FeagiSessionFrameContent::SensorInput { sensor_cortical_type, cortical_grouping_index, device_channel, value: Box::new(value) })
+}
@@ -0,0 +1,320 @@
+//! Plays back a recorded session, at its original or an accelerated pace.
+
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+use std::time::{Duration, Instant};
+use byteorder::{ByteOrder, LittleEndian};
+use crate::error::{FeagiBytesError, FeagiDataProcessingError, IODataError};
+use crate::io_processing::SensorCache;
+use super::feagi_session_frame::*;
+
+/// Location and time of a single recorded frame.
+#[derive(Debug, Clone, Copy)]
+struct FrameIndexEntry {
+    offset: u64,
+    timestamp: Duration,
+}
+
+/// Reads the frames of a session recording, sequentially at their recorded pace or by seeking.
+///
+/// By default, [`next_frame`](Self::next_frame) waits until each frame is due at its original
+/// timing. [`set_playback_speed`](Self::set_playback_speed) speeds this up, or disables waiting.
+///
+/// # Example
+/// ```rust
+/// use std::io::{Cursor, Seek};
+/// use std::time::Duration;
+/// use feagi_core_data_structures_and_processing::genomic_structures::SensorCorticalType;
+/// use feagi_core_data_structures_and_processing::io_data::IOTypeData;
+/// use feagi_core_data_structures_and_processing::io_processing::recording::{FeagiSessionFrameContent, FeagiSessionPlayer, FeagiSessionRecorder};
+///
+/// let mut recorder = FeagiSessionRecorder::new(Cursor::new(Vec::new())).unwrap();
+/// for tick in 0..10u64 {
+///     recorder.record_sensor_input_at(Duration::from_millis(tick * 100), &IOTypeData::F32(tick as f32), SensorCorticalType::Proximity, 0.into(), 0.into()).unwrap();
+/// }
+/// let mut file = recorder.finish().unwrap();
+/// file.rewind().unwrap();
+/// let mut player = FeagiSessionPlayer::new(file).unwrap();
+///
+/// // Skip to the frame recorded half a second in, and play the rest as fast as possible
+/// player.seek_to_time(Duration::from_millis(500)).unwrap();
+/// player.set_playback_speed(None).unwrap();
+/// let frame = player.next_frame().unwrap().unwrap();
+/// assert_eq!(frame.get_timestamp(), Duration::from_millis(500));
+/// match frame.get_content() {
+///     FeagiSessionFrameContent::SensorInput { value, .. } => assert_eq!(f32::try_from(value.as_ref()).unwrap(), 5.0),
+///     _ => unreachable!(),
+/// }
+/// ```
+pub struct FeagiSessionPlayer<R: Read + Seek> {
+    reader: R,
+    header_position: u64,
+    index: Vec<FrameIndexEntry>,
+    frames_end: u64,
+    next_frame_index: usize,
+    playback_speed: Option<f32>,
+    playback_clock: Option<(Instant, Duration)>,
+    frame_buffer: Vec<u8>,
+}
+
+impl<R: Read + Seek> FeagiSessionPlayer<R> {
+
+    /// Opens a recording starting at the current position of the given reader.
+    ///
+    /// If the recording was never finished, its index is rebuilt by scanning every frame, and
+    /// an incomplete last frame is ignored.
+    ///
+    /// # Returns
+    /// * `Ok(FeagiSessionPlayer)` - A player positioned at the first frame
+    /// * `Err(FeagiDataProcessingError)` - If this is not a session recording of a supported
+    ///   version, its index is corrupt, or the reader failed
+    pub fn new(mut reader: R) -> Result<Self, FeagiDataProcessingError> {
+        let header_position = reader.stream_position().map_err(io_error)?;
+        let mut header = [0u8; RECORDING_HEADER_SIZE_IN_BYTES];
+        reader.read_exact(&mut header).map_err(io_error)?;
+        if header[0..RECORDING_MAGIC.len()] != RECORDING_MAGIC {
+            return Err(FeagiBytesError::UnableToValidateBytes("Data is not a FEAGI session recording!".into()).into());
+        }
+        if header[RECORDING_MAGIC.len()] != RECORDING_FORMAT_VERSION {
+            return Err(FeagiBytesError::UnableToValidateBytes(format!("Unsupported session recording format version {}!", header[RECORDING_MAGIC.len()])).into());
+        }
+        let index_offset = LittleEndian::read_u64(&header[RECORDING_HEADER_INDEX_OFFSET_POSITION..RECORDING_HEADER_INDEX_OFFSET_POSITION + 8]);
+        let frame_count = LittleEndian::read_u64(&header[RECORDING_HEADER_INDEX_OFFSET_POSITION + 8..RECORDING_HEADER_INDEX_OFFSET_POSITION + 16]);
+
+        let mut player = FeagiSessionPlayer {
+            reader,
+            header_position,
+            index: Vec::new(),
+            frames_end: RECORDING_HEADER_SIZE_IN_BYTES as u64,
+            next_frame_index: 0,
+            playback_speed: Some(1.0),
+            playback_clock: None,
+            frame_buffer: Vec::new(),
+        };
+        if index_offset == 0 {
+            player.rebuild_index()?;
+        } else {
+            player.read_index(index_offset, frame_count)?;
+        }
+        Ok(player)
+    }
+
+    /// Returns the number of frames in the recording.
+    pub fn get_frame_count(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns the timestamp of the last frame, which is the length of the recording.
+    pub fn get_duration(&self) -> Duration {
+        self.index.last().map(|entry| entry.timestamp).unwrap_or(Duration::ZERO)
+    }
+
+    /// Returns the timestamp of the frame at the given index, if it exists.
+    pub fn get_frame_timestamp(&self, frame_index: usize) -> Option<Duration> {
+        self.index.get(frame_index).map(|entry| entry.timestamp)
+    }
+
+    /// Returns the index of the frame `next_frame` will return.
+    pub fn get_position(&self) -> usize {
+        self.next_frame_index
+    }
+
+    /// Returns the playback speed, where None means frames are returned without waiting.
+    pub fn get_playback_speed(&self) -> Option<f32> {
+        self.playback_speed
+    }
+
+    /// Sets how fast `next_frame` plays back frames relative to their recorded timing.
+    ///
+    /// `Some(1.0)` plays at the original pace, `Some(4.0)` four times as fast, and `None` returns
+    /// every frame immediately.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the speed was set
+    /// * `Err(FeagiDataProcessingError)` - If the speed is not a positive finite number
+    pub fn set_playback_speed(&mut self, playback_speed: Option<f32>) -> Result<(), FeagiDataProcessingError> {
+        if let Some(speed) = playback_speed && !(speed.is_finite() && speed > 0.0) {
+            return Err(IODataError::InvalidParameters(format!("Playback speed must be a positive finite number, not {}!", speed)).into());
+        }
+        self.playback_speed = playback_speed;
+        self.playback_clock = None;
+        Ok(())
+    }
+
+    /// Moves playback to the frame at the given index. Timing restarts from that frame.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the position was set. Seeking to the frame count ends playback
+    /// * `Err(FeagiDataProcessingError)` - If the index is past the frame count
+    pub fn seek_to_frame(&mut self, frame_index: usize) -> Result<(), FeagiDataProcessingError> {
+        if frame_index > self.index.len() {
+            return Err(IODataError::InvalidParameters(format!("Cannot seek to frame {} of a recording with {} frames!", frame_index, self.index.len())).into());
+        }
+        self.next_frame_index = frame_index;
+        self.playback_clock = None;
+        Ok(())
+    }
+
+    /// Moves playback to the first frame recorded at or after the given time. Timing restarts from that frame.
+    pub fn seek_to_time(&mut self, timestamp: Duration) -> Result<(), FeagiDataProcessingError> {
+        let frame_index = self.index.partition_point(|entry| entry.timestamp < timestamp);
+        self.seek_to_frame(frame_index)
+    }
+
+    /// Reads the frame at the given index, without waiting or moving the playback position.
+    pub fn read_frame(&mut self, frame_index: usize) -> Result<FeagiSessionFrame, FeagiDataProcessingError> {
+        let entry = *self.index.get(frame_index)
+            .ok_or_else(|| IODataError::InvalidParameters(format!("Frame {} does not exist in a recording with {} frames!", frame_index, self.index.len())))?;
+
+        // A frame cannot extend past the next one, or past the index for the last frame
+        let next_offset = self.index.get(frame_index + 1).map(|next| next.offset).unwrap_or(self.frames_end);
+        let max_body_length = next_offset.checked_sub(entry.offset)
+            .and_then(|frame_space| frame_space.checked_sub(FRAME_LENGTH_PREFIX_SIZE_IN_BYTES as u64))
+            .ok_or_else(|| FeagiBytesError::UnableToValidateBytes(format!("Session recording frame {} overlaps the frame after it!", frame_index)))?;
+
+        let frame_position = self.position_in_reader(entry.offset)?;
+        self.reader.seek(SeekFrom::Start(frame_position)).map_err(io_error)?;
+        let mut length_prefix = [0u8; FRAME_LENGTH_PREFIX_SIZE_IN_BYTES];
+        self.reader.read_exact(&mut length_prefix).map_err(io_error)?;
+        let body_length = LittleEndian::read_u32(&length_prefix) as u64;
+        if body_length > max_body_length {
+            return Err(FeagiBytesError::UnableToValidateBytes(format!("Session recording frame {} claims a length of {} bytes, but only {} are available!", frame_index, body_length, max_body_length)).into());
+        }
+        self.frame_buffer.resize(body_length as usize, 0);
+        self.reader.read_exact(&mut self.frame_buffer).map_err(io_error)?;
+        FeagiSessionFrame::new_from_body(&self.frame_buffer)
+    }
+
+    /// Returns the next frame, once it is due at the current playback speed.
+    ///
+    /// # Returns
+    /// * `Ok(Some(FeagiSessionFrame))` - The next frame
+    /// * `Ok(None)` - If playback reached the end of the recording
+    /// * `Err(FeagiDataProcessingError)` - If the frame could not be read, or the playback speed is
+    ///   too slow to schedule it
+    pub fn next_frame(&mut self) -> Result<Option<FeagiSessionFrame>, FeagiDataProcessingError> {
+        if self.next_frame_index >= self.index.len() {
+            return Ok(None);
+        }
+        let frame = self.read_frame(self.next_frame_index)?;
+
+        if let Some(speed) = self.playback_speed {
+            let (clock_start, clock_start_timestamp) = *self.playback_clock.get_or_insert((Instant::now(), frame.get_timestamp()));
+            let recorded_delay = frame.get_timestamp().saturating_sub(clock_start_timestamp);
+            let due = Duration::try_from_secs_f64(recorded_delay.as_secs_f64() / speed as f64).ok()
+                .and_then(|delay| clock_start.checked_add(delay))
+                .ok_or_else(|| IODataError::InvalidParameters(format!("Playback speed {} is too slow to schedule a frame recorded {:?} in!", speed, recorded_delay)))?;
+            self.next_frame_index += 1;
+            let now = Instant::now();
+            if due > now {
+                std::thread::sleep(due - now);
+            }
+        } else {
+            self.next_frame_index += 1;
+        }
+        Ok(Some(frame))
+    }
+
+    /// Plays the rest of the recording into a sensor cache, at the current playback speed.
+    ///
+    /// Every sensor input is sent to its channel of the sensor cache, after which `on_frame` is
+    /// called with the frame and the cache, for example to encode the cache into neurons.
+    /// Recorded neuron data is not sent anywhere, but still passed to `on_frame`.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - The number of sensor inputs sent
+    /// * `Err(FeagiDataProcessingError)` - The first error reading a frame, sending it, or from `on_frame`
+    pub fn replay_into_sensor_cache<F>(&mut self, sensor_cache: &mut SensorCache, mut on_frame: F) -> Result<usize, FeagiDataProcessingError>
+    where
+        F: FnMut(&FeagiSessionFrame, &mut SensorCache) -> Result<(), FeagiDataProcessingError>,
+    {
+        let mut sent_count: usize = 0;
+        while let Some(frame) = self.next_frame()? {
+            if frame.apply_to_sensor_cache(sensor_cache)? {
+                sent_count += 1;
+            }
+            on_frame(&frame, sensor_cache)?;
+        }
+        Ok(sent_count)
+    }
+
+    /// Consumes this player, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    //region Internal Functions
+
+    fn read_index(&mut self, index_offset: u64, frame_count: u64) -> Result<(), FeagiDataProcessingError> {
+        let index_length = frame_count.checked_mul(INDEX_ENTRY_SIZE_IN_BYTES as u64)
+            .ok_or_else(|| FeagiBytesError::UnableToValidateBytes(format!("Session recording index claims an invalid frame count of {}!", frame_count)))?;
+        let end_position = self.reader.seek(SeekFrom::End(0)).map_err(io_error)?;
+        let index_position = self.position_in_reader(index_offset)?;
+        let index_end = index_position.checked_add(index_length)
+            .ok_or_else(|| FeagiBytesError::UnableToValidateBytes("Session recording index lies outside of the recording!".into()))?;
+        if index_offset < RECORDING_HEADER_SIZE_IN_BYTES as u64 || index_end > end_position {
+            return Err(FeagiBytesError::UnableToValidateBytes("Session recording index lies outside of the recording!".into()).into());
+        }
+
+        self.reader.seek(SeekFrom::Start(index_position)).map_err(io_error)?;
+        let mut index_bytes = vec![0u8; index_length as usize];
+        self.reader.read_exact(&mut index_bytes).map_err(io_error)?;
+        self.index = index_bytes.chunks_exact(INDEX_ENTRY_SIZE_IN_BYTES)
+            .map(|entry| FrameIndexEntry {
+                offset: LittleEndian::read_u64(&entry[0..8]),
+                timestamp: Duration::from_micros(LittleEndian::read_u64(&entry[8..16])),
+            })
+            .collect();
+        if self.index.iter().any(|entry| entry.offset < RECORDING_HEADER_SIZE_IN_BYTES as u64 || entry.offset >= index_offset)
+            || !self.index.is_sorted_by_key(|entry| entry.timestamp) {
+            self.index.clear();
+            return Err(FeagiBytesError::UnableToValidateBytes("Session recording index is corrupt!".into()).into());
+        }
+        self.frames_end = index_offset;
+        Ok(())
+    }
+
+    /// Rebuilds the index of an unfinished recording by walking the length prefix of every frame.
+    ///
+    /// Scanning stops at the first incomplete frame, or at a frame recorded earlier than the one
+    /// before it, so the index stays in timestamp order for seeking.
+    fn rebuild_index(&mut self) -> Result<(), FeagiDataProcessingError> {
+        let mut offset = RECORDING_HEADER_SIZE_IN_BYTES as u64;
+        let mut frame_header = [0u8; FRAME_LENGTH_PREFIX_SIZE_IN_BYTES + FRAME_BODY_HEADER_SIZE_IN_BYTES];
+        let end_position = self.reader.seek(SeekFrom::End(0)).map_err(io_error)?;
+        loop {
+            let frame_position = self.position_in_reader(offset)?;
+            self.reader.seek(SeekFrom::Start(frame_position)).map_err(io_error)?;
+            match self.reader.read_exact(&mut frame_header) {
+                Ok(()) => {}
+                Err(err) if err.kind() == ErrorKind::UnexpectedEof => break,
+                Err(err) => return Err(io_error(err)),
+            }
+            let body_length = LittleEndian::read_u32(&frame_header[0..FRAME_LENGTH_PREFIX_SIZE_IN_BYTES]) as u64;
+            let frame_end = offset.checked_add(FRAME_LENGTH_PREFIX_SIZE_IN_BYTES as u64 + body_length)
+                .ok_or_else(|| FeagiBytesError::UnableToValidateBytes("Session recording frame lies outside of the recording!".into()))?;
+            if body_length < FRAME_BODY_HEADER_SIZE_IN_BYTES as u64 || self.position_in_reader(frame_end)? > end_position {
+                break; // The recording was cut off while writing this frame
+            }
+            let timestamp = Duration::from_micros(LittleEndian::read_u64(&frame_header[FRAME_LENGTH_PREFIX_SIZE_IN_BYTES..FRAME_LENGTH_PREFIX_SIZE_IN_BYTES + 8]));
+            if self.index.last().is_some_and(|previous| previous.timestamp > timestamp) {
+                break; // Not written by the recorder, which never goes back in time
+            }
+            self.index.push(FrameIndexEntry { offset, timestamp });
+            offset = frame_end;
+        }
+        self.frames_end = offset;
+        Ok(())
+    }
+
+    /// Converts an offset within the recording into a position in the reader.
+    fn position_in_reader(&self, offset: u64) -> Result<u64, FeagiDataProcessingError> {
+        self.header_position.checked_add(offset)
+            .ok_or_else(|| FeagiBytesError::UnableToValidateBytes(format!("Session recording offset {} lies outside of the recording!", offset)).into())
+    }
+
+    //endregion
+}
+
+fn io_error(err: std::io::Error) -> FeagiDataProcessingError {
+    FeagiBytesError::UnableToDeserializeBytes(format!("Unable to read session recording: {}", err)).into()
+}
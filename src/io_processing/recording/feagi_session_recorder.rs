@@ -0,0 +1,173 @@
+//! Records a session of neuron bursts and sensor inputs to a seekable file.
+
+use std::io::{Seek, SeekFrom, Write};
+use std::time::{Duration, Instant};
+use byteorder::{ByteOrder, LittleEndian};
+use crate::error::{FeagiBytesError, FeagiDataProcessingError, IODataError};
+use crate::genomic_structures::{CorticalGroupingIndex, CorticalIOChannelIndex, SensorCorticalType};
+use crate::io_data::IOTypeData;
+use crate::neuron_data::xyzp::CorticalMappedXYZPNeuronData;
+use super::feagi_session_frame::*;
+
+/// Appends timestamped neuron data and sensor inputs to a session recording.
+///
+/// Frames are written as they are recorded. [`finish`](Self::finish) then appends an index of
+/// every frame's offset and patches it into the header, allowing players to seek. A recording
+/// that was never finished (for example because the agent crashed) is still playable, as the
+/// player rebuilds the index by scanning its frames.
+///
+/// # Example
+/// ```rust
+/// use std::io::{Cursor, Seek};
+/// use std::time::Duration;
+/// use feagi_core_data_structures_and_processing::genomic_structures::SensorCorticalType;
+/// use feagi_core_data_structures_and_processing::io_data::IOTypeData;
+/// use feagi_core_data_structures_and_processing::io_processing::recording::{FeagiSessionPlayer, FeagiSessionRecorder};
+///
+/// let mut recorder = FeagiSessionRecorder::new(Cursor::new(Vec::new())).unwrap();
+/// recorder.record_sensor_input_at(Duration::from_millis(0), &IOTypeData::F32(10.0), SensorCorticalType::Proximity, 0.into(), 0.into()).unwrap();
+/// recorder.record_sensor_input_at(Duration::from_millis(50), &IOTypeData::F32(12.5), SensorCorticalType::Proximity, 0.into(), 0.into()).unwrap();
+/// let mut file = recorder.finish().unwrap();
+/// file.rewind().unwrap();
+///
+/// let player = FeagiSessionPlayer::new(file).unwrap();
+/// assert_eq!(player.get_frame_count(), 2);
+/// assert_eq!(player.get_duration(), Duration::from_millis(50));
+/// ```
+pub struct FeagiSessionRecorder<W: Write + Seek> {
+    writer: W,
+    header_position: u64,
+    start_time: Instant,
+    last_timestamp: Duration,
+    written_byte_count: u64,
+    index: Vec<u8>,
+    frame_buffer: Vec<u8>,
+}
+
+impl<W: Write + Seek> FeagiSessionRecorder<W> {
+
+    /// Starts a recording at the current position of the given writer, writing its header.
+    ///
+    /// Timestamps of frames recorded without an explicit time are measured from this call.
+    pub fn new(mut writer: W) -> Result<Self, FeagiDataProcessingError> {
+        let header_position = writer.stream_position().map_err(io_error)?;
+        let mut header = [0u8; RECORDING_HEADER_SIZE_IN_BYTES];
+        header[0..RECORDING_MAGIC.len()].copy_from_slice(&RECORDING_MAGIC);
+        header[RECORDING_MAGIC.len()] = RECORDING_FORMAT_VERSION;
+        writer.write_all(&header).map_err(io_error)?;
+        Ok(FeagiSessionRecorder {
+            writer,
+            header_position,
+            start_time: Instant::now(),
+            last_timestamp: Duration::ZERO,
+            written_byte_count: RECORDING_HEADER_SIZE_IN_BYTES as u64,
+            index: Vec::new(),
+            frame_buffer: Vec::new(),
+        })
+    }
+
+    /// Records a burst of neuron data, timestamped with the time since the recording started.
+    pub fn record_neuron_data(&mut self, neuron_data: &CorticalMappedXYZPNeuronData) -> Result<(), FeagiDataProcessingError> {
+        let timestamp = self.elapsed_timestamp();
+        self.record_neuron_data_at(timestamp, neuron_data)
+    }
+
+    /// Records a burst of neuron data with an explicit time since the start of the recording.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the frame was written
+    /// * `Err(FeagiDataProcessingError)` - If the timestamp is earlier than the previous frame's,
+    ///   the neuron data cannot be serialized, or the writer failed
+    pub fn record_neuron_data_at(&mut self, timestamp: Duration, neuron_data: &CorticalMappedXYZPNeuronData) -> Result<(), FeagiDataProcessingError> {
+        self.write_frame(timestamp, FRAME_KIND_NEURON_DATA, |buffer| write_neuron_data_body(buffer, timestamp, neuron_data))
+    }
+
+    /// Records a raw value sent to a sensor channel, timestamped with the time since the recording started.
+    pub fn record_sensor_input(&mut self, value: &IOTypeData, sensor_cortical_type: SensorCorticalType,
+                               cortical_grouping_index: CorticalGroupingIndex, device_channel: CorticalIOChannelIndex) -> Result<(), FeagiDataProcessingError> {
+        let timestamp = self.elapsed_timestamp();
+        self.record_sensor_input_at(timestamp, value, sensor_cortical_type, cortical_grouping_index, device_channel)
+    }
+
+    /// Records a raw value sent to a sensor channel, with an explicit time since the start of the recording.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the frame was written
+    /// * `Err(FeagiDataProcessingError)` - If the timestamp is earlier than the previous frame's,
+    ///   the value cannot be serialized, or the writer failed
+    pub fn record_sensor_input_at(&mut self, timestamp: Duration, value: &IOTypeData, sensor_cortical_type: SensorCorticalType,
+                                  cortical_grouping_index: CorticalGroupingIndex, device_channel: CorticalIOChannelIndex) -> Result<(), FeagiDataProcessingError> {
+        self.write_frame(timestamp, FRAME_KIND_SENSOR_INPUT,
+                         |buffer| write_sensor_input_body(buffer, timestamp, value, sensor_cortical_type, cortical_grouping_index, device_channel))
+    }
+
+    /// Records a frame as is.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the frame was written
+    /// * `Err(FeagiDataProcessingError)` - If its timestamp is earlier than the previous frame's,
+    ///   its content cannot be serialized, or the writer failed
+    pub fn record_frame(&mut self, frame: &FeagiSessionFrame) -> Result<(), FeagiDataProcessingError> {
+        self.write_frame(frame.get_timestamp(), frame.get_kind(), |buffer| frame.write_body(buffer))
+    }
+
+    /// Returns the number of frames recorded so far.
+    pub fn get_frame_count(&self) -> usize {
+        self.index.len() / INDEX_ENTRY_SIZE_IN_BYTES
+    }
+
+    /// Writes the frame index, patches the header to point to it, and returns the writer,
+    /// positioned at the end of the recording.
+    pub fn finish(mut self) -> Result<W, FeagiDataProcessingError> {
+        let index_offset = self.written_byte_count;
+        self.writer.write_all(&self.index).map_err(io_error)?;
+        let end_position = self.writer.stream_position().map_err(io_error)?;
+
+        let mut index_location = [0u8; 16];
+        LittleEndian::write_u64(&mut index_location[0..8], index_offset);
+        LittleEndian::write_u64(&mut index_location[8..16], self.get_frame_count() as u64);
+        self.writer.seek(SeekFrom::Start(self.header_position + RECORDING_HEADER_INDEX_OFFSET_POSITION as u64)).map_err(io_error)?;
+        self.writer.write_all(&index_location).map_err(io_error)?;
+        self.writer.seek(SeekFrom::Start(end_position)).map_err(io_error)?;
+        self.writer.flush().map_err(io_error)?;
+        Ok(self.writer)
+    }
+
+    fn elapsed_timestamp(&self) -> Duration {
+        self.start_time.elapsed().max(self.last_timestamp)
+    }
+
+    /// Writes a length prefixed frame whose body is appended to the frame buffer by `write_body`.
+    fn write_frame<F>(&mut self, timestamp: Duration, kind: u8, write_body: F) -> Result<(), FeagiDataProcessingError>
+    where
+        F: FnOnce(&mut Vec<u8>) -> Result<(), FeagiDataProcessingError>,
+    {
+        if timestamp < self.last_timestamp {
+            return Err(IODataError::InvalidParameters(format!("Frame timestamp {:?} is earlier than the previous frame's timestamp {:?}!", timestamp, self.last_timestamp)).into());
+        }
+
+        self.frame_buffer.clear();
+        self.frame_buffer.resize(FRAME_LENGTH_PREFIX_SIZE_IN_BYTES, 0);
+        write_body(&mut self.frame_buffer)?;
+        let body_length = self.frame_buffer.len() - FRAME_LENGTH_PREFIX_SIZE_IN_BYTES;
+        if body_length > u32::MAX as usize {
+            return Err(FeagiBytesError::UnableToSerializeBytes(format!("Recorded frame of {} bytes is too large!", body_length)).into());
+        }
+        LittleEndian::write_u32(&mut self.frame_buffer[0..FRAME_LENGTH_PREFIX_SIZE_IN_BYTES], body_length as u32);
+        self.writer.write_all(&self.frame_buffer).map_err(io_error)?;
+
+        let mut index_entry = [0u8; INDEX_ENTRY_SIZE_IN_BYTES];
+        LittleEndian::write_u64(&mut index_entry[0..8], self.written_byte_count);
+        LittleEndian::write_u64(&mut index_entry[8..16], duration_to_micros(timestamp));
+        index_entry[16] = kind;
+        self.index.extend_from_slice(&index_entry);
+
+        self.written_byte_count += self.frame_buffer.len() as u64;
+        self.last_timestamp = timestamp;
+        Ok(())
+    }
+}
+
+fn io_error(err: std::io::Error) -> FeagiDataProcessingError {
+    FeagiBytesError::UnableToSerializeBytes(format!("Unable to write session recording: {}", err)).into()
+}
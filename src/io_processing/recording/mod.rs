@@ -0,0 +1,26 @@
+//! Recording and replay of agent sessions.
+//!
+//! `FeagiSessionRecorder` appends timestamped neuron bursts and raw sensor inputs to any
+//! seekable writer, and `FeagiSessionPlayer` reads them back at their original or an
+//! accelerated pace, optionally feeding sensor inputs back into a `SensorCache`. This allows
+//! reproducing a session offline, for debugging or regression testing of encoders.
+//!
+//! # Binary Format
+//!
+//! ```text
+//! [Magic "FEAGIREC" (8)][Version (1)][Reserved (7)][Index Offset (u64)][Frame Count (u64)]
+//! [Frame Length (u32)][Timestamp in µs (u64)][Frame Kind (u8)][Payload...]   (repeated)
+//! [Frame Offset (u64)][Timestamp in µs (u64)][Frame Kind (u8)]               (index, per frame)
+//! ```
+//!
+//! Neuron data payloads and image sensor values are stored as FEAGI byte structures. The index
+//! is written when the recording is finished; until then the index offset is 0, and players
+//! rebuild the index by scanning the frames.
+
+mod feagi_session_frame;
+mod feagi_session_recorder;
+mod feagi_session_player;
+
+pub use feagi_session_frame::{FeagiSessionFrame, FeagiSessionFrameContent};
+pub use feagi_session_recorder::FeagiSessionRecorder;
+pub use feagi_session_player::FeagiSessionPlayer;
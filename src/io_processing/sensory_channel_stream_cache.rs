@@ -4,7 +4,7 @@
 //! in FEAGI's neural processing system.
 
 use std::time::{Instant};
-use crate::error::{FeagiDataProcessingError};
+use crate::error::{FeagiDataProcessingError, IODataError};
 use crate::genomic_structures::{CorticalIOChannelIndex};
 use crate::io_data::{IOTypeData, IOTypeVariant};
 use crate::io_processing::{StreamCacheProcessor};
@@ -28,6 +28,7 @@ use crate::neuron_data::xyzp::{NeuronXYZPEncoder};
 #[derive(Debug)]
 pub(crate) struct SensoryChannelStreamCache { 
     processor_runner: ProcessorRunner,
+    carried_over_value: Option<IOTypeData>, // output of a replaced processor chain, until the next update
     channel: CorticalIOChannelIndex,
     last_updated: Instant,
     should_allow_sending_stale_data: bool,
@@ -65,6 +66,7 @@ impl SensoryChannelStreamCache {
         let processor_runner = ProcessorRunner::new(cache_processors)?;
         Ok(SensoryChannelStreamCache {
             processor_runner,
+            carried_over_value: None,
             channel,
             last_updated: Instant::now(),
             should_allow_sending_stale_data: should_allow_sending_stale_data
@@ -91,8 +93,10 @@ impl SensoryChannelStreamCache {
     /// Returns an error if any processor in the chain fails to handle the data,
     /// typically due to data type mismatches or processing-specific failures.
     pub fn update_sensor_value(&mut self, value: IOTypeData) -> Result<(), FeagiDataProcessingError> {
-        self.last_updated = Instant::now();
-        _ = self.processor_runner.update_value(&value, Instant::now())?;
+        let now = Instant::now();
+        _ = self.processor_runner.update_value(&value, now)?;
+        self.last_updated = now;
+        self.carried_over_value = None;
         Ok(())
     }
     
@@ -128,7 +132,38 @@ impl SensoryChannelStreamCache {
     ///
     /// Reference to the most recent processed sensor data
     pub fn get_most_recent_sensor_value(&self) -> &IOTypeData {
-        self.processor_runner.get_most_recent_output()
+        match &self.carried_over_value {
+            Some(value) => value,
+            None => self.processor_runner.get_most_recent_output(),
+        }
+    }
+
+    /// Replaces the processor chain, keeping the most recent sensor value.
+    ///
+    /// The new chain must accept and produce the same data types as the current one. Until the
+    /// next update, the cache keeps returning the last value processed by the old chain, so a
+    /// swap does not change what is encoded or reset the staleness of the channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache_processors` - Vector of processors to apply to incoming data in order
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The chain was replaced
+    /// * `Err(FeagiDataProcessingError)` - If the chain is invalid or its data types differ,
+    ///   in which case the current chain is kept
+    pub fn replace_processors(&mut self, cache_processors: Vec<Box<dyn StreamCacheProcessor + Sync + Send>>) -> Result<(), FeagiDataProcessingError> {
+        let processor_runner = ProcessorRunner::new(cache_processors)?;
+        if processor_runner.get_input_data_type() != self.get_input_data_type() || processor_runner.get_output_data_type() != self.get_output_data_type() {
+            return Err(IODataError::InvalidParameters(format!("Replacement processor chain takes {} and outputs {}, but the channel takes {} and outputs {}!",
+                                                              processor_runner.get_input_data_type(), processor_runner.get_output_data_type(),
+                                                              self.get_input_data_type(), self.get_output_data_type())).into());
+        }
+        let carried_over_value = self.get_most_recent_sensor_value().clone();
+        self.processor_runner = processor_runner;
+        self.carried_over_value = Some(carried_over_value);
+        Ok(())
     }
     
     /// Encodes the cached sensor data into neural representations.
@@ -194,66 +229,3 @@ impl SensoryChannelStreamCache {
         self.processor_runner.get_output_data_type()
     }
 }
-
-
-/*
-// TODO add callback for only on change
-
-pub struct MotorChannelStreamCache {
-    stream_cache_processor: Box<dyn StreamCacheProcessor>,
-    neuron_xyzp_decoder: Box<dyn NeuronXYZPDecoder>,
-    channel: CorticalIOChannelIndex,
-    last_updated: Instant,
-    callbacks_all_bursts: CallBackManager
-}
-
-impl MotorChannelStreamCache {
-    
-    pub fn new(stream_cache_processor: Box<dyn StreamCacheProcessor>, 
-               neuron_xyzp_decoder: Box<dyn NeuronXYZPDecoder>,
-               channel: CorticalIOChannelIndex) -> Result<Self, FeagiDataProcessingError> {
-
-        if stream_cache_processor.get_data_type() != neuron_xyzp_decoder.get_data_type() {
-            return Err(FeagiDataProcessingError::InternalError("Stream Cache Processor and Neuron Decoder do not have matching data types!".into()));
-        }
-        
-        Ok(MotorChannelStreamCache{
-            stream_cache_processor,
-            neuron_xyzp_decoder,
-            channel,
-            last_updated: Instant::now(),
-            callbacks_all_bursts: CallBackManager::new()
-        })
-        
-    }
-    
-    pub fn decode_from_neurons(&mut self, neuron_data: &NeuronXYZPArrays) -> Result<&IOTypeData, FeagiDataProcessingError> {
-        let decoded_value: IOTypeData = self.neuron_xyzp_decoder.read_neuron_data_single_channel(
-            neuron_data,
-            self.channel
-        )?;
-        self.last_updated = Instant::now();
-        self.stream_cache_processor.process_new_input(&decoded_value)
-    }
-    
-    pub fn is_more_recent_than_given(&self, time: Instant) -> bool {
-        time < self.last_updated
-    }
-    
-    pub fn get_most_recent_motor_value(&self) -> &IOTypeData {
-        self.stream_cache_processor.get_most_recent_output()
-    }
-
-    pub fn get_input_data_type(&self) -> IOTypeVariant {
-        self.stream_cache_processor.get_input_data_type()
-    }
-
-    pub fn get_output_data_type(&self) -> IOTypeVariant {
-        self.stream_cache_processor.get_output_data_type()
-    }
-    
-    // TODO allow registering callbacks
-}
-
- */
-
@@ -8,7 +8,11 @@
 //!
 //! - **StreamCacheProcessor trait**: Defines the interface that all processors must implement
 //! - **ProcessorRunner**: Orchestrates execution of a chain of processors, ensuring type compatibility
+//! - **ProcessorGraphRunner**: Runs a directed acyclic graph of processors, with fan-out and
+//!   combinator nodes merging several outputs
 //! - **processors module**: Contains concrete implementations of various processor types
+//! - **ProcessorDefinition**: Serializable description of a processor, so chains can be loaded from
+//!   configuration or received from FEAGI as JSON, and built with `ProcessorChainBuilder`
 //!
 //! # Usage
 //!
@@ -26,10 +30,14 @@
 
 mod stream_cache_processor_trait;
 mod processor_runner;
+mod processor_graph_runner;
+mod processor_definition;
 pub mod processors;
 mod verify_stream_cache_processor_chain;
 
 pub use stream_cache_processor_trait::StreamCacheProcessor;
-pub(crate) use processor_runner::ProcessorRunner;
+pub use processor_runner::ProcessorRunner;
+pub use processor_graph_runner::{ProcessorGraphRunner, ProcessorGraphNodeID, ProcessorGraphCombinator};
+pub use processor_definition::*;
 pub(crate) use verify_stream_cache_processor_chain::*;
 
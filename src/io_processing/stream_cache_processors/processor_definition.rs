@@ -0,0 +1,364 @@
+//! Declarative, serializable definitions of stream cache processors.
+//!
+//! Processor chains are otherwise assembled in code from boxed `StreamCacheProcessor` instances.
+//! A `ProcessorDefinition` instead describes a single processor by its parameters, so chains can
+//! be loaded from configuration files or received from FEAGI as JSON, and then built into live
+//! processors with `ProcessorChainBuilder`.
+//!
+//! # JSON Layout
+//!
+//! A chain is a JSON array of definitions, each tagged by the processor it describes:
+//! ```json
+//! [
+//!     {"type": "KalmanFilter", "process_noise": 0.01, "measurement_noise": 0.5, "initial_value": 0.0},
+//!     {"type": "LinearScaleTo0And1", "lower_bound": -10.0, "upper_bound": 10.0, "initial_value": 0.0}
+//! ]
+//! ```
+
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::error::{FeagiDataProcessingError, IODataError};
+use crate::io_data::image_descriptors::{ColorChannelLayout, ColorSpace, GazeProperties, ImageFrameProperties, SegmentedFrameTargetResolutions, SegmentedImageFrameProperties};
+use crate::io_data::{FeagiJSON, ImageFrame, ImageFrameSegmentator, ImageFrameTransformer, SegmentedImageFrame};
+use crate::io_processing::stream_cache_processors::processor_runner::ProcessorRunner;
+use crate::io_processing::stream_cache_processors::verify_stream_cache_processor_chain::verify_sensor_chain;
+use crate::io_processing::StreamCacheProcessor;
+use crate::io_processing::processors::*;
+
+//region Processor Definition
+
+/// Describes a single processor of `io_processing::processors` by its construction parameters.
+///
+/// Definitions are plain data, and are only validated when built with [`build`](Self::build),
+/// using the same checks as the constructor of the processor they describe.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", deny_unknown_fields)]
+pub enum ProcessorDefinition {
+    /// [`IdentityFloatProcessor`]
+    IdentityFloat { initial_value: f32 },
+    /// [`IdentityImageFrameProcessor`], starting from a blank image
+    IdentityImageFrame { image_properties: ImagePropertiesDefinition },
+    /// [`IdentitySegmentedImageFrameProcessor`], starting from a blank segmented image
+    IdentitySegmentedImageFrame { segmented_image_properties: SegmentedImagePropertiesDefinition },
+    /// [`LinearScaleTo0And1Processor`]
+    LinearScaleTo0And1 { lower_bound: f32, upper_bound: f32, initial_value: f32 },
+    /// [`LinearScaleToM1And1`]
+    LinearScaleToM1And1 { lower_bound: f32, upper_bound: f32, initial_value: f32 },
+    /// [`LinearScaleFromM1And1Processor`]
+    LinearScaleFromM1And1 { lower_bound: f32, upper_bound: f32, initial_value: f32 },
+    /// [`LinearAverageRollingWindowProcessor`]
+    LinearAverageRollingWindow { window_length: usize, initial_value: f32 },
+    /// [`RollingPercentileProcessor`], where a percentile of 0.5 is the median
+    RollingPercentile { window_length: WindowLengthDefinition, percentile: f32, initial_value: f32 },
+    /// [`RollingStandardDeviationProcessor`]
+    RollingStandardDeviation { window_length: WindowLengthDefinition, initial_value: f32 },
+    /// [`ExponentialMovingAverageProcessor`]
+    ExponentialMovingAverage { time_constant_seconds: f32, initial_value: f32 },
+    /// [`LowPassFilterProcessor`]
+    LowPassFilter { cutoff_frequency_hz: f32, initial_value: f32 },
+    /// [`KalmanFilterProcessor`]
+    KalmanFilter { process_noise: f32, measurement_noise: f32, initial_value: f32 },
+    /// [`OneEuroFilterProcessor`]
+    OneEuroFilter { min_cutoff_hz: f32, beta: f32, derivative_cutoff_hz: f32, initial_value: f32 },
+    /// [`ImageFrameTransformerProcessor`], where each unset step is skipped
+    ImageFrameTransformer {
+        input_image_properties: ImagePropertiesDefinition,
+        /// Lower left (inclusive) and upper right (exclusive) XY points to crop to
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cropping_from: Option<((usize, usize), (usize, usize))>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        resizing_to: Option<(usize, usize)>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        brightness_multiplier: Option<f32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        contrast_change: Option<f32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        color_space_to: Option<ColorSpace>,
+        #[serde(default)]
+        convert_to_grayscale: bool,
+    },
+    /// [`ImageFrameQuickDiffProcessor`]
+    ImageFrameQuickDiff { image_properties: ImagePropertiesDefinition, threshold: f32 },
+    /// [`ImageFrameSegmentatorProcessor`], gazing at the center of the image if no gaze is given
+    ImageFrameSegmentator {
+        input_image_properties: ImagePropertiesDefinition,
+        output_image_properties: SegmentedImagePropertiesDefinition,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        initial_gaze: Option<GazeDefinition>,
+    },
+}
+
+impl ProcessorDefinition {
+    /// Creates the processor this definition describes.
+    ///
+    /// # Returns
+    /// * `Ok(Box<dyn StreamCacheProcessor + Sync + Send>)` - The new processor
+    /// * `Err(FeagiDataProcessingError)` - If the parameters are rejected by the processor
+    ///
+    /// # Example
+    /// ```rust
+    /// use feagi_core_data_structures_and_processing::io_data::IOTypeVariant;
+    /// use feagi_core_data_structures_and_processing::io_processing::ProcessorDefinition;
+    ///
+    /// let definition = ProcessorDefinition::LinearScaleTo0And1 { lower_bound: 0.0, upper_bound: 100.0, initial_value: 50.0 };
+    /// let processor = definition.build().unwrap();
+    /// assert_eq!(processor.get_output_data_type(), IOTypeVariant::F32Normalized0To1);
+    /// ```
+    pub fn build(&self) -> Result<Box<dyn StreamCacheProcessor + Sync + Send>, FeagiDataProcessingError> {
+        Ok(match self {
+            ProcessorDefinition::IdentityFloat { initial_value } =>
+                Box::new(IdentityFloatProcessor::new(*initial_value)?),
+            ProcessorDefinition::IdentityImageFrame { image_properties } =>
+                Box::new(IdentityImageFrameProcessor::new(ImageFrame::from_image_frame_properties(&image_properties.build()?)?)?),
+            ProcessorDefinition::IdentitySegmentedImageFrame { segmented_image_properties } =>
+                Box::new(IdentitySegmentedImageFrameProcessor::new(SegmentedImageFrame::from_segmented_image_frame_properties(&segmented_image_properties.build()?)?)?),
+            ProcessorDefinition::LinearScaleTo0And1 { lower_bound, upper_bound, initial_value } =>
+                Box::new(LinearScaleTo0And1Processor::new(*lower_bound, *upper_bound, *initial_value)?),
+            ProcessorDefinition::LinearScaleToM1And1 { lower_bound, upper_bound, initial_value } =>
+                Box::new(LinearScaleToM1And1::new(*lower_bound, *upper_bound, *initial_value)?),
+            ProcessorDefinition::LinearScaleFromM1And1 { lower_bound, upper_bound, initial_value } =>
+                Box::new(LinearScaleFromM1And1Processor::new(*lower_bound, *upper_bound, *initial_value)?),
+            ProcessorDefinition::LinearAverageRollingWindow { window_length, initial_value } =>
+                Box::new(LinearAverageRollingWindowProcessor::new(*window_length, *initial_value)?),
+            ProcessorDefinition::RollingPercentile { window_length, percentile, initial_value } =>
+                Box::new(RollingPercentileProcessor::new(window_length.build()?, *percentile, *initial_value)?),
+            ProcessorDefinition::RollingStandardDeviation { window_length, initial_value } =>
+                Box::new(RollingStandardDeviationProcessor::new(window_length.build()?, *initial_value)?),
+            ProcessorDefinition::ExponentialMovingAverage { time_constant_seconds, initial_value } =>
+                Box::new(ExponentialMovingAverageProcessor::new(seconds_to_duration(*time_constant_seconds)?, *initial_value)?),
+            ProcessorDefinition::LowPassFilter { cutoff_frequency_hz, initial_value } =>
+                Box::new(LowPassFilterProcessor::new(*cutoff_frequency_hz, *initial_value)?),
+            ProcessorDefinition::KalmanFilter { process_noise, measurement_noise, initial_value } =>
+                Box::new(KalmanFilterProcessor::new(*process_noise, *measurement_noise, *initial_value)?),
+            ProcessorDefinition::OneEuroFilter { min_cutoff_hz, beta, derivative_cutoff_hz, initial_value } =>
+                Box::new(OneEuroFilterProcessor::new(*min_cutoff_hz, *beta, *derivative_cutoff_hz, *initial_value)?),
+            ProcessorDefinition::ImageFrameTransformer { input_image_properties, cropping_from, resizing_to,
+                brightness_multiplier, contrast_change, color_space_to, convert_to_grayscale } => {
+                let mut transformer = ImageFrameTransformer::new(input_image_properties.build()?);
+                if let Some((lower_left, upper_right)) = cropping_from {
+                    transformer.set_cropping_from(*lower_left, *upper_right)?;
+                }
+                if let Some(resolution) = resizing_to {
+                    transformer.set_resizing_to(*resolution)?;
+                }
+                if let Some(multiplier) = brightness_multiplier {
+                    transformer.set_brightness_multiplier(*multiplier)?;
+                }
+                if let Some(change) = contrast_change {
+                    transformer.set_contrast_change(*change)?;
+                }
+                if let Some(color_space) = color_space_to {
+                    transformer.set_color_space_to(color_space)?;
+                }
+                transformer.set_conversion_to_grayscale(*convert_to_grayscale)?;
+                Box::new(ImageFrameTransformerProcessor::new(transformer)?)
+            }
+            ProcessorDefinition::ImageFrameQuickDiff { image_properties, threshold } =>
+                Box::new(ImageFrameQuickDiffProcessor::new(image_properties.build()?, *threshold)?),
+            ProcessorDefinition::ImageFrameSegmentator { input_image_properties, output_image_properties, initial_gaze } => {
+                let input_image_properties = input_image_properties.build()?;
+                let output_image_properties = output_image_properties.build()?;
+                let initial_gaze = match initial_gaze {
+                    Some(gaze) => gaze.build()?,
+                    None => GazeProperties::create_default_centered(),
+                };
+                let segmentator = ImageFrameSegmentator::new(input_image_properties, output_image_properties, initial_gaze)?;
+                Box::new(ImageFrameSegmentatorProcessor::new(input_image_properties, output_image_properties, segmentator))
+            }
+        })
+    }
+}
+
+//endregion
+
+//region Parameter Definitions
+
+/// Serializable form of [`ImageFrameProperties`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImagePropertiesDefinition {
+    pub xy_resolution: (usize, usize),
+    pub color_space: ColorSpace,
+    pub color_channel_layout: ColorChannelLayout,
+}
+
+impl ImagePropertiesDefinition {
+    /// Validates the definition into [`ImageFrameProperties`].
+    pub fn build(&self) -> Result<ImageFrameProperties, FeagiDataProcessingError> {
+        ImageFrameProperties::new(self.xy_resolution, self.color_space, self.color_channel_layout)
+    }
+}
+
+impl From<ImageFrameProperties> for ImagePropertiesDefinition {
+    fn from(properties: ImageFrameProperties) -> Self {
+        ImagePropertiesDefinition {
+            xy_resolution: properties.get_expected_xy_resolution(),
+            color_space: properties.get_expected_color_space(),
+            color_channel_layout: properties.get_expected_color_channel_layout(),
+        }
+    }
+}
+
+/// Serializable form of [`SegmentedImageFrameProperties`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SegmentedImagePropertiesDefinition {
+    pub segment_xy_resolutions: SegmentedFrameTargetResolutions,
+    pub center_color_channel_layout: ColorChannelLayout,
+    pub peripheral_color_channel_layout: ColorChannelLayout,
+    pub color_space: ColorSpace,
+}
+
+impl SegmentedImagePropertiesDefinition {
+    /// Validates the definition into [`SegmentedImageFrameProperties`].
+    pub fn build(&self) -> Result<SegmentedImageFrameProperties, FeagiDataProcessingError> {
+        let r = &self.segment_xy_resolutions;
+        let resolutions = SegmentedFrameTargetResolutions::new(r.lower_left, r.middle_left, r.upper_left, r.upper_middle,
+                                                               r.upper_right, r.middle_right, r.lower_right, r.lower_middle, r.center)?;
+        Ok(SegmentedImageFrameProperties::new(&resolutions, &self.center_color_channel_layout, &self.peripheral_color_channel_layout, &self.color_space))
+    }
+}
+
+impl From<SegmentedImageFrameProperties> for SegmentedImagePropertiesDefinition {
+    fn from(properties: SegmentedImageFrameProperties) -> Self {
+        SegmentedImagePropertiesDefinition {
+            segment_xy_resolutions: *properties.get_expected_resolutions(),
+            center_color_channel_layout: *properties.get_center_color_channel(),
+            peripheral_color_channel_layout: *properties.get_peripheral_color_channels(),
+            color_space: *properties.get_color_space(),
+        }
+    }
+}
+
+/// Serializable form of [`GazeProperties`], in normalized cartesian coordinates where the origin
+/// is the bottom left of the image, as with [`GazeProperties::cartesian_where_origin_bottom_left`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GazeDefinition {
+    pub center_normalized_xy: (f32, f32),
+    pub size_normalized_xy: (f32, f32),
+}
+
+impl GazeDefinition {
+    /// Validates the definition into [`GazeProperties`].
+    pub fn build(&self) -> Result<GazeProperties, FeagiDataProcessingError> {
+        GazeProperties::cartesian_where_origin_bottom_left(self.center_normalized_xy, self.size_normalized_xy)
+    }
+}
+
+/// Serializable form of [`RollingWindowLength`], written as `{"Samples": 10}` or `{"Seconds": 0.5}`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WindowLengthDefinition {
+    /// Keep the given number of most recent values
+    Samples(usize),
+    /// Keep the values received within the given number of seconds
+    Seconds(f32),
+}
+
+impl WindowLengthDefinition {
+    /// Converts the definition into a [`RollingWindowLength`].
+    pub fn build(&self) -> Result<RollingWindowLength, FeagiDataProcessingError> {
+        Ok(match self {
+            WindowLengthDefinition::Samples(samples) => RollingWindowLength::Samples(*samples),
+            WindowLengthDefinition::Seconds(seconds) => RollingWindowLength::Duration(seconds_to_duration(*seconds)?),
+        })
+    }
+}
+
+fn seconds_to_duration(seconds: f32) -> Result<Duration, FeagiDataProcessingError> {
+    Duration::try_from_secs_f32(seconds)
+        .map_err(|_| IODataError::InvalidParameters(format!("{} is not a valid number of seconds!", seconds)).into())
+}
+
+//endregion
+
+//region Processor Chain Builder
+
+/// Builds verified processor chains from an ordered list of [`ProcessorDefinition`].
+///
+/// Serializes as a plain JSON array of definitions, and can be converted to and from [`FeagiJSON`]
+/// for sending between FEAGI and agents.
+///
+/// # Example
+/// ```rust
+/// use feagi_core_data_structures_and_processing::io_data::{FeagiJSON, IOTypeVariant};
+/// use feagi_core_data_structures_and_processing::io_processing::ProcessorChainBuilder;
+///
+/// let json = FeagiJSON::from_json_string(r#"[
+///     {"type": "KalmanFilter", "process_noise": 0.01, "measurement_noise": 0.5, "initial_value": 0.0},
+///     {"type": "LinearScaleTo0And1", "lower_bound": -10.0, "upper_bound": 10.0, "initial_value": 0.0}
+/// ]"#.to_string()).unwrap();
+///
+/// let builder = ProcessorChainBuilder::from_feagi_json(&json).unwrap();
+/// let runner = builder.build_runner().unwrap();
+/// assert_eq!(runner.get_input_data_type(), IOTypeVariant::F32);
+/// assert_eq!(runner.get_output_data_type(), IOTypeVariant::F32Normalized0To1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ProcessorChainBuilder {
+    definitions: Vec<ProcessorDefinition>,
+}
+
+impl ProcessorChainBuilder {
+    /// Creates a builder with no processors.
+    pub fn new() -> Self {
+        ProcessorChainBuilder { definitions: Vec::new() }
+    }
+
+    /// Creates a builder from definitions, in the order the data flows through them.
+    pub fn from_definitions(definitions: Vec<ProcessorDefinition>) -> Self {
+        ProcessorChainBuilder { definitions }
+    }
+
+    /// Appends a processor to the end of the chain.
+    pub fn push(&mut self, definition: ProcessorDefinition) -> &mut Self {
+        self.definitions.push(definition);
+        self
+    }
+
+    /// Returns the definitions of the chain, in order.
+    pub fn get_definitions(&self) -> &[ProcessorDefinition] {
+        &self.definitions
+    }
+
+    /// Builds every processor, and verifies they form a valid chain.
+    ///
+    /// The result can be passed directly as a channel's processors to cache registration
+    /// functions such as `SensorCache::register_cortical_area_with_encoder`.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Box<dyn StreamCacheProcessor + Sync + Send>>)` - The processors, in order
+    /// * `Err(FeagiDataProcessingError)` - If the chain is empty, a definition is invalid, or
+    ///   the output type of a processor does not match the input type of the next
+    pub fn build_processors(&self) -> Result<Vec<Box<dyn StreamCacheProcessor + Sync + Send>>, FeagiDataProcessingError> {
+        let processors = self.definitions.iter()
+            .map(ProcessorDefinition::build)
+            .collect::<Result<Vec<_>, _>>()?;
+        verify_sensor_chain(&processors)?;
+        Ok(processors)
+    }
+
+    /// Builds every processor into a verified [`ProcessorRunner`].
+    pub fn build_runner(&self) -> Result<ProcessorRunner, FeagiDataProcessingError> {
+        ProcessorRunner::new(self.build_processors()?)
+    }
+
+    /// Serializes the chain as a JSON array of definitions.
+    pub fn to_feagi_json(&self) -> Result<FeagiJSON, FeagiDataProcessingError> {
+        // Written through a string so f32 parameters keep their shortest form (0.01, not 0.009999999776482582)
+        let json = serde_json::to_string(self)
+            .map_err(|err| IODataError::InvalidParameters(format!("Unable to serialize processor chain to JSON: {}", err)))?;
+        FeagiJSON::from_json_string(json)
+    }
+
+    /// Reads a chain from a JSON array of definitions, as written by [`to_feagi_json`](Self::to_feagi_json).
+    ///
+    /// The definitions are not validated until the chain is built.
+    pub fn from_feagi_json(json: &FeagiJSON) -> Result<Self, FeagiDataProcessingError> {
+        serde_json::from_value(json.borrow_json_value().clone())
+            .map_err(|err| IODataError::InvalidParameters(format!("Unable to read processor chain from JSON: {}", err)).into())
+    }
+}
+
+//endregion
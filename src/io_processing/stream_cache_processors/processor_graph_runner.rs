@@ -0,0 +1,363 @@
+//! Processor graph runner for branching and merging stream cache processors.
+//!
+//! Where `ProcessorRunner` runs a strictly linear chain, `ProcessorGraphRunner` runs a directed
+//! acyclic graph of processors. A node may feed any number of others (fan-out), such as one
+//! camera frame feeding both a quick diff and a resize path, and combinator nodes merge the
+//! outputs of several nodes (fan-in), such as summing or mixing float channels.
+//!
+//! Nodes can only be connected to nodes added before them, so the graph cannot contain cycles
+//! and the order nodes were added in is always a valid processing order.
+//!
+//! ```text
+//! Input(ImageFrame) --+--> ImageFrameQuickDiffProcessor
+//!                     |
+//!                     +--> ImageFrameTransformerProcessor
+//!
+//! Input(F32) --> KalmanFilterProcessor --+
+//!                                        +--> WeightedMix([0.7, 0.3])
+//! Input(F32) --> LowPassFilterProcessor -+
+//! ```
+
+use std::time::Instant;
+use crate::error::{FeagiDataProcessingError, IODataError};
+use crate::io_data::{IOTypeData, IOTypeVariant};
+use crate::io_processing::stream_cache_processors::verify_stream_cache_processor_chain::verify_sensor_chain;
+use crate::io_processing::StreamCacheProcessor;
+
+/// Identifies a node of the [`ProcessorGraphRunner`] it was returned by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProcessorGraphNodeID(usize);
+
+/// How a combinator node of a [`ProcessorGraphRunner`] merges the outputs of its source nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessorGraphCombinator {
+    /// Adds float sources of any float type, outputting `F32`
+    Sum,
+    /// Weighted average of float sources of the same type, with one non-negative weight per
+    /// source. Weights are normalized to sum to 1, so the output keeps the type of the sources
+    WeightedMix(Vec<f32>),
+    /// Passes through the source at the given index. All sources must be of the same type
+    Select(usize),
+}
+
+#[derive(Debug)]
+enum ProcessorGraphNode {
+    Input {
+        value: IOTypeData,
+    },
+    Processor {
+        source: ProcessorGraphNodeID,
+        processor: Box<dyn StreamCacheProcessor + Sync + Send>,
+    },
+    Combinator {
+        sources: Vec<ProcessorGraphNodeID>,
+        combinator: ProcessorGraphCombinator,
+        output: IOTypeData,
+    },
+}
+
+impl ProcessorGraphNode {
+    fn get_output(&self) -> &IOTypeData {
+        match self {
+            ProcessorGraphNode::Input { value } => value,
+            ProcessorGraphNode::Processor { processor, .. } => processor.get_most_recent_output(),
+            ProcessorGraphNode::Combinator { output, .. } => output,
+        }
+    }
+
+    fn get_output_data_type(&self) -> IOTypeVariant {
+        match self {
+            ProcessorGraphNode::Processor { processor, .. } => processor.get_output_data_type(),
+            _ => IOTypeVariant::from(self.get_output()),
+        }
+    }
+
+    fn is_fed_by(&self, is_updated: &[bool]) -> bool {
+        match self {
+            ProcessorGraphNode::Input { .. } => false,
+            ProcessorGraphNode::Processor { source, .. } => is_updated[source.0],
+            ProcessorGraphNode::Combinator { sources, .. } => sources.iter().any(|source| is_updated[source.0]),
+        }
+    }
+}
+
+/// Runs a directed acyclic graph of stream cache processors, with fan-out and combinator nodes.
+///
+/// Data enters through input nodes. Each update only runs the nodes downstream of the updated
+/// inputs, so processors on other branches do not see repeated samples. The output of every
+/// node can be read at any time.
+///
+/// # Example
+/// ```rust
+/// use std::time::Instant;
+/// use feagi_core_data_structures_and_processing::io_data::IOTypeData;
+/// use feagi_core_data_structures_and_processing::io_processing::{ProcessorGraphCombinator, ProcessorGraphRunner};
+/// use feagi_core_data_structures_and_processing::io_processing::processors::LinearScaleTo0And1Processor;
+///
+/// let mut graph = ProcessorGraphRunner::new();
+/// let left = graph.add_input(IOTypeData::new_f32(0.0).unwrap());
+/// let right = graph.add_input(IOTypeData::new_f32(0.0).unwrap());
+/// let left_scaled = graph.add_processor(left, Box::new(LinearScaleTo0And1Processor::new(0.0, 10.0, 0.0).unwrap())).unwrap();
+/// let right_scaled = graph.add_processor(right, Box::new(LinearScaleTo0And1Processor::new(0.0, 100.0, 0.0).unwrap())).unwrap();
+/// let mix = graph.add_combinator(&[left_scaled, right_scaled], ProcessorGraphCombinator::WeightedMix(vec![3.0, 1.0])).unwrap();
+///
+/// let now = Instant::now();
+/// graph.update_values(&[(left, &IOTypeData::new_f32(10.0).unwrap()), (right, &IOTypeData::new_f32(50.0).unwrap())], now).unwrap();
+/// assert_eq!(f32::try_from(graph.get_output(mix).unwrap()).unwrap(), 0.875);
+/// ```
+#[derive(Debug, Default)]
+pub struct ProcessorGraphRunner {
+    nodes: Vec<ProcessorGraphNode>, // Sources always come before the nodes they feed
+}
+
+impl ProcessorGraphRunner {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        ProcessorGraphRunner { nodes: Vec::new() }
+    }
+
+    /// Adds an input node, through which data enters the graph.
+    ///
+    /// # Arguments
+    /// * `initial_value` - The value of the input before any update. Defines the data type the input accepts
+    ///
+    /// # Returns
+    /// The ID of the new input node
+    pub fn add_input(&mut self, initial_value: IOTypeData) -> ProcessorGraphNodeID {
+        self.push_node(ProcessorGraphNode::Input { value: initial_value })
+    }
+
+    /// Adds a processor fed by the output of an existing node.
+    ///
+    /// # Arguments
+    /// * `source` - The node whose output is processed
+    /// * `processor` - The processor, which must accept the output type of `source`
+    ///
+    /// # Returns
+    /// * `Ok(ProcessorGraphNodeID)` - The ID of the new processor node
+    /// * `Err(FeagiDataProcessingError)` - If the source does not exist, or its output type does
+    ///   not match the input type of the processor
+    pub fn add_processor(&mut self, source: ProcessorGraphNodeID, processor: Box<dyn StreamCacheProcessor + Sync + Send>) -> Result<ProcessorGraphNodeID, FeagiDataProcessingError> {
+        let source_type = self.get_output_data_type(source)?;
+        if source_type != processor.get_input_data_type() {
+            return Err(IODataError::InvalidParameters(format!("Graph node {} has output type {}, which does not match the input type {} of the given processor!",
+                                                              source.0, source_type, processor.get_input_data_type())).into());
+        }
+        Ok(self.push_node(ProcessorGraphNode::Processor { source, processor }))
+    }
+
+    /// Adds a linear chain of processors fed by the output of an existing node, as one node per processor.
+    ///
+    /// # Returns
+    /// * `Ok(ProcessorGraphNodeID)` - The ID of the node of the last processor of the chain
+    /// * `Err(FeagiDataProcessingError)` - If the chain is empty or invalid, or does not accept the
+    ///   output type of `source`. No nodes are added in that case
+    pub fn add_processor_chain(&mut self, source: ProcessorGraphNodeID, cache_processors: Vec<Box<dyn StreamCacheProcessor + Sync + Send>>) -> Result<ProcessorGraphNodeID, FeagiDataProcessingError> {
+        verify_sensor_chain(&cache_processors)?;
+        let source_type = self.get_output_data_type(source)?;
+        if source_type != cache_processors[0].get_input_data_type() {
+            return Err(IODataError::InvalidParameters(format!("Graph node {} has output type {}, which does not match the input type {} of the given processor chain!",
+                                                              source.0, source_type, cache_processors[0].get_input_data_type())).into());
+        }
+        let mut last = source;
+        for processor in cache_processors {
+            last = self.push_node(ProcessorGraphNode::Processor { source: last, processor });
+        }
+        Ok(last)
+    }
+
+    /// Adds a combinator node merging the outputs of several existing nodes.
+    ///
+    /// The output of the combinator is computed from the current outputs of its sources right away.
+    ///
+    /// # Arguments
+    /// * `sources` - The nodes to merge, in order (must not be empty)
+    /// * `combinator` - How the sources are merged, see [`ProcessorGraphCombinator`] for the type rules
+    ///
+    /// # Returns
+    /// * `Ok(ProcessorGraphNodeID)` - The ID of the new combinator node
+    /// * `Err(FeagiDataProcessingError)` - If a source does not exist, the source types are not
+    ///   accepted by the combinator, the weights are invalid, or the selected index is out of range
+    pub fn add_combinator(&mut self, sources: &[ProcessorGraphNodeID], combinator: ProcessorGraphCombinator) -> Result<ProcessorGraphNodeID, FeagiDataProcessingError> {
+        if sources.is_empty() {
+            return Err(IODataError::InvalidParameters("A combinator node needs at least 1 source!".into()).into());
+        }
+        let source_types = sources.iter()
+            .map(|source| self.get_output_data_type(*source))
+            .collect::<Result<Vec<IOTypeVariant>, FeagiDataProcessingError>>()?;
+
+        let (combinator, output) = match combinator {
+            ProcessorGraphCombinator::Sum => {
+                if let Some(source_type) = source_types.iter().find(|source_type| !is_float_type(source_type)) {
+                    return Err(IODataError::InvalidParameters(format!("Sum combinators only accept float sources, not {}!", source_type)).into());
+                }
+                (ProcessorGraphCombinator::Sum, IOTypeData::F32(0.0))
+            }
+            ProcessorGraphCombinator::WeightedMix(weights) => {
+                if !is_float_type(&source_types[0]) || source_types.iter().any(|source_type| source_type != &source_types[0]) {
+                    return Err(IODataError::InvalidParameters("Weighted mix combinators only accept float sources of a single type!".into()).into());
+                }
+                if weights.len() != sources.len() {
+                    return Err(IODataError::InvalidParameters(format!("Weighted mix combinator has {} weights for {} sources!", weights.len(), sources.len())).into());
+                }
+                let total: f32 = weights.iter().sum();
+                if weights.iter().any(|weight| !weight.is_finite() || *weight < 0.0) || !total.is_normal() {
+                    return Err(IODataError::InvalidParameters("Weighted mix weights must be finite, non-negative, and not all 0!".into()).into());
+                }
+                let output = match source_types[0] {
+                    IOTypeVariant::F32Normalized0To1 => IOTypeData::F32Normalized0To1(0.0),
+                    IOTypeVariant::F32NormalizedM1To1 => IOTypeData::F32NormalizedM1To1(0.0),
+                    _ => IOTypeData::F32(0.0),
+                };
+                (ProcessorGraphCombinator::WeightedMix(weights.iter().map(|weight| weight / total).collect()), output)
+            }
+            ProcessorGraphCombinator::Select(index) => {
+                if source_types.iter().any(|source_type| source_type != &source_types[0]) {
+                    return Err(IODataError::InvalidParameters("Select combinators only accept sources of a single type!".into()).into());
+                }
+                if index >= sources.len() {
+                    return Err(IODataError::InvalidParameters(format!("Cannot select source {} of {} sources!", index, sources.len())).into());
+                }
+                (ProcessorGraphCombinator::Select(index), self.nodes[sources[index].0].get_output().clone())
+            }
+        };
+
+        let mut node = ProcessorGraphNode::Combinator { sources: sources.to_vec(), combinator, output };
+        let index = self.nodes.len();
+        Self::process_node(&self.nodes, &mut node, Instant::now())?;
+        self.nodes.push(node);
+        Ok(ProcessorGraphNodeID(index))
+    }
+
+    /// Changes which source a select combinator passes through, from the next update that reaches it.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The selection was changed
+    /// * `Err(FeagiDataProcessingError)` - If the node is not a select combinator, or the index is out of range
+    pub fn set_select_index(&mut self, node: ProcessorGraphNodeID, index: usize) -> Result<(), FeagiDataProcessingError> {
+        match self.nodes.get_mut(node.0) {
+            Some(ProcessorGraphNode::Combinator { sources, combinator: ProcessorGraphCombinator::Select(selected), .. }) => {
+                if index >= sources.len() {
+                    return Err(IODataError::InvalidParameters(format!("Cannot select source {} of {} sources!", index, sources.len())).into());
+                }
+                *selected = index;
+                Ok(())
+            }
+            _ => Err(IODataError::InvalidParameters(format!("Graph node {} is not a select combinator!", node.0)).into()),
+        }
+    }
+
+    /// Updates a single input node and runs every node downstream of it.
+    ///
+    /// See [`update_values`](Self::update_values).
+    pub fn update_value(&mut self, input: ProcessorGraphNodeID, new_value: &IOTypeData, time_of_update: Instant) -> Result<(), FeagiDataProcessingError> {
+        self.update_values(&[(input, new_value)], time_of_update)
+    }
+
+    /// Updates several input nodes at once, and runs every node downstream of any of them once.
+    ///
+    /// # Arguments
+    /// * `new_values` - Pairs of input node and the value it now holds
+    /// * `time_of_update` - Timestamp for when this update occurred
+    ///
+    /// # Returns
+    /// * `Ok(())` - Every affected node was run
+    /// * `Err(FeagiDataProcessingError)` - If a node is not an input, or a value does not match
+    ///   the type of its input, in which case nothing is updated. Also if any processor fails
+    pub fn update_values(&mut self, new_values: &[(ProcessorGraphNodeID, &IOTypeData)], time_of_update: Instant) -> Result<(), FeagiDataProcessingError> {
+        for (input, new_value) in new_values {
+            match self.nodes.get(input.0) {
+                Some(ProcessorGraphNode::Input { value }) => {
+                    if IOTypeVariant::from(*new_value) != IOTypeVariant::from(value) {
+                        return Err(IODataError::InvalidParameters(format!("Expected Input data type of {} but received {}!", IOTypeVariant::from(value), new_value)).into());
+                    }
+                }
+                _ => return Err(IODataError::InvalidParameters(format!("Graph node {} is not an input!", input.0)).into()),
+            }
+        }
+
+        let mut is_updated: Vec<bool> = vec![false; self.nodes.len()];
+        for (input, new_value) in new_values {
+            if let ProcessorGraphNode::Input { value } = &mut self.nodes[input.0] {
+                value.clone_from(new_value);
+            }
+            is_updated[input.0] = true;
+        }
+
+        for index in 0..self.nodes.len() {
+            if !self.nodes[index].is_fed_by(&is_updated) {
+                continue;
+            }
+            // Split so the node can read the outputs of its sources, which always come before it
+            let (sources, rest) = self.nodes.split_at_mut(index);
+            Self::process_node(sources, &mut rest[0], time_of_update)?;
+            is_updated[index] = true;
+        }
+        Ok(())
+    }
+
+    /// Returns the most recent output of a node.
+    pub fn get_output(&self, node: ProcessorGraphNodeID) -> Result<&IOTypeData, FeagiDataProcessingError> {
+        Ok(self.get_node(node)?.get_output())
+    }
+
+    /// Returns the data type a node outputs.
+    pub fn get_output_data_type(&self, node: ProcessorGraphNodeID) -> Result<IOTypeVariant, FeagiDataProcessingError> {
+        Ok(self.get_node(node)?.get_output_data_type())
+    }
+
+    /// Returns the number of nodes in the graph, including inputs.
+    pub fn get_node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn get_node(&self, node: ProcessorGraphNodeID) -> Result<&ProcessorGraphNode, FeagiDataProcessingError> {
+        self.nodes.get(node.0)
+            .ok_or_else(|| IODataError::InvalidParameters(format!("Graph node {} does not exist!", node.0)).into())
+    }
+
+    fn push_node(&mut self, node: ProcessorGraphNode) -> ProcessorGraphNodeID {
+        self.nodes.push(node);
+        ProcessorGraphNodeID(self.nodes.len() - 1)
+    }
+
+    fn process_node(sources: &[ProcessorGraphNode], node: &mut ProcessorGraphNode, time_of_update: Instant) -> Result<(), FeagiDataProcessingError> {
+        match node {
+            ProcessorGraphNode::Input { .. } => {}
+            ProcessorGraphNode::Processor { source, processor } => {
+                _ = processor.process_new_input(sources[source.0].get_output(), time_of_update)?;
+            }
+            ProcessorGraphNode::Combinator { sources: source_ids, combinator, output } => {
+                match combinator {
+                    ProcessorGraphCombinator::Sum => {
+                        let mut sum: f32 = 0.0;
+                        for source in source_ids.iter() {
+                            sum += f32::try_from(sources[source.0].get_output())?;
+                        }
+                        *output = IOTypeData::F32(sum);
+                    }
+                    ProcessorGraphCombinator::WeightedMix(weights) => {
+                        let mut mixed: f32 = 0.0;
+                        for (source, weight) in source_ids.iter().zip(weights.iter()) {
+                            mixed += weight * f32::try_from(sources[source.0].get_output())?;
+                        }
+                        // Clamped, as rounding can nudge a mix of normalized values just outside its range
+                        match output {
+                            IOTypeData::F32Normalized0To1(value) => *value = mixed.clamp(0.0, 1.0),
+                            IOTypeData::F32NormalizedM1To1(value) => *value = mixed.clamp(-1.0, 1.0),
+                            _ => *output = IOTypeData::F32(mixed),
+                        }
+                    }
+                    ProcessorGraphCombinator::Select(index) => {
+                        output.clone_from(sources[source_ids[*index].0].get_output());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_float_type(io_type: &IOTypeVariant) -> bool {
+    matches!(io_type, IOTypeVariant::F32 | IOTypeVariant::F32Normalized0To1 | IOTypeVariant::F32NormalizedM1To1)
+}
@@ -23,7 +23,7 @@ use crate::io_processing::StreamCacheProcessor;
 /// - **Error Handling**: Provides clear error messages for incompatible processors
 /// - **Performance**: Uses efficient borrowing patterns to avoid unnecessary clones
 #[derive(Debug)]
-pub(crate) struct ProcessorRunner {
+pub struct ProcessorRunner {
     input_type: IOTypeVariant,
     output_type: IOTypeVariant,
     cache_processors: Vec<Box<dyn StreamCacheProcessor + Sync + Send>>,
@@ -3,6 +3,7 @@
 
 mod identities;
 mod rolling_windows;
+mod temporal_filters;
 mod ranges;
 mod image_transformer;
 mod image_segmentor;
@@ -10,6 +11,7 @@ mod image_quick_diff;
 
 pub use identities::*;
 pub use rolling_windows::*;
+pub use temporal_filters::*;
 pub use ranges::*;
 pub use image_transformer::*;
 pub use image_quick_diff::*;
@@ -183,4 +183,91 @@ impl LinearScaleToM1And1 {
             upper_minus_lower_halved: (upper_bound - lower_bound) * 0.5,
         })
     }
-}
\ No newline at end of file
+}
+
+/// A stream processor that linearly scales normalized [-1, 1] values back out to a given range.
+///
+/// This is the inverse of [`LinearScaleToM1And1`], and is typically used on the motor side
+/// to map decoded bidirectional neuron activity to a range the actuator understands.
+/// Values outside [-1, 1] are clamped before scaling.
+///
+/// # Example
+/// ```
+/// // Scale values from range [-1, 1] to [-90, 90]
+/// use feagi_core_data_structures_and_processing::io_processing::processors::LinearScaleFromM1And1Processor;
+/// let mut processor = LinearScaleFromM1And1Processor::new(-90.0, 90.0, 0.0).unwrap();
+/// // Input 0.5 would map to 45.0 in the output range
+/// ```
+#[derive(Debug, Clone)]
+pub struct LinearScaleFromM1And1Processor {
+    previous_value: IOTypeData,
+    lower: f32,
+    upper: f32,
+    upper_minus_lower_halved: f32
+}
+
+impl Display for LinearScaleFromM1And1Processor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LinearScaleFromM1And1Processor(lower_bound={:?},upper_bound={:?},prev_val={:?})", self.lower, self.upper,  self.previous_value)
+    }
+}
+
+impl StreamCacheProcessor for LinearScaleFromM1And1Processor {
+    fn get_input_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32NormalizedM1To1
+    }
+
+    fn get_output_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32
+    }
+
+    fn get_most_recent_output(&self) -> &IOTypeData {
+        &self.previous_value
+    }
+
+    fn process_new_input(&mut self, value: &IOTypeData, _: Instant) -> Result<&IOTypeData, FeagiDataProcessingError> {
+        let float_result = f32::try_from(value)?;
+        let clamped = float_result.clamp(-1.0, 1.0);
+        let scaled = ((clamped + 1.0) * self.upper_minus_lower_halved) + self.lower;
+
+        self.previous_value = IOTypeData::F32(scaled);
+        Ok(&self.previous_value)
+    }
+}
+
+impl LinearScaleFromM1And1Processor {
+    /// Creates a new LinearScaleFromM1And1Processor.
+    ///
+    /// # Arguments
+    /// * `lower_bound` - The value that -1 maps to
+    /// * `upper_bound` - The value that 1 maps to (must be > lower_bound)
+    /// * `initial_value` - The initial output value to store in the processor (must be within bounds)
+    ///
+    /// # Returns
+    /// * `Ok(LinearScaleFromM1And1Processor)` - A new processor instance
+    /// * `Err(FeagiDataProcessingError)` - If parameters are invalid (NaN, infinite, or out of bounds)
+    pub fn new(lower_bound: f32, upper_bound: f32, initial_value: f32) -> Result<Self, FeagiDataProcessingError> {
+        if lower_bound.is_nan() || lower_bound.is_infinite() {
+            return Err(IODataError::InvalidParameters(format!("Given lower bound float {} is not valid!", lower_bound)).into());
+        }
+        if upper_bound.is_nan() || upper_bound.is_infinite() {
+            return Err(IODataError::InvalidParameters(format!("Given upper bound float {} is not valid!", upper_bound)).into());
+        }
+        if initial_value.is_nan() || initial_value.is_infinite() {
+            return Err(IODataError::InvalidParameters(format!("Given initial value float {} is not valid!", initial_value)).into());
+        }
+        if upper_bound < lower_bound {
+            return Err(IODataError::InvalidParameters(format!("Upper bound float {} must be greater than lower bound {}!!", upper_bound, lower_bound)).into());
+        }
+        if initial_value > upper_bound || initial_value < lower_bound {
+            return Err(IODataError::InvalidParameters(format!("Initial value float {} must be between bounds {} and {}!", initial_value, lower_bound, upper_bound)).into());
+        }
+
+        Ok(LinearScaleFromM1And1Processor {
+            previous_value: IOTypeData::F32(initial_value),
+            lower: lower_bound,
+            upper: upper_bound,
+            upper_minus_lower_halved: (upper_bound - lower_bound) * 0.5,
+        })
+    }
+}
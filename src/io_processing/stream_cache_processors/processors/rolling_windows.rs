@@ -3,9 +3,14 @@
 //! This module provides processors that maintain a sliding window of recent values
 //! and compute statistics (like averages) over that window. These processors are
 //! useful for smoothing noisy sensor data or computing temporal aggregations.
+//!
+//! Apart from the linear average, windows can be sized by sample count or by duration (see
+//! [`RollingWindowLength`]), the latter using the `time_of_input` of each value.
 
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
 use std::fmt::{Display, Formatter};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use crate::error::{FeagiDataProcessingError, IODataError};
 use crate::io_data::{IOTypeData, IOTypeVariant};
 use crate::io_processing::StreamCacheProcessor;
@@ -87,4 +92,387 @@ impl LinearAverageRollingWindowProcessor {
             window_length: window_length as f32
         })
     }
-}
\ No newline at end of file
+}
+
+/// How many of the most recent values a rolling window processor keeps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RollingWindowLength {
+    /// Keep the given number of most recent values.
+    Samples(usize),
+    /// Keep the values whose `time_of_input` is within the given duration of the newest value.
+    /// The newest value is always kept.
+    Duration(Duration),
+}
+
+impl Display for RollingWindowLength {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RollingWindowLength::Samples(count) => write!(f, "{} samples", count),
+            RollingWindowLength::Duration(duration) => write!(f, "{:?}", duration),
+        }
+    }
+}
+
+impl RollingWindowLength {
+    fn validate(&self) -> Result<(), FeagiDataProcessingError> {
+        match self {
+            RollingWindowLength::Samples(0) => Err(IODataError::InvalidParameters("Window length cannot be 0!".into()).into()),
+            RollingWindowLength::Duration(duration) if duration.is_zero() => Err(IODataError::InvalidParameters("Window duration cannot be 0!".into()).into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A stream processor outputting a percentile of a rolling window of float values.
+///
+/// Unlike an average, a percentile is not thrown off by occasional spikes, such as those of a
+/// flaky ultrasonic proximity sensor. Percentiles are linearly interpolated between the two
+/// nearest values, as with NumPy's default, so the median of an even count of values is the
+/// mean of the middle two. Percentile 0 is the minimum and percentile 1 the maximum.
+///
+/// Values are kept sorted, so each input is processed in O(log n) for a window of n values.
+/// Until the first input arrives, the initial value is output. The window starts empty, so
+/// early outputs only consider the values received so far. Non-finite inputs are rejected.
+///
+/// # Example
+/// ```
+/// use std::time::Instant;
+/// use feagi_core_data_structures_and_processing::io_data::IOTypeData;
+/// use feagi_core_data_structures_and_processing::io_processing::StreamCacheProcessor;
+/// use feagi_core_data_structures_and_processing::io_processing::processors::{RollingPercentileProcessor, RollingWindowLength};
+///
+/// let mut processor = RollingPercentileProcessor::new_median(RollingWindowLength::Samples(5), 0.0).unwrap();
+/// let mut output = 0.0;
+/// for distance in [50.0, 51.0, 400.0, 49.0, 50.5] { // A single spike to 400
+///     output = f32::try_from(processor.process_new_input(&IOTypeData::F32(distance), Instant::now()).unwrap()).unwrap();
+/// }
+/// assert_eq!(output, 50.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingPercentileProcessor {
+    previous_value: IOTypeData,
+    window: RollingWindow,
+    percentile: f32,
+    /// The smallest values of the window, up to and including the one at the percentile rank
+    lower: SortedFloatMultiset,
+    /// The remaining, larger values of the window
+    upper: SortedFloatMultiset,
+}
+
+impl Display for RollingPercentileProcessor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RollingPercentileProcessor(length={},percentile={:?},prev_val={:?})", self.window.length, self.percentile, self.previous_value)
+    }
+}
+
+impl StreamCacheProcessor for RollingPercentileProcessor {
+    fn get_input_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32
+    }
+
+    fn get_output_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32
+    }
+
+    fn get_most_recent_output(&self) -> &IOTypeData {
+        &self.previous_value
+    }
+
+    fn process_new_input(&mut self, value: &IOTypeData, time_of_input: Instant) -> Result<&IOTypeData, FeagiDataProcessingError> {
+        let float_result = finite_window_input(value)?;
+        if self.lower.last().is_some_and(|lower_max| float_result <= lower_max) {
+            self.lower.insert(float_result);
+        } else {
+            self.upper.insert(float_result);
+        }
+        for evicted in self.window.push(float_result, time_of_input) {
+            if self.lower.last().is_some_and(|lower_max| evicted <= lower_max) && self.lower.remove(evicted) {
+                continue;
+            }
+            self.upper.remove(evicted);
+        }
+        self.rebalance();
+
+        let count = self.window.values.len();
+        let rank = self.percentile * (count - 1) as f32;
+        let lower_value = self.lower.last().unwrap(); // The window always holds at least the newest value
+        let fraction = rank - rank.floor();
+        let output = match self.upper.first() {
+            Some(upper_value) if fraction > 0.0 => lower_value + fraction * (upper_value - lower_value),
+            _ => lower_value,
+        };
+        self.previous_value = IOTypeData::F32(output);
+        Ok(&self.previous_value)
+    }
+}
+
+impl RollingPercentileProcessor {
+    /// Creates a new RollingPercentileProcessor.
+    ///
+    /// # Arguments
+    /// * `window_length` - How many values to keep, by count (must be > 0) or by duration (must not be 0)
+    /// * `percentile` - The percentile to output, from 0 (minimum) to 1 (maximum)
+    /// * `initial_value` - The value to output before any input arrives (must be finite)
+    ///
+    /// # Returns
+    /// * `Ok(RollingPercentileProcessor)` - A new processor instance
+    /// * `Err(FeagiDataProcessingError)` - If the window is empty, the percentile is outside [0, 1], or initial_value is invalid (NaN/infinite)
+    pub fn new(window_length: RollingWindowLength, percentile: f32, initial_value: f32) -> Result<Self, FeagiDataProcessingError> {
+        if !initial_value.is_finite() {
+            return Err(IODataError::InvalidParameters(format!("Given float {} is not valid!", initial_value)).into());
+        }
+        if !(0.0..=1.0).contains(&percentile) {
+            return Err(IODataError::InvalidParameters(format!("Percentile {} must be between 0 and 1!", percentile)).into());
+        }
+        window_length.validate()?;
+        Ok(RollingPercentileProcessor {
+            previous_value: IOTypeData::F32(initial_value),
+            window: RollingWindow::new(window_length),
+            percentile,
+            lower: SortedFloatMultiset::default(),
+            upper: SortedFloatMultiset::default(),
+        })
+    }
+
+    /// Creates a new RollingPercentileProcessor outputting the median of the window.
+    pub fn new_median(window_length: RollingWindowLength, initial_value: f32) -> Result<Self, FeagiDataProcessingError> {
+        Self::new(window_length, 0.5, initial_value)
+    }
+
+    /// Creates a new RollingPercentileProcessor outputting the smallest value of the window.
+    pub fn new_minimum(window_length: RollingWindowLength, initial_value: f32) -> Result<Self, FeagiDataProcessingError> {
+        Self::new(window_length, 0.0, initial_value)
+    }
+
+    /// Creates a new RollingPercentileProcessor outputting the largest value of the window.
+    pub fn new_maximum(window_length: RollingWindowLength, initial_value: f32) -> Result<Self, FeagiDataProcessingError> {
+        Self::new(window_length, 1.0, initial_value)
+    }
+
+    /// Returns the percentile this processor outputs, from 0 (minimum) to 1 (maximum).
+    pub fn get_percentile(&self) -> f32 {
+        self.percentile
+    }
+
+    /// Moves values between the lower and upper halves until the lower half ends at the percentile rank.
+    fn rebalance(&mut self) {
+        let count = self.window.values.len();
+        let target_lower_count = if count == 0 { 0 } else { (self.percentile * (count - 1) as f32).floor() as usize + 1 };
+        while self.lower.len > target_lower_count {
+            let moved = self.lower.pop_last().unwrap();
+            self.upper.insert(moved);
+        }
+        while self.lower.len < target_lower_count {
+            let moved = self.upper.pop_first().unwrap();
+            self.lower.insert(moved);
+        }
+    }
+}
+
+/// A stream processor outputting the standard deviation of a rolling window of float values.
+///
+/// Useful for detecting how noisy or unsteady a sensor currently is. This is the population
+/// standard deviation, so a window of a single value outputs 0. Each input is processed in O(1)
+/// amortized time. Until the first input arrives, the initial value is output. Non-finite
+/// inputs are rejected.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use feagi_core_data_structures_and_processing::io_processing::processors::{RollingStandardDeviationProcessor, RollingWindowLength};
+/// // Standard deviation of the last half second of values
+/// let processor = RollingStandardDeviationProcessor::new(RollingWindowLength::Duration(Duration::from_millis(500)), 0.0).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingStandardDeviationProcessor {
+    previous_value: IOTypeData,
+    window: RollingWindow,
+    sum: f64,
+    sum_of_squares: f64,
+    evictions_since_resum: usize,
+}
+
+impl Display for RollingStandardDeviationProcessor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RollingStandardDeviationProcessor(length={},prev_val={:?})", self.window.length, self.previous_value)
+    }
+}
+
+impl StreamCacheProcessor for RollingStandardDeviationProcessor {
+    fn get_input_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32
+    }
+
+    fn get_output_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32
+    }
+
+    fn get_most_recent_output(&self) -> &IOTypeData {
+        &self.previous_value
+    }
+
+    fn process_new_input(&mut self, value: &IOTypeData, time_of_input: Instant) -> Result<&IOTypeData, FeagiDataProcessingError> {
+        let float_result = finite_window_input(value)? as f64;
+        self.sum += float_result;
+        self.sum_of_squares += float_result * float_result;
+        for evicted in self.window.push(float_result as f32, time_of_input) {
+            self.sum -= evicted as f64;
+            self.sum_of_squares -= evicted as f64 * evicted as f64;
+            self.evictions_since_resum += 1;
+        }
+        // Adding and removing values slowly accumulates rounding errors, so periodically start over
+        if self.evictions_since_resum >= self.window.values.len() {
+            self.sum = self.window.values.iter().map(|(value, _)| *value as f64).sum();
+            self.sum_of_squares = self.window.values.iter().map(|(value, _)| *value as f64 * *value as f64).sum();
+            self.evictions_since_resum = 0;
+        }
+
+        let count = self.window.values.len() as f64;
+        let mean = self.sum / count;
+        let variance = (self.sum_of_squares / count - mean * mean).max(0.0);
+        self.previous_value = IOTypeData::F32(variance.sqrt() as f32);
+        Ok(&self.previous_value)
+    }
+}
+
+impl RollingStandardDeviationProcessor {
+    /// Creates a new RollingStandardDeviationProcessor.
+    ///
+    /// # Arguments
+    /// * `window_length` - How many values to keep, by count (must be > 0) or by duration (must not be 0)
+    /// * `initial_value` - The value to output before any input arrives (must be finite)
+    ///
+    /// # Returns
+    /// * `Ok(RollingStandardDeviationProcessor)` - A new processor instance
+    /// * `Err(FeagiDataProcessingError)` - If the window is empty or initial_value is invalid (NaN/infinite)
+    pub fn new(window_length: RollingWindowLength, initial_value: f32) -> Result<Self, FeagiDataProcessingError> {
+        if !initial_value.is_finite() {
+            return Err(IODataError::InvalidParameters(format!("Given float {} is not valid!", initial_value)).into());
+        }
+        window_length.validate()?;
+        Ok(RollingStandardDeviationProcessor {
+            previous_value: IOTypeData::F32(initial_value),
+            window: RollingWindow::new(window_length),
+            sum: 0.0,
+            sum_of_squares: 0.0,
+            evictions_since_resum: 0,
+        })
+    }
+}
+
+//region Internal Helpers
+
+/// The values currently in a rolling window, oldest first, with their time of input.
+#[derive(Debug, Clone)]
+struct RollingWindow {
+    length: RollingWindowLength,
+    values: VecDeque<(f32, Instant)>,
+}
+
+impl RollingWindow {
+    fn new(length: RollingWindowLength) -> Self {
+        let capacity = match length {
+            RollingWindowLength::Samples(count) => count + 1,
+            RollingWindowLength::Duration(_) => 0,
+        };
+        RollingWindow { length, values: VecDeque::with_capacity(capacity) }
+    }
+
+    /// Adds a value, returning the values that fell out of the window as a result.
+    fn push(&mut self, value: f32, time_of_input: Instant) -> impl Iterator<Item = f32> + '_ {
+        self.values.push_back((value, time_of_input));
+        let evict_count = match self.length {
+            RollingWindowLength::Samples(count) => self.values.len().saturating_sub(count),
+            RollingWindowLength::Duration(duration) => {
+                let newest = self.values.len() - 1;
+                self.values.iter().take(newest)
+                    .take_while(|(_, time)| time_of_input.saturating_duration_since(*time) > duration)
+                    .count()
+            }
+        };
+        self.values.drain(..evict_count).map(|(value, _)| value)
+    }
+}
+
+/// A float ordered by `f32::total_cmp`, to be used as a sorted key.
+#[derive(Debug, Clone, Copy)]
+struct OrderedFloat(f32);
+
+impl PartialEq for OrderedFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.total_cmp(&other.0).is_eq()
+    }
+}
+
+impl Eq for OrderedFloat {}
+
+impl PartialOrd for OrderedFloat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedFloat {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// A sorted collection of floats allowing duplicates, with O(log n) insertion and removal.
+#[derive(Debug, Clone, Default)]
+struct SortedFloatMultiset {
+    counts: BTreeMap<OrderedFloat, usize>,
+    len: usize,
+}
+
+impl SortedFloatMultiset {
+    fn insert(&mut self, value: f32) {
+        *self.counts.entry(OrderedFloat(value)).or_insert(0) += 1;
+        self.len += 1;
+    }
+
+    /// Removes one instance of the value, returning false if there was none.
+    fn remove(&mut self, value: f32) -> bool {
+        match self.counts.get_mut(&OrderedFloat(value)) {
+            None => false,
+            Some(count) => {
+                *count -= 1;
+                if *count == 0 {
+                    self.counts.remove(&OrderedFloat(value));
+                }
+                self.len -= 1;
+                true
+            }
+        }
+    }
+
+    fn first(&self) -> Option<f32> {
+        self.counts.first_key_value().map(|(value, _)| value.0)
+    }
+
+    fn last(&self) -> Option<f32> {
+        self.counts.last_key_value().map(|(value, _)| value.0)
+    }
+
+    fn pop_first(&mut self) -> Option<f32> {
+        let value = self.first()?;
+        self.remove(value);
+        Some(value)
+    }
+
+    fn pop_last(&mut self) -> Option<f32> {
+        let value = self.last()?;
+        self.remove(value);
+        Some(value)
+    }
+}
+
+fn finite_window_input(value: &IOTypeData) -> Result<f32, FeagiDataProcessingError> {
+    let float_result = f32::try_from(value)?;
+    if !float_result.is_finite() {
+        return Err(IODataError::InvalidParameters(format!("Cannot add non-finite value {} to a rolling window!", float_result)).into());
+    }
+    Ok(float_result)
+}
+
+//endregion
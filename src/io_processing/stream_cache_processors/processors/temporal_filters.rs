@@ -0,0 +1,415 @@
+//! Time aware filtering processors for smoothing float streams arriving at irregular rates.
+//!
+//! Unlike the rolling window processors, which treat every input as one step, these processors
+//! use the `time_of_input` of each input, so their output does not depend on how often a
+//! sensor happens to report. An input arriving at the same instant as the previous one is
+//! treated as having had no time to change the filter, except by the Kalman filter, which
+//! still learns from the extra measurement.
+//!
+//! Each filter adopts its first input directly, as there is no earlier time to filter from.
+//! The initial value given on construction is only reported before any input arrives.
+//! Non-finite inputs are rejected, rather than permanently corrupting the filter state.
+
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+use crate::error::{FeagiDataProcessingError, IODataError};
+use crate::io_data::{IOTypeData, IOTypeVariant};
+use crate::io_processing::StreamCacheProcessor;
+
+//region Exponential Moving Average
+
+/// A stream processor outputting an exponential moving average of float values, with a time constant.
+///
+/// Each input moves the output towards it by `1 - e^(-dt / time_constant)`, where `dt` is the
+/// time since the previous input. After one time constant of a steady input, the output has
+/// covered about 63% of the distance to it, however many inputs arrived in that time.
+///
+/// # Example
+/// ```
+/// use std::time::{Duration, Instant};
+/// use feagi_core_data_structures_and_processing::io_data::IOTypeData;
+/// use feagi_core_data_structures_and_processing::io_processing::StreamCacheProcessor;
+/// use feagi_core_data_structures_and_processing::io_processing::processors::ExponentialMovingAverageProcessor;
+///
+/// let mut processor = ExponentialMovingAverageProcessor::new(Duration::from_millis(100), 0.0).unwrap();
+/// let start = Instant::now();
+/// processor.process_new_input(&IOTypeData::F32(0.0), start).unwrap();
+/// let output = processor.process_new_input(&IOTypeData::F32(1.0), start + Duration::from_millis(100)).unwrap();
+/// assert!((f32::try_from(output).unwrap() - 0.632).abs() < 0.001);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExponentialMovingAverageProcessor {
+    previous_value: IOTypeData,
+    time_constant_seconds: f32,
+    average: f32,
+    last_input_time: Option<Instant>,
+}
+
+impl Display for ExponentialMovingAverageProcessor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ExponentialMovingAverageProcessor(time_constant={:?}s,prev_val={:?})", self.time_constant_seconds, self.previous_value)
+    }
+}
+
+impl StreamCacheProcessor for ExponentialMovingAverageProcessor {
+    fn get_input_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32
+    }
+
+    fn get_output_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32
+    }
+
+    fn get_most_recent_output(&self) -> &IOTypeData {
+        &self.previous_value
+    }
+
+    fn process_new_input(&mut self, value: &IOTypeData, time_of_input: Instant) -> Result<&IOTypeData, FeagiDataProcessingError> {
+        let input = finite_input(value)?;
+        self.average = match seconds_since_last_input(&mut self.last_input_time, time_of_input) {
+            None => input,
+            Some(dt) => {
+                let alpha = 1.0 - (-dt / self.time_constant_seconds).exp();
+                self.average + alpha * (input - self.average)
+            }
+        };
+        self.previous_value = IOTypeData::F32(self.average);
+        Ok(&self.previous_value)
+    }
+}
+
+impl ExponentialMovingAverageProcessor {
+    /// Creates a new ExponentialMovingAverageProcessor.
+    ///
+    /// # Arguments
+    /// * `time_constant` - Time for the output to cover ~63% of a step in the input (must be > 0)
+    /// * `initial_value` - The value to output before any input arrives (must be finite)
+    ///
+    /// # Returns
+    /// * `Ok(ExponentialMovingAverageProcessor)` - A new processor instance
+    /// * `Err(FeagiDataProcessingError)` - If the time constant is 0 or initial_value is invalid (NaN/infinite)
+    pub fn new(time_constant: Duration, initial_value: f32) -> Result<Self, FeagiDataProcessingError> {
+        validate_finite("initial value", initial_value)?;
+        if time_constant.is_zero() {
+            return Err(IODataError::InvalidParameters("Time constant cannot be 0!".into()).into());
+        }
+        Ok(ExponentialMovingAverageProcessor {
+            previous_value: IOTypeData::F32(initial_value),
+            time_constant_seconds: time_constant.as_secs_f32(),
+            average: initial_value,
+            last_input_time: None,
+        })
+    }
+}
+
+//endregion
+
+//region Low Pass
+
+/// A stream processor applying a first order (RC) low-pass filter to float values.
+///
+/// Frequencies in the input above the cutoff frequency are attenuated, removing jitter while
+/// following slower changes. Each input moves the output towards it by `dt / (RC + dt)`, with
+/// `RC = 1 / (2π * cutoff_frequency)` and `dt` the time since the previous input.
+///
+/// # Example
+/// ```
+/// use feagi_core_data_structures_and_processing::io_processing::processors::LowPassFilterProcessor;
+/// // Remove jitter above 5Hz
+/// let processor = LowPassFilterProcessor::new(5.0, 0.0).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct LowPassFilterProcessor {
+    previous_value: IOTypeData,
+    cutoff_frequency_hz: f32,
+    filtered: f32,
+    last_input_time: Option<Instant>,
+}
+
+impl Display for LowPassFilterProcessor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LowPassFilterProcessor(cutoff={:?}Hz,prev_val={:?})", self.cutoff_frequency_hz, self.previous_value)
+    }
+}
+
+impl StreamCacheProcessor for LowPassFilterProcessor {
+    fn get_input_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32
+    }
+
+    fn get_output_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32
+    }
+
+    fn get_most_recent_output(&self) -> &IOTypeData {
+        &self.previous_value
+    }
+
+    fn process_new_input(&mut self, value: &IOTypeData, time_of_input: Instant) -> Result<&IOTypeData, FeagiDataProcessingError> {
+        let input = finite_input(value)?;
+        self.filtered = match seconds_since_last_input(&mut self.last_input_time, time_of_input) {
+            None => input,
+            Some(dt) => low_pass(self.filtered, input, dt, self.cutoff_frequency_hz),
+        };
+        self.previous_value = IOTypeData::F32(self.filtered);
+        Ok(&self.previous_value)
+    }
+}
+
+impl LowPassFilterProcessor {
+    /// Creates a new LowPassFilterProcessor.
+    ///
+    /// # Arguments
+    /// * `cutoff_frequency_hz` - The frequency in Hz above which input changes are attenuated (must be > 0)
+    /// * `initial_value` - The value to output before any input arrives (must be finite)
+    ///
+    /// # Returns
+    /// * `Ok(LowPassFilterProcessor)` - A new processor instance
+    /// * `Err(FeagiDataProcessingError)` - If the cutoff frequency is not positive, or initial_value is invalid (NaN/infinite)
+    pub fn new(cutoff_frequency_hz: f32, initial_value: f32) -> Result<Self, FeagiDataProcessingError> {
+        validate_finite("initial value", initial_value)?;
+        validate_positive("cutoff frequency", cutoff_frequency_hz)?;
+        Ok(LowPassFilterProcessor {
+            previous_value: IOTypeData::F32(initial_value),
+            cutoff_frequency_hz,
+            filtered: initial_value,
+            last_input_time: None,
+        })
+    }
+}
+
+//endregion
+
+//region Kalman
+
+/// A stream processor estimating a slowly changing float value from noisy measurements with a 1D Kalman filter.
+///
+/// The value is modelled as a random walk, whose variance grows by `process_noise` every second
+/// between inputs. Each input is a measurement with variance `measurement_noise`, blended in
+/// according to how uncertain the current estimate is. A larger process noise follows changes
+/// faster, while a larger measurement noise smooths more.
+///
+/// # Example
+/// ```
+/// use feagi_core_data_structures_and_processing::io_processing::processors::KalmanFilterProcessor;
+/// // A distance sensor with a standard deviation of 2cm, tracking objects moving ~10cm per second
+/// let processor = KalmanFilterProcessor::new(100.0, 4.0, 0.0).unwrap();
+/// assert_eq!(processor.get_estimate_variance(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KalmanFilterProcessor {
+    previous_value: IOTypeData,
+    process_noise: f32,
+    measurement_noise: f32,
+    estimate: f32,
+    estimate_variance: f32,
+    last_input_time: Option<Instant>,
+}
+
+impl Display for KalmanFilterProcessor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "KalmanFilterProcessor(process_noise={:?},measurement_noise={:?},prev_val={:?})", self.process_noise, self.measurement_noise, self.previous_value)
+    }
+}
+
+impl StreamCacheProcessor for KalmanFilterProcessor {
+    fn get_input_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32
+    }
+
+    fn get_output_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32
+    }
+
+    fn get_most_recent_output(&self) -> &IOTypeData {
+        &self.previous_value
+    }
+
+    fn process_new_input(&mut self, value: &IOTypeData, time_of_input: Instant) -> Result<&IOTypeData, FeagiDataProcessingError> {
+        let measurement = finite_input(value)?;
+        match seconds_since_last_input(&mut self.last_input_time, time_of_input) {
+            None => {
+                self.estimate = measurement;
+                self.estimate_variance = self.measurement_noise;
+            }
+            Some(dt) => {
+                let predicted_variance = self.estimate_variance + self.process_noise * dt;
+                let gain = predicted_variance / (predicted_variance + self.measurement_noise);
+                self.estimate += gain * (measurement - self.estimate);
+                self.estimate_variance = (1.0 - gain) * predicted_variance;
+            }
+        }
+        self.previous_value = IOTypeData::F32(self.estimate);
+        Ok(&self.previous_value)
+    }
+}
+
+impl KalmanFilterProcessor {
+    /// Creates a new KalmanFilterProcessor.
+    ///
+    /// # Arguments
+    /// * `process_noise` - Variance the true value gains per second (must be > 0)
+    /// * `measurement_noise` - Variance of each measurement (must be > 0)
+    /// * `initial_value` - The value to output before any input arrives (must be finite)
+    ///
+    /// # Returns
+    /// * `Ok(KalmanFilterProcessor)` - A new processor instance
+    /// * `Err(FeagiDataProcessingError)` - If a noise is not positive, or initial_value is invalid (NaN/infinite)
+    pub fn new(process_noise: f32, measurement_noise: f32, initial_value: f32) -> Result<Self, FeagiDataProcessingError> {
+        validate_finite("initial value", initial_value)?;
+        validate_positive("process noise", process_noise)?;
+        validate_positive("measurement noise", measurement_noise)?;
+        Ok(KalmanFilterProcessor {
+            previous_value: IOTypeData::F32(initial_value),
+            process_noise,
+            measurement_noise,
+            estimate: initial_value,
+            estimate_variance: measurement_noise,
+            last_input_time: None,
+        })
+    }
+
+    /// Returns the variance of the current estimate, or None if no input has arrived yet.
+    pub fn get_estimate_variance(&self) -> Option<f32> {
+        self.last_input_time.map(|_| self.estimate_variance)
+    }
+}
+
+//endregion
+
+//region One Euro
+
+/// A stream processor applying the one euro filter to float values.
+///
+/// A low-pass filter whose cutoff frequency rises with the speed the value changes at, as
+/// described by Casiez et al. (2012). Slow movements are smoothed heavily to remove jitter,
+/// while fast movements are followed with little lag.
+///
+/// The cutoff frequency is `min_cutoff_hz + beta * |speed|`, where speed is the rate of change
+/// per second, itself low-pass filtered at `derivative_cutoff_hz`.
+///
+/// # Example
+/// ```
+/// use feagi_core_data_structures_and_processing::io_processing::processors::OneEuroFilterProcessor;
+/// // Typical starting parameters: tune min_cutoff for jitter, then beta for lag
+/// let processor = OneEuroFilterProcessor::new(1.0, 0.007, 1.0, 0.0).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct OneEuroFilterProcessor {
+    previous_value: IOTypeData,
+    min_cutoff_hz: f32,
+    beta: f32,
+    derivative_cutoff_hz: f32,
+    filtered: f32,
+    filtered_derivative: f32,
+    last_input_time: Option<Instant>,
+}
+
+impl Display for OneEuroFilterProcessor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OneEuroFilterProcessor(min_cutoff={:?}Hz,beta={:?},derivative_cutoff={:?}Hz,prev_val={:?})", self.min_cutoff_hz, self.beta, self.derivative_cutoff_hz, self.previous_value)
+    }
+}
+
+impl StreamCacheProcessor for OneEuroFilterProcessor {
+    fn get_input_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32
+    }
+
+    fn get_output_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32
+    }
+
+    fn get_most_recent_output(&self) -> &IOTypeData {
+        &self.previous_value
+    }
+
+    fn process_new_input(&mut self, value: &IOTypeData, time_of_input: Instant) -> Result<&IOTypeData, FeagiDataProcessingError> {
+        let input = finite_input(value)?;
+        match seconds_since_last_input(&mut self.last_input_time, time_of_input) {
+            None => {
+                self.filtered = input;
+                self.filtered_derivative = 0.0;
+            }
+            Some(dt) if dt > 0.0 => {
+                let derivative = (input - self.filtered) / dt;
+                self.filtered_derivative = low_pass(self.filtered_derivative, derivative, dt, self.derivative_cutoff_hz);
+                let cutoff_hz = self.min_cutoff_hz + self.beta * self.filtered_derivative.abs();
+                self.filtered = low_pass(self.filtered, input, dt, cutoff_hz);
+            }
+            Some(_) => {} // No time has passed, so the speed is unknown
+        }
+        self.previous_value = IOTypeData::F32(self.filtered);
+        Ok(&self.previous_value)
+    }
+}
+
+impl OneEuroFilterProcessor {
+    /// Creates a new OneEuroFilterProcessor.
+    ///
+    /// # Arguments
+    /// * `min_cutoff_hz` - Cutoff frequency in Hz while the value is still (must be > 0). Lower removes more jitter
+    /// * `beta` - How much the cutoff frequency rises per unit per second of speed (must be >= 0). Higher reduces lag
+    /// * `derivative_cutoff_hz` - Cutoff frequency in Hz used to smooth the speed (must be > 0)
+    /// * `initial_value` - The value to output before any input arrives (must be finite)
+    ///
+    /// # Returns
+    /// * `Ok(OneEuroFilterProcessor)` - A new processor instance
+    /// * `Err(FeagiDataProcessingError)` - If a parameter is out of range or invalid (NaN/infinite)
+    pub fn new(min_cutoff_hz: f32, beta: f32, derivative_cutoff_hz: f32, initial_value: f32) -> Result<Self, FeagiDataProcessingError> {
+        validate_finite("initial value", initial_value)?;
+        validate_positive("minimum cutoff frequency", min_cutoff_hz)?;
+        validate_positive("derivative cutoff frequency", derivative_cutoff_hz)?;
+        validate_finite("beta", beta)?;
+        if beta < 0.0 {
+            return Err(IODataError::InvalidParameters(format!("Beta {} cannot be negative!", beta)).into());
+        }
+        Ok(OneEuroFilterProcessor {
+            previous_value: IOTypeData::F32(initial_value),
+            min_cutoff_hz,
+            beta,
+            derivative_cutoff_hz,
+            filtered: initial_value,
+            filtered_derivative: 0.0,
+            last_input_time: None,
+        })
+    }
+}
+
+//endregion
+
+/// Records the time of a new input, returning the seconds since the previous one, or None if this is the first.
+fn seconds_since_last_input(last_input_time: &mut Option<Instant>, time_of_input: Instant) -> Option<f32> {
+    let previous = last_input_time.replace(time_of_input)?;
+    Some(time_of_input.saturating_duration_since(previous).as_secs_f32())
+}
+
+/// One step of a first order RC low-pass filter.
+fn low_pass(filtered: f32, input: f32, dt: f32, cutoff_frequency_hz: f32) -> f32 {
+    let rc = 1.0 / (std::f32::consts::TAU * cutoff_frequency_hz);
+    let alpha = dt / (rc + dt);
+    filtered + alpha * (input - filtered)
+}
+
+fn finite_input(value: &IOTypeData) -> Result<f32, FeagiDataProcessingError> {
+    let input = f32::try_from(value)?;
+    if !input.is_finite() {
+        return Err(IODataError::InvalidParameters(format!("Cannot filter non-finite input {}!", input)).into());
+    }
+    Ok(input)
+}
+
+fn validate_finite(name: &str, value: f32) -> Result<(), FeagiDataProcessingError> {
+    if !value.is_finite() {
+        return Err(IODataError::InvalidParameters(format!("Given {} {} is not valid!", name, value)).into());
+    }
+    Ok(())
+}
+
+fn validate_positive(name: &str, value: f32) -> Result<(), FeagiDataProcessingError> {
+    validate_finite(name, value)?;
+    if value <= 0.0 {
+        return Err(IODataError::InvalidParameters(format!("Given {} {} must be positive!", name, value)).into());
+    }
+    Ok(())
+}
@@ -0,0 +1,329 @@
+//! Registry of neural encoder and decoder constructors.
+//!
+//! This module defines the `NeuronCoderRegistry`, which maps coder IDs to functions that
+//! construct encoders and decoders. All built-in coders are registered under their
+//! `NeuronCoderVariantType`, and downstream crates may register their own coders under
+//! a custom ID, or replace the built-in ones.
+
+use std::collections::HashMap;
+use std::fmt;
+use crate::error::{FeagiDataProcessingError, IODataError};
+use crate::genomic_structures::{CorticalID, CorticalType};
+use crate::io_data::IOTypeVariant;
+use crate::neuron_data::xyzp::coders::{NeuronCoderVariantType, NeuronXYZPDecoder, NeuronXYZPEncoder};
+use crate::neuron_data::xyzp::coders::encoders::{F32LinearNeuronXYZPEncoder, F32PSPBidirectionalNeuronXYZPEncoder, F32SplitSignDividedNeuronXYZPEncoder, ImageFrameNeuronXYZPEncoder, SegmentedImageFrameNeuronXYZPEncoder};
+use crate::neuron_data::xyzp::coders::decoders::{F32LinearNeuronXYZPDecoder, F32PSPBidirectionalNeuronXYZPDecoder, F32SplitSignDividedNeuronXYZPDecoder, ImageFrameNeuronXYZPDecoder, SegmentedImageFrameNeuronXYZPDecoder};
+
+/// Function that constructs an encoder from the given parameters.
+pub type NeuronXYZPEncoderConstructor = Box<dyn Fn(&NeuronCoderParameters) -> Result<Box<dyn NeuronXYZPEncoder + Sync + Send>, FeagiDataProcessingError> + Sync + Send>;
+
+/// Function that constructs a decoder from the given parameters.
+pub type NeuronXYZPDecoderConstructor = Box<dyn Fn(&NeuronCoderParameters) -> Result<Box<dyn NeuronXYZPDecoder + Sync + Send>, FeagiDataProcessingError> + Sync + Send>;
+
+//region Neuron Coder ID
+
+/// Identifies a coder within a `NeuronCoderRegistry`.
+///
+/// Built-in coders are identified by their `NeuronCoderVariantType`, while coders defined
+/// outside this crate are identified by a user chosen name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NeuronCoderID {
+    BuiltIn(NeuronCoderVariantType),
+    Custom(String),
+}
+
+impl fmt::Display for NeuronCoderID {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BuiltIn(variant) => write!(f, "NeuronCoderID<BuiltIn>({:?})", variant),
+            Self::Custom(name) => write!(f, "NeuronCoderID<Custom>({})", name),
+        }
+    }
+}
+
+impl From<NeuronCoderVariantType> for NeuronCoderID {
+    fn from(value: NeuronCoderVariantType) -> Self {
+        NeuronCoderID::BuiltIn(value)
+    }
+}
+
+//endregion
+
+//region Neuron Coder Parameters
+
+/// Parameters passed to coder constructors.
+///
+/// # Fields
+///
+/// * `cortical_ids` - The cortical areas the coder writes to / reads from
+/// * `data_type` - The data type of the coder. For images, this includes the image properties
+/// * `z_resolution` - The depth of each channel, used by coders that encode values along Z
+#[derive(Debug, Clone, PartialEq)]
+pub struct NeuronCoderParameters {
+    cortical_ids: Vec<CorticalID>,
+    data_type: IOTypeVariant,
+    z_resolution: u32,
+}
+
+impl NeuronCoderParameters {
+    /// Creates a new set of coder parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no cortical IDs are given, or if the Z resolution is 0.
+    pub fn new(cortical_ids: Vec<CorticalID>, data_type: IOTypeVariant, z_resolution: u32) -> Result<Self, FeagiDataProcessingError> {
+        if cortical_ids.is_empty() {
+            return Err(IODataError::InvalidParameters("Coder parameters must include at least one Cortical ID!".into()).into())
+        }
+        if z_resolution == 0 {
+            return Err(IODataError::InvalidParameters("Unable to define a neuron resolution of 0!".into()).into())
+        }
+        Ok(NeuronCoderParameters {
+            cortical_ids,
+            data_type,
+            z_resolution,
+        })
+    }
+
+    pub fn get_cortical_ids(&self) -> &[CorticalID] {
+        &self.cortical_ids
+    }
+
+    pub fn get_data_type(&self) -> IOTypeVariant {
+        self.data_type
+    }
+
+    pub fn get_z_resolution(&self) -> u32 {
+        self.z_resolution
+    }
+
+    /// Returns the only cortical ID, erroring if there is not exactly one.
+    pub fn get_single_cortical_id(&self) -> Result<CorticalID, FeagiDataProcessingError> {
+        if self.cortical_ids.len() != 1 {
+            return Err(IODataError::InvalidParameters(format!("Expected exactly 1 Cortical ID, but was given {}!", self.cortical_ids.len())).into())
+        }
+        Ok(self.cortical_ids[0])
+    }
+
+    /// Returns all nine cortical IDs of a segmented image, erroring if there are not exactly nine.
+    pub fn get_nine_cortical_ids(&self) -> Result<[CorticalID; 9], FeagiDataProcessingError> {
+        match <[CorticalID; 9]>::try_from(self.cortical_ids.as_slice()) {
+            Ok(ids) => Ok(ids),
+            Err(_) => Err(IODataError::InvalidParameters(format!("Expected exactly 9 Cortical IDs, but was given {}!", self.cortical_ids.len())).into())
+        }
+    }
+}
+
+//endregion
+
+//region Neuron Coder Registry
+
+/// Maps coder IDs to encoder and decoder constructors.
+///
+/// `NeuronCoderRegistry::new()` comes with every built-in coder registered under its
+/// `NeuronCoderVariantType`. Custom coders can be added with `register_encoder` /
+/// `register_decoder`, and built-in coders can be replaced by deregistering them first.
+///
+/// # Example
+///
+/// ```rust
+/// use feagi_core_data_structures_and_processing::genomic_structures::{CorticalType, SensorCorticalType, CorticalGroupingIndex};
+/// use feagi_core_data_structures_and_processing::io_data::IOTypeVariant;
+/// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{NeuronCoderParameters, NeuronCoderRegistry};
+///
+/// let registry = NeuronCoderRegistry::new();
+/// let cortical_type: CorticalType = SensorCorticalType::Proximity.into();
+/// let cortical_id = cortical_type.to_cortical_id(CorticalGroupingIndex::from(0)).unwrap();
+/// let parameters = NeuronCoderParameters::new(vec![cortical_id], IOTypeVariant::F32Normalized0To1, 10).unwrap();
+/// let encoder = registry.create_encoder_for_cortical_type(&cortical_type, &parameters).unwrap();
+/// assert_eq!(encoder.get_encodable_data_type(), IOTypeVariant::F32Normalized0To1);
+/// ```
+pub struct NeuronCoderRegistry {
+    encoder_constructors: HashMap<NeuronCoderID, NeuronXYZPEncoderConstructor>,
+    decoder_constructors: HashMap<NeuronCoderID, NeuronXYZPDecoderConstructor>,
+}
+
+impl Default for NeuronCoderRegistry {
+    fn default() -> Self {
+        NeuronCoderRegistry::new()
+    }
+}
+
+impl NeuronCoderRegistry {
+    /// Creates a registry with all built-in coders registered.
+    pub fn new() -> Self {
+        let mut registry = NeuronCoderRegistry::new_empty();
+        registry.register_built_in_coders();
+        registry
+    }
+
+    /// Creates a registry with no coders registered at all.
+    pub fn new_empty() -> Self {
+        NeuronCoderRegistry {
+            encoder_constructors: HashMap::new(),
+            decoder_constructors: HashMap::new(),
+        }
+    }
+
+    //region Registration
+
+    /// Registers an encoder constructor under the given ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an encoder is already registered under that ID.
+    pub fn register_encoder(&mut self, coder_id: NeuronCoderID, constructor: NeuronXYZPEncoderConstructor) -> Result<(), FeagiDataProcessingError> {
+        if self.encoder_constructors.contains_key(&coder_id) {
+            return Err(IODataError::InvalidParameters(format!("An encoder is already registered under {}!", coder_id)).into())
+        }
+        _ = self.encoder_constructors.insert(coder_id, constructor);
+        Ok(())
+    }
+
+    /// Registers a decoder constructor under the given ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a decoder is already registered under that ID.
+    pub fn register_decoder(&mut self, coder_id: NeuronCoderID, constructor: NeuronXYZPDecoderConstructor) -> Result<(), FeagiDataProcessingError> {
+        if self.decoder_constructors.contains_key(&coder_id) {
+            return Err(IODataError::InvalidParameters(format!("A decoder is already registered under {}!", coder_id)).into())
+        }
+        _ = self.decoder_constructors.insert(coder_id, constructor);
+        Ok(())
+    }
+
+    /// Removes the encoder registered under the given ID. Returns false if there was none.
+    pub fn deregister_encoder(&mut self, coder_id: &NeuronCoderID) -> bool {
+        self.encoder_constructors.remove(coder_id).is_some()
+    }
+
+    /// Removes the decoder registered under the given ID. Returns false if there was none.
+    pub fn deregister_decoder(&mut self, coder_id: &NeuronCoderID) -> bool {
+        self.decoder_constructors.remove(coder_id).is_some()
+    }
+
+    pub fn contains_encoder(&self, coder_id: &NeuronCoderID) -> bool {
+        self.encoder_constructors.contains_key(coder_id)
+    }
+
+    pub fn contains_decoder(&self, coder_id: &NeuronCoderID) -> bool {
+        self.decoder_constructors.contains_key(coder_id)
+    }
+
+    //endregion
+
+    //region Construction
+
+    /// Constructs the encoder registered under the given ID.
+    pub fn create_encoder(&self, coder_id: &NeuronCoderID, parameters: &NeuronCoderParameters) -> Result<Box<dyn NeuronXYZPEncoder + Sync + Send>, FeagiDataProcessingError> {
+        match self.encoder_constructors.get(coder_id) {
+            Some(constructor) => constructor(parameters),
+            None => Err(IODataError::InvalidParameters(format!("No encoder is registered under {}!", coder_id)).into())
+        }
+    }
+
+    /// Constructs the decoder registered under the given ID.
+    pub fn create_decoder(&self, coder_id: &NeuronCoderID, parameters: &NeuronCoderParameters) -> Result<Box<dyn NeuronXYZPDecoder + Sync + Send>, FeagiDataProcessingError> {
+        match self.decoder_constructors.get(coder_id) {
+            Some(constructor) => constructor(parameters),
+            None => Err(IODataError::InvalidParameters(format!("No decoder is registered under {}!", coder_id)).into())
+        }
+    }
+
+    /// Constructs the encoder for the default coder type of the given cortical type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cortical type is not a sensor, or if its coder is not registered.
+    pub fn create_encoder_for_cortical_type(&self, cortical_type: &CorticalType, parameters: &NeuronCoderParameters) -> Result<Box<dyn NeuronXYZPEncoder + Sync + Send>, FeagiDataProcessingError> {
+        cortical_type.verify_is_sensor()?;
+        self.create_encoder(&cortical_type.try_get_coder_type()?.into(), parameters)
+    }
+
+    /// Constructs the decoder for the default coder type of the given cortical type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cortical type is not a motor, or if its coder is not registered.
+    pub fn create_decoder_for_cortical_type(&self, cortical_type: &CorticalType, parameters: &NeuronCoderParameters) -> Result<Box<dyn NeuronXYZPDecoder + Sync + Send>, FeagiDataProcessingError> {
+        cortical_type.verify_is_motor()?;
+        self.create_decoder(&cortical_type.try_get_coder_type()?.into(), parameters)
+    }
+
+    //endregion
+
+    //region Internal Functions
+
+    fn register_built_in_coders(&mut self) {
+        // None of these can collide, as the registry is empty when this is called
+        _ = self.register_encoder(NeuronCoderVariantType::F32Normalized0To1_Linear.into(), Box::new(|parameters| {
+            verify_data_type(parameters, IOTypeVariant::F32Normalized0To1)?;
+            Ok(Box::new(F32LinearNeuronXYZPEncoder::new(parameters.get_single_cortical_id()?, parameters.get_z_resolution())?))
+        }));
+        _ = self.register_encoder(NeuronCoderVariantType::F32NormalizedM1To1_PSPBidirectional.into(), Box::new(|parameters| {
+            verify_data_type(parameters, IOTypeVariant::F32NormalizedM1To1)?;
+            Ok(Box::new(F32PSPBidirectionalNeuronXYZPEncoder::new(parameters.get_single_cortical_id()?, parameters.get_z_resolution())?))
+        }));
+        _ = self.register_encoder(NeuronCoderVariantType::F32NormalizedM1To1_SplitSignDivided.into(), Box::new(|parameters| {
+            verify_data_type(parameters, IOTypeVariant::F32NormalizedM1To1)?;
+            Ok(Box::new(F32SplitSignDividedNeuronXYZPEncoder::new(parameters.get_single_cortical_id()?, parameters.get_z_resolution())?))
+        }));
+        _ = self.register_encoder(NeuronCoderVariantType::ImageFrame.into(), Box::new(|parameters| {
+            match parameters.get_data_type() {
+                IOTypeVariant::ImageFrame(Some(image_properties)) => Ok(Box::new(ImageFrameNeuronXYZPEncoder::new(parameters.get_single_cortical_id()?, &image_properties)?)),
+                _ => Err(data_type_error(parameters, "an ImageFrame with defined properties"))
+            }
+        }));
+        _ = self.register_encoder(NeuronCoderVariantType::SegmentedImageFrame.into(), Box::new(|parameters| {
+            match parameters.get_data_type() {
+                IOTypeVariant::SegmentedImageFrame(Some(segmented_properties)) => Ok(Box::new(SegmentedImageFrameNeuronXYZPEncoder::new(parameters.get_nine_cortical_ids()?, segmented_properties)?)),
+                _ => Err(data_type_error(parameters, "a SegmentedImageFrame with defined properties"))
+            }
+        }));
+
+        _ = self.register_decoder(NeuronCoderVariantType::F32Normalized0To1_Linear.into(), Box::new(|parameters| {
+            verify_data_type(parameters, IOTypeVariant::F32Normalized0To1)?;
+            Ok(Box::new(F32LinearNeuronXYZPDecoder::new(parameters.get_single_cortical_id()?, parameters.get_z_resolution())?))
+        }));
+        _ = self.register_decoder(NeuronCoderVariantType::F32NormalizedM1To1_PSPBidirectional.into(), Box::new(|parameters| {
+            verify_data_type(parameters, IOTypeVariant::F32NormalizedM1To1)?;
+            Ok(Box::new(F32PSPBidirectionalNeuronXYZPDecoder::new(parameters.get_single_cortical_id()?, parameters.get_z_resolution())?))
+        }));
+        _ = self.register_decoder(NeuronCoderVariantType::F32NormalizedM1To1_SplitSignDivided.into(), Box::new(|parameters| {
+            verify_data_type(parameters, IOTypeVariant::F32NormalizedM1To1)?;
+            Ok(Box::new(F32SplitSignDividedNeuronXYZPDecoder::new(parameters.get_single_cortical_id()?, parameters.get_z_resolution())?))
+        }));
+        _ = self.register_decoder(NeuronCoderVariantType::ImageFrame.into(), Box::new(|parameters| {
+            match parameters.get_data_type() {
+                IOTypeVariant::ImageFrame(Some(image_properties)) => Ok(Box::new(ImageFrameNeuronXYZPDecoder::new(parameters.get_single_cortical_id()?, &image_properties)?)),
+                _ => Err(data_type_error(parameters, "an ImageFrame with defined properties"))
+            }
+        }));
+        _ = self.register_decoder(NeuronCoderVariantType::SegmentedImageFrame.into(), Box::new(|parameters| {
+            match parameters.get_data_type() {
+                IOTypeVariant::SegmentedImageFrame(Some(segmented_properties)) => Ok(Box::new(SegmentedImageFrameNeuronXYZPDecoder::new(parameters.get_nine_cortical_ids()?, segmented_properties)?)),
+                _ => Err(data_type_error(parameters, "a SegmentedImageFrame with defined properties"))
+            }
+        }));
+    }
+
+    //endregion
+}
+
+//endregion
+
+//region helpers
+
+fn verify_data_type(parameters: &NeuronCoderParameters, expected: IOTypeVariant) -> Result<(), FeagiDataProcessingError> {
+    if parameters.get_data_type() != expected {
+        return Err(IODataError::InvalidParameters(format!("Coder expected data type {} but was given {}!", expected, parameters.get_data_type())).into())
+    }
+    Ok(())
+}
+
+fn data_type_error(parameters: &NeuronCoderParameters, expected: &str) -> FeagiDataProcessingError {
+    IODataError::InvalidParameters(format!("Coder expected {} but was given {}!", expected, parameters.get_data_type())).into()
+}
+
+//endregion
@@ -36,7 +36,7 @@ use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData};
 /// # Thread Safety
 /// All encoder implementations must be `Sync + Send` for use in multi-threaded
 /// neural simulation environments.
-pub(crate) trait NeuronXYZPEncoder {
+pub trait NeuronXYZPEncoder {
     /// Returns the I/O data type this encoder can process.
     ///
     /// This method specifies which [`IOTypeVariant`] this encoder is designed
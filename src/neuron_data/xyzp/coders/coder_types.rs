@@ -48,6 +48,7 @@ use crate::neuron_data::xyzp::coders::encoders::{ImageFrameNeuronXYZPEncoder, F3
 /// - **Bidirectional encoders**: Moderate overhead for sign handling
 /// - **Visual encoders**: Higher computational cost but maintains spatial relationships
 /// - **Segmented encoders**: Most complex, handles multiple spatial regions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NeuronCoderVariantType { // Enum itself must be exposed (methods don't)
     #[allow(non_camel_case_types)] F32Normalized0To1_Linear, // Due to the segmented nature, do this
     #[allow(non_camel_case_types)] F32NormalizedM1To1_PSPBidirectional,
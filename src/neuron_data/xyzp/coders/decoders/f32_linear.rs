@@ -0,0 +1,62 @@
+use crate::error::FeagiDataProcessingError;
+use crate::genomic_structures::{CorticalID, CorticalIOChannelIndex, SingleChannelDimensions};
+use crate::io_data::{IOTypeData, IOTypeVariant};
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPArrays};
+use crate::neuron_data::xyzp::coders::NeuronXYZPDecoder;
+
+pub(crate) struct F32LinearNeuronXYZPDecoder {
+    channel_dimensions: SingleChannelDimensions,
+    cortical_read_target: [CorticalID; 1],
+    z_res: f32,
+}
+
+impl NeuronXYZPDecoder for F32LinearNeuronXYZPDecoder {
+    fn get_decoded_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32Normalized0To1
+    }
+
+    fn get_channel_dimensions(&self) -> &SingleChannelDimensions {
+        &self.channel_dimensions
+    }
+
+    fn get_cortical_id_read_destinations(&self) -> &[CorticalID] {
+        &self.cortical_read_target
+    }
+
+    fn read_neuron_data_single_channel(&self, cortical_channel: CorticalIOChannelIndex, read_from: &CorticalMappedXYZPNeuronData) -> Result<IOTypeData, FeagiDataProcessingError> {
+        // No neurons fired in this cortical area, which means the value is at its minimum
+        let neuron_data: &NeuronXYZPArrays = match read_from.get_neurons_of(&self.cortical_read_target[0]) {
+            Some(neuron_data) => neuron_data,
+            None => return Ok(IOTypeData::F32Normalized0To1(0.0))
+        };
+
+        let channel_offset: u32 = self.channel_dimensions.get_x() * *cortical_channel;
+
+        // The encoder writes a single neuron at the floored Z depth. If there are multiple, take the deepest
+        let mut z_index: Option<u32> = None;
+        for neuron in neuron_data.iter() {
+            if neuron.x == channel_offset && neuron.p > 0.0 {
+                z_index = Some(z_index.map_or(neuron.z, |z| z.max(neuron.z)));
+            }
+        };
+        let value: f32 = match z_index {
+            Some(z) => z as f32 / self.z_res,
+            None => 0.0
+        };
+        Ok(IOTypeData::F32Normalized0To1(value.clamp(0.0, 1.0)))
+    }
+}
+
+impl F32LinearNeuronXYZPDecoder {
+
+    pub const CHANNEL_X_LENGTH: u32 = 1;
+    pub const CHANNEL_Y_LENGTH: u32 = 1;
+
+    pub fn new(cortical_read_target: CorticalID, z_resolution: u32) -> Result<Self, FeagiDataProcessingError> {
+        Ok(F32LinearNeuronXYZPDecoder {
+            channel_dimensions: SingleChannelDimensions::new(Self::CHANNEL_X_LENGTH, Self::CHANNEL_Y_LENGTH, z_resolution)?,
+            cortical_read_target: [cortical_read_target; 1],
+            z_res: z_resolution as f32,
+        })
+    }
+}
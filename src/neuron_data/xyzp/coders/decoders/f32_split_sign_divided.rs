@@ -0,0 +1,59 @@
+use crate::error::FeagiDataProcessingError;
+use crate::genomic_structures::{CorticalID, CorticalIOChannelIndex, SingleChannelDimensions};
+use crate::io_data::{IOTypeData, IOTypeVariant};
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPArrays};
+use crate::neuron_data::xyzp::coders::NeuronXYZPDecoder;
+
+pub(crate) struct F32SplitSignDividedNeuronXYZPDecoder {
+    channel_dimensions: SingleChannelDimensions,
+    cortical_read_target: [CorticalID; 1]
+}
+
+impl NeuronXYZPDecoder for F32SplitSignDividedNeuronXYZPDecoder {
+    fn get_decoded_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32NormalizedM1To1
+    }
+
+    fn get_channel_dimensions(&self) -> &SingleChannelDimensions {
+        &self.channel_dimensions
+    }
+
+    fn get_cortical_id_read_destinations(&self) -> &[CorticalID] {
+        &self.cortical_read_target
+    }
+
+    fn read_neuron_data_single_channel(&self, cortical_channel: CorticalIOChannelIndex, read_from: &CorticalMappedXYZPNeuronData) -> Result<IOTypeData, FeagiDataProcessingError> {
+        // No neurons fired in this cortical area, which means the value is at rest
+        let neuron_data: &NeuronXYZPArrays = match read_from.get_neurons_of(&self.cortical_read_target[0]) {
+            Some(neuron_data) => neuron_data,
+            None => return Ok(IOTypeData::F32NormalizedM1To1(0.0))
+        };
+
+        // The encoder places negative values on the first X index of the channel, and positive values on the second
+        let negative_x_index: u32 = self.channel_dimensions.get_x() * *cortical_channel;
+        let positive_x_index: u32 = negative_x_index + 1;
+
+        let mut value: f32 = 0.0;
+        for neuron in neuron_data.iter() {
+            if neuron.x == positive_x_index {
+                value += neuron.p;
+            }
+            else if neuron.x == negative_x_index {
+                value -= neuron.p;
+            }
+        };
+        Ok(IOTypeData::F32NormalizedM1To1(value.clamp(-1.0, 1.0)))
+    }
+}
+
+impl F32SplitSignDividedNeuronXYZPDecoder {
+
+    pub const CHANNEL_X_LENGTH: u32 = 2;
+    pub const CHANNEL_Y_LENGTH: u32 = 1;
+    pub fn new(cortical_read_target: CorticalID, z_resolution: u32) -> Result<Self, FeagiDataProcessingError> {
+        Ok(F32SplitSignDividedNeuronXYZPDecoder {
+            channel_dimensions: SingleChannelDimensions::new(Self::CHANNEL_X_LENGTH, Self::CHANNEL_Y_LENGTH, z_resolution)?,
+            cortical_read_target: [cortical_read_target; 1],
+        })
+    }
+}
@@ -0,0 +1,45 @@
+use crate::error::{FeagiDataProcessingError};
+use crate::genomic_structures::{CorticalID, CorticalIOChannelIndex, SingleChannelDimensions};
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData};
+use crate::neuron_data::xyzp::coders::NeuronXYZPDecoder;
+use crate::io_data::{ImageFrame, IOTypeData, IOTypeVariant};
+use crate::io_data::image_descriptors::ImageFrameProperties;
+
+pub(crate) struct ImageFrameNeuronXYZPDecoder {
+    image_properties: ImageFrameProperties,
+    channel_dimensions: SingleChannelDimensions,
+    cortical_read_target: [CorticalID; 1]
+}
+
+impl NeuronXYZPDecoder for ImageFrameNeuronXYZPDecoder {
+
+    fn get_decoded_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::ImageFrame(Some(self.image_properties))
+    }
+
+    fn get_channel_dimensions(&self) -> &SingleChannelDimensions {
+        &self.channel_dimensions
+    }
+
+    fn get_cortical_id_read_destinations(&self) -> &[CorticalID] {
+        &self.cortical_read_target
+    }
+
+    fn read_neuron_data_single_channel(&self, cortical_channel: CorticalIOChannelIndex, read_from: &CorticalMappedXYZPNeuronData) -> Result<IOTypeData, FeagiDataProcessingError> {
+        let mut image: ImageFrame = ImageFrame::from_image_frame_properties(&self.image_properties)?;
+        image.overwrite_from_neuron_xyzp_data(read_from, self.cortical_read_target[0], cortical_channel)?;
+        Ok(IOTypeData::ImageFrame(image))
+    }
+}
+
+impl ImageFrameNeuronXYZPDecoder {
+    pub fn new(cortical_read_target: CorticalID, image_properties: &ImageFrameProperties) -> Result<Self, FeagiDataProcessingError> {
+        let (width, height) = image_properties.get_expected_xy_resolution();
+        let color_channel_count: usize = image_properties.get_expected_color_channel_layout().into();
+        Ok(ImageFrameNeuronXYZPDecoder{
+            image_properties: *image_properties,
+            channel_dimensions: SingleChannelDimensions::new(width as u32, height as u32, color_channel_count as u32)?,
+            cortical_read_target: [cortical_read_target; 1]
+        })
+    }
+}
@@ -13,24 +13,32 @@
 //! 3. Extracting meaningful values using algorithm-specific methods
 //! 4. Converting to appropriate I/O data types
 //!
-//! # Implementation Status
+//! # Available Decoders
 //!
-//! Currently, decoder implementations are in development. The module structure
-//! is prepared for future decoder types that will complement the existing
-//! encoder implementations.
+//! ## Continuous Value Decoders
+//! - **F32LinearNeuronXYZPDecoder**: Reads Z depth back into normalized [0,1] values
+//! - **F32PSPBidirectionalNeuronXYZPDecoder**: Reads post-synaptic potential encoding back into [-1,1] values
+//! - **F32SplitSignDividedNeuronXYZPDecoder**: Reads separate positive/negative populations back into [-1,1] values
 //!
-//! # Future Decoders
+//! ## Visual Data Decoders
+//! - **ImageFrameNeuronXYZPDecoder**: Reconstructs 2D image data from spatial activations
+//! - **SegmentedImageFrameNeuronXYZPDecoder**: Reconstructs all nine segments of a segmented image
 //!
-//! Planned decoder implementations include:
-//! - **Linear decoders**: For extracting continuous values from activation patterns
-//! - **Bidirectional decoders**: For signed value extraction
-//! - **Visual decoders**: For reconstructing spatial data from neural patterns
-//! - **Multi-region decoders**: For aggregating information across cortical areas
+//! Each decoder is the exact inverse of its matching encoder, within the quantization
+//! of the encoder (for example, the Z resolution of linear floats).
 //!
 //! # Thread Safety
-//! All future decoder implementations will be designed to be thread-safe
-//! for use in multi-threaded neural simulation environments.
+//! All decoder implementations are designed to be `Sync + Send` for safe use
+//! in multi-threaded neural simulation environments.
 
-//mod linear_normalized_floats;
+mod image_frame;
+mod f32_split_sign_divided;
+mod f32_psp_bidirectional;
+mod f32_linear;
+mod segmented_image_frame;
 
-//pub use linear_normalized_floats::*;
\ No newline at end of file
+pub(crate) use image_frame::{ImageFrameNeuronXYZPDecoder};
+pub(crate) use f32_split_sign_divided::{F32SplitSignDividedNeuronXYZPDecoder};
+pub(crate) use f32_psp_bidirectional::{F32PSPBidirectionalNeuronXYZPDecoder};
+pub(crate) use f32_linear::{F32LinearNeuronXYZPDecoder};
+pub(crate) use segmented_image_frame::{SegmentedImageFrameNeuronXYZPDecoder};
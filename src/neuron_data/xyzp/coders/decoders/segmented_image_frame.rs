@@ -0,0 +1,43 @@
+use crate::error::FeagiDataProcessingError;
+use crate::genomic_structures::{CorticalID, CorticalIOChannelIndex, SingleChannelDimensions};
+use crate::io_data::{IOTypeData, IOTypeVariant, SegmentedImageFrame};
+use crate::io_data::image_descriptors::SegmentedImageFrameProperties;
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPDecoder};
+
+pub(crate) struct SegmentedImageFrameNeuronXYZPDecoder {
+    segmented_image_properties: SegmentedImageFrameProperties,
+    channel_dimensions: SingleChannelDimensions, // of the center segment
+    cortical_read_targets: [CorticalID; 9],
+}
+
+impl NeuronXYZPDecoder for SegmentedImageFrameNeuronXYZPDecoder {
+    fn get_decoded_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::SegmentedImageFrame(Some(self.segmented_image_properties))
+    }
+
+    fn get_channel_dimensions(&self) -> &SingleChannelDimensions {
+        &self.channel_dimensions
+    }
+
+    fn get_cortical_id_read_destinations(&self) -> &[CorticalID] {
+        &self.cortical_read_targets
+    }
+
+    fn read_neuron_data_single_channel(&self, cortical_channel: CorticalIOChannelIndex, read_from: &CorticalMappedXYZPNeuronData) -> Result<IOTypeData, FeagiDataProcessingError> {
+        let mut segmented_image: SegmentedImageFrame = SegmentedImageFrame::from_segmented_image_frame_properties(&self.segmented_image_properties)?;
+        segmented_image.overwrite_from_neuron_xyzp_data(read_from, cortical_channel, &self.cortical_read_targets)?;
+        Ok(IOTypeData::SegmentedImageFrame(segmented_image))
+    }
+}
+
+impl SegmentedImageFrameNeuronXYZPDecoder {
+    pub fn new(cortical_read_targets: [CorticalID; 9], segmented_image_properties: SegmentedImageFrameProperties) -> Result<Self, FeagiDataProcessingError> {
+        let (width, height) = segmented_image_properties.get_expected_resolutions().center;
+        let color_channel_count: usize = (*segmented_image_properties.get_center_color_channel()).into();
+        Ok(SegmentedImageFrameNeuronXYZPDecoder{
+            segmented_image_properties,
+            channel_dimensions: SingleChannelDimensions::new(width as u32, height as u32, color_channel_count as u32)?,
+            cortical_read_targets
+        })
+    }
+}
@@ -51,6 +51,11 @@
 //! - Factory methods for creating appropriate encoder/decoder instances
 //! - Cortical area type integration for automatic coder selection
 //!
+//! ## Registry
+//! - **NeuronCoderRegistry**: Maps coder IDs to encoder/decoder constructors
+//! - **NeuronCoderID**: Either a built-in `NeuronCoderVariantType` or a custom name
+//! - Custom coders implementing the public traits can be registered alongside the built-in ones
+//!
 //! # Usage Patterns
 //!
 //!
@@ -71,12 +76,13 @@
 
 mod coder_traits;
 mod coder_types;
+mod coder_registry;
 pub(crate) mod decoders;
 pub(crate) mod encoders;
 
-// The only thing that *may* be used outside of this crate is the NeuronEncoderVariantType enum.
-// The encoder logic is internal to this crate and nothing needs to know the details of the
-// encoders themselves, just their trait (which is spawned by the methods of NeuronEncoderVariantType)
+// The built-in coders themselves are internal to this crate, and are only reachable through the
+// NeuronCoderRegistry. The traits are public so that users can write and register their own coders.
 
 pub use coder_types::{NeuronCoderVariantType};
-pub(crate) use coder_traits::{NeuronXYZPEncoder};
\ No newline at end of file
+pub use coder_traits::{NeuronXYZPEncoder, NeuronXYZPDecoder};
+pub use coder_registry::{NeuronCoderID, NeuronCoderParameters, NeuronCoderRegistry, NeuronXYZPEncoderConstructor, NeuronXYZPDecoderConstructor};
@@ -23,6 +23,10 @@
 //!
 //! The structure supports efficient binary serialization for network transmission
 //! and persistent storage, implementing the [`FeagiByteStructureCompatible`] trait.
+//! A compressed format can be chosen per serialization with
+//! `as_new_feagi_byte_structure_compressed()`; both formats deserialize through
+//! `new_from_feagi_byte_structure()`. To read the uncompressed format without copying,
+//! see [`CorticalMappedXYZPNeuronDataView`].
 //!
 //! # Performance Considerations
 //!
@@ -36,7 +40,7 @@ use byteorder::{ByteOrder, LittleEndian};
 use crate::error::{FeagiBytesError, FeagiDataProcessingError};
 use crate::io_processing::byte_structures::{FeagiByteStructureType, FeagiByteStructure, FeagiByteStructureCompatible};
 use crate::genomic_structures::{CorticalID};
-use crate::neuron_data::xyzp::{NeuronXYZP, NeuronXYZPArrays};
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronDataView, NeuronXYZPArrays};
 
 /// Collection of neuron data organized by cortical areas.
 ///
@@ -119,81 +123,14 @@ impl FeagiByteStructureCompatible for CorticalMappedXYZPNeuronData {
     }
 
     fn new_from_feagi_byte_structure(feagi_byte_structure: &FeagiByteStructure) -> Result<Self, FeagiDataProcessingError> {
-        FeagiByteStructure::verify_matching_structure_type_and_version(&feagi_byte_structure,
-                                                   Self::BYTE_STRUCT_TYPE,
-                                                   Self::BYTE_STRUCT_VERSION)?;
-        
-        let bytes = feagi_byte_structure.borrow_data_as_slice();
-        let number_cortical_areas: u16 = LittleEndian::read_u16(&bytes[2..4]);
-
-        let min_array_length_with_cortical_headers: usize = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES +  Self::NUMBER_BYTES_CORTICAL_COUNT_HEADER +
-            (Self::NUMBER_BYTES_PER_CORTICAL_ID_HEADER * number_cortical_areas as usize);
-
-        if bytes.len() < min_array_length_with_cortical_headers {
-            return Err(FeagiBytesError::UnableToSerializeBytes(format!("Byte structure for NeuronCategoricalXYZPV1 needs a length of {} to fit just the cortical details header, but is a length of {}",
-                                                                         min_array_length_with_cortical_headers, bytes.len())).into());
+        if feagi_byte_structure.try_get_structure_type()? == FeagiByteStructureType::NeuronCategoricalXYZPCompressed {
+            FeagiByteStructure::verify_matching_structure_type_and_version(feagi_byte_structure,
+                                                       FeagiByteStructureType::NeuronCategoricalXYZPCompressed,
+                                                       FeagiByteStructure::SUPPORTED_VERSION_NEURON_XYZP_COMPRESSED)?;
+            return Self::new_from_compressed_bytes(feagi_byte_structure.borrow_data_as_slice());
         }
-
-        let number_cortical_areas: usize = number_cortical_areas as usize;
-        let mut output: CorticalMappedXYZPNeuronData = CorticalMappedXYZPNeuronData::new_with_capacity(number_cortical_areas);
-        
-        let mut reading_header_index: usize = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + Self::NUMBER_BYTES_CORTICAL_COUNT_HEADER;
-
-        for _cortical_index in 0..number_cortical_areas {
-            let cortical_id = CorticalID::from_bytes(
-                <&[u8; 6]>::try_from(&bytes[reading_header_index..reading_header_index + 6]).unwrap()
-            )?;
-            let data_start_reading: usize = LittleEndian::read_u32(&bytes[reading_header_index + 6..reading_header_index + 10]) as usize;
-            let number_bytes_to_read: usize = LittleEndian::read_u32(&bytes[reading_header_index + 10..reading_header_index + 14]) as usize;
-
-            if bytes.len() < data_start_reading + number_bytes_to_read {
-                return Err(FeagiBytesError::UnableToSerializeBytes("Byte structure for NeuronCategoricalXYZPV1 is too short to fit the data the header says it contains!".into()).into());
-            }
-
-            let neuron_bytes = &bytes[data_start_reading..data_start_reading + number_bytes_to_read];
-            let bytes_length = neuron_bytes.len();
-            if bytes_length % NeuronXYZP::NUMBER_BYTES_PER_NEURON != 0 {
-                return Err(FeagiBytesError::UnableToSerializeBytes("Byte structure for NeuronCategoricalXYZPV1 seems invalid! Size is nonsensical given neuron data size!".into()).into());
-            }
-            
-            if bytes_length % NeuronXYZP::NUMBER_BYTES_PER_NEURON != 0 {
-                return Err(FeagiBytesError::UnableToSerializeBytes("As NeuronXYCPArrays contains 4 internal arrays of equal length, each of elements of 4 bytes each (uint32 and float), the input byte array must be divisible by 16!".into()).into());
-            }
-            let x_end = bytes_length / 4; // q1
-            let y_end = bytes_length / 2; // q2
-            let z_end = x_end * 3; // q3
-
-            // Create vectors using byte order to avoid alignment issues
-            let num_neurons = bytes_length / NeuronXYZP::NUMBER_BYTES_PER_NEURON;
-            let mut x_coords: Vec<u32> = Vec::with_capacity(num_neurons);
-            let mut y_coords: Vec<u32> = Vec::with_capacity(num_neurons);
-            let mut z_coords: Vec<u32> = Vec::with_capacity(num_neurons);
-            let mut potentials: Vec<f32> = Vec::with_capacity(num_neurons);
-            
-            for i in 0..num_neurons {
-                let x_start = i * 4;
-                let y_start = x_end + x_start;
-                let z_start = y_end + x_start;
-                let p_start = z_end + x_start;
-
-                x_coords.push(LittleEndian::read_u32(&neuron_bytes[x_start..x_start + 4]));
-                y_coords.push(LittleEndian::read_u32(&neuron_bytes[y_start..y_start + 4]));
-                z_coords.push(LittleEndian::read_u32(&neuron_bytes[z_start..z_start + 4]));
-                potentials.push(LittleEndian::read_f32(&neuron_bytes[p_start..p_start + 4]));
-            }
-
-            let neurons = NeuronXYZPArrays::new_from_vectors(
-                x_coords,
-                y_coords,
-                z_coords,
-                potentials,
-            )?;
-
-            output.insert(cortical_id, neurons);
-            reading_header_index += Self::NUMBER_BYTES_PER_CORTICAL_ID_HEADER;
-        };
         
-        Ok(output)
+        CorticalMappedXYZPNeuronDataView::new(feagi_byte_structure)?.to_cortical_mapped_xyzp_neuron_data()
     }
 }
 
@@ -273,13 +210,13 @@ impl std::fmt::Display for CorticalMappedXYZPNeuronData {
 
 impl CorticalMappedXYZPNeuronData {
     /// Binary structure type identifier for serialization.
-    const BYTE_STRUCT_TYPE: FeagiByteStructureType = FeagiByteStructureType::NeuronCategoricalXYZP;
+    pub(crate) const BYTE_STRUCT_TYPE: FeagiByteStructureType = FeagiByteStructureType::NeuronCategoricalXYZP;
     /// Binary structure version for compatibility checking.
-    const BYTE_STRUCT_VERSION: u8 = 1;
+    pub(crate) const BYTE_STRUCT_VERSION: u8 = 1;
     /// Size in bytes of each cortical area header in binary format.
-    const NUMBER_BYTES_PER_CORTICAL_ID_HEADER: usize = CorticalID::NUMBER_OF_BYTES + size_of::<u32>() + size_of::<u32>();
+    pub(crate) const NUMBER_BYTES_PER_CORTICAL_ID_HEADER: usize = CorticalID::NUMBER_OF_BYTES + size_of::<u32>() + size_of::<u32>();
     /// Size in bytes of the cortical count field in binary format.
-    const NUMBER_BYTES_CORTICAL_COUNT_HEADER: usize = size_of::<u16>();
+    pub(crate) const NUMBER_BYTES_CORTICAL_COUNT_HEADER: usize = size_of::<u16>();
     
     /// Creates a new empty neuron data collection.
     ///
@@ -0,0 +1,407 @@
+//! Compressed binary format for [`CorticalMappedXYZPNeuronData`].
+//!
+//! The uncompressed `NeuronCategoricalXYZP` format stores every neuron as four raw 4 byte
+//! values. For large, dense areas such as camera frames this is wasteful, as neighbouring
+//! neurons share most of their coordinates and often have identical potentials.
+//!
+//! The `NeuronCategoricalXYZPCompressed` format instead:
+//! - Sorts the neurons of each cortical area by (X, Y, Z)
+//! - Stores each coordinate as a varint delta from the previous neuron where possible
+//! - Stores a single potential for areas where all neurons share one, otherwise stores the
+//!   potentials as raw f32, or quantized to u16 / u8 over the range of the area
+//!
+//! Deserializing either format returns a [`CorticalMappedXYZPNeuronData`], but the neurons of
+//! each cortical area come back sorted by (X, Y, Z) rather than in their original order.
+//!
+//! # Binary Format
+//!
+//! ```text
+//! [Type (1 byte)][Version (1 byte)][Cortical Count (u16)]
+//! Per cortical area:
+//!     [Cortical ID (6 bytes)][Neuron Count (u32)][Coordinate Byte Length (u32)]
+//!     [Potential Encoding (u8)][Potential Parameters (0, 4, or 8 bytes)]
+//!     [Coordinate Bytes][Potential Bytes]
+//! ```
+//!
+//! Coordinates are written per neuron as varints. X is always a delta from the previous
+//! neuron. Y is a delta if X did not change, and absolute otherwise. Z is a delta if neither
+//! X nor Y changed, and absolute otherwise.
+
+use std::mem::size_of;
+use byteorder::{ByteOrder, LittleEndian};
+use crate::error::{FeagiBytesError, FeagiDataProcessingError};
+use crate::genomic_structures::CorticalID;
+use crate::io_processing::byte_structures::{FeagiByteStructure, FeagiByteStructureType};
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPArrays};
+
+/// How potentials are stored in the compressed neuron format.
+///
+/// Areas where every neuron shares one potential are always stored as that single value,
+/// regardless of the quantization chosen. Areas containing non-finite potentials are always
+/// stored as raw f32.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NeuronXYZPPotentialQuantization {
+    /// Potentials are stored losslessly as 4 byte floats.
+    Float32,
+    /// Potentials are quantized to 65536 steps between the minimum and maximum of the area.
+    U16,
+    /// Potentials are quantized to 256 steps between the minimum and maximum of the area.
+    U8,
+}
+
+impl std::fmt::Display for NeuronXYZPPotentialQuantization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Float32 => "Float32",
+            Self::U16 => "U16",
+            Self::U8 => "U8",
+        };
+        write!(f, "NeuronXYZPPotentialQuantization({})", name)
+    }
+}
+
+/// Encoding of the potentials of a single cortical area, as written in its header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PotentialEncoding {
+    Constant(f32),
+    Float32,
+    U16 { min: f32, max: f32 },
+    U8 { min: f32, max: f32 },
+}
+
+impl PotentialEncoding {
+    const ID_CONSTANT: u8 = 0;
+    const ID_FLOAT32: u8 = 1;
+    const ID_U16: u8 = 2;
+    const ID_U8: u8 = 3;
+
+    fn choose(potentials: &[f32], quantization: NeuronXYZPPotentialQuantization) -> Self {
+        let first = match potentials.first() {
+            Some(first) => *first,
+            None => return PotentialEncoding::Constant(0.0),
+        };
+        if potentials.iter().all(|p| p.to_bits() == first.to_bits()) {
+            return PotentialEncoding::Constant(first);
+        }
+        if quantization == NeuronXYZPPotentialQuantization::Float32 || potentials.iter().any(|p| !p.is_finite()) {
+            return PotentialEncoding::Float32;
+        }
+        let (min, max) = potentials.iter().fold((f32::MAX, f32::MIN), |(min, max), p| (min.min(*p), max.max(*p)));
+        if !(max - min).is_finite() {
+            return PotentialEncoding::Float32;
+        }
+        match quantization {
+            NeuronXYZPPotentialQuantization::U16 => PotentialEncoding::U16 { min, max },
+            _ => PotentialEncoding::U8 { min, max },
+        }
+    }
+
+    fn id(&self) -> u8 {
+        match self {
+            Self::Constant(_) => Self::ID_CONSTANT,
+            Self::Float32 => Self::ID_FLOAT32,
+            Self::U16 { .. } => Self::ID_U16,
+            Self::U8 { .. } => Self::ID_U8,
+        }
+    }
+
+    fn number_parameter_bytes(&self) -> usize {
+        match self {
+            Self::Constant(_) => size_of::<f32>(),
+            Self::Float32 => 0,
+            Self::U16 { .. } | Self::U8 { .. } => size_of::<f32>() * 2,
+        }
+    }
+
+    fn number_bytes_per_potential(&self) -> usize {
+        match self {
+            Self::Constant(_) => 0,
+            Self::Float32 => size_of::<f32>(),
+            Self::U16 { .. } => size_of::<u16>(),
+            Self::U8 { .. } => size_of::<u8>(),
+        }
+    }
+
+    fn write_parameters(&self, slice: &mut [u8]) {
+        match self {
+            Self::Constant(value) => LittleEndian::write_f32(&mut slice[0..4], *value),
+            Self::Float32 => {},
+            Self::U16 { min, max } | Self::U8 { min, max } => {
+                LittleEndian::write_f32(&mut slice[0..4], *min);
+                LittleEndian::write_f32(&mut slice[4..8], *max);
+            }
+        }
+    }
+
+    fn read(id: u8, bytes: &[u8], index: &mut usize) -> Result<Self, FeagiBytesError> {
+        let number_parameter_bytes = match id {
+            Self::ID_CONSTANT => size_of::<f32>(),
+            Self::ID_FLOAT32 => 0,
+            Self::ID_U16 | Self::ID_U8 => size_of::<f32>() * 2,
+            _ => return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Unknown potential encoding {} in compressed neuron data!", id))),
+        };
+        let parameters = take_bytes(bytes, index, number_parameter_bytes)?;
+        Ok(match id {
+            Self::ID_CONSTANT => Self::Constant(LittleEndian::read_f32(&parameters[0..4])),
+            Self::ID_FLOAT32 => Self::Float32,
+            Self::ID_U16 => Self::U16 { min: LittleEndian::read_f32(&parameters[0..4]), max: LittleEndian::read_f32(&parameters[4..8]) },
+            _ => Self::U8 { min: LittleEndian::read_f32(&parameters[0..4]), max: LittleEndian::read_f32(&parameters[4..8]) },
+        })
+    }
+}
+
+impl CorticalMappedXYZPNeuronData {
+    /// Binary structure type identifier for compressed serialization.
+    const COMPRESSED_BYTE_STRUCT_TYPE: FeagiByteStructureType = FeagiByteStructureType::NeuronCategoricalXYZPCompressed;
+    /// Binary structure version for compressed serialization.
+    const COMPRESSED_BYTE_STRUCT_VERSION: u8 = FeagiByteStructure::SUPPORTED_VERSION_NEURON_XYZP_COMPRESSED;
+    /// Size in bytes of each cortical area header in the compressed format, excluding potential parameters.
+    const NUMBER_BYTES_PER_COMPRESSED_CORTICAL_HEADER: usize = CorticalID::NUMBER_OF_BYTES + size_of::<u32>() + size_of::<u32>() + size_of::<u8>();
+    /// Largest number of bytes a u32 varint can occupy.
+    const MAX_NUMBER_BYTES_PER_VARINT: usize = 5;
+
+    /// Serializes this neuron data into a new byte structure using the compressed format.
+    ///
+    /// This is the compressed counterpart to `as_new_feagi_byte_structure`, and can be chosen
+    /// per serialization. The result deserializes back through `new_from_feagi_byte_structure`.
+    ///
+    /// # Arguments
+    /// * `potential_quantization` - How non-constant potentials should be stored
+    ///
+    /// # Returns
+    /// * `Ok(FeagiByteStructure)` - The compressed byte structure, trimmed to its actual length
+    /// * `Err(FeagiDataProcessingError)` - If there is no data to serialize
+    ///
+    /// # Example
+    /// ```rust
+    /// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZP, NeuronXYZPArrays, NeuronXYZPPotentialQuantization};
+    /// use feagi_core_data_structures_and_processing::genomic_structures::CorticalID;
+    /// use feagi_core_data_structures_and_processing::io_processing::byte_structures::FeagiByteStructureCompatible;
+    ///
+    /// let mut neurons = NeuronXYZPArrays::with_capacity(2);
+    /// neurons.push(&NeuronXYZP::new(1, 2, 0, 1.0));
+    /// neurons.push(&NeuronXYZP::new(1, 3, 0, 1.0));
+    /// let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    /// neuron_data.insert(CorticalID::from_string("iic400".to_string()).unwrap(), neurons);
+    ///
+    /// let bytes = neuron_data.as_new_feagi_byte_structure_compressed(NeuronXYZPPotentialQuantization::U8).unwrap();
+    /// let decoded = CorticalMappedXYZPNeuronData::new_from_feagi_byte_structure(&bytes).unwrap();
+    /// assert_eq!(decoded.iter().next().unwrap().len(), 2);
+    /// ```
+    pub fn as_new_feagi_byte_structure_compressed(&self, potential_quantization: NeuronXYZPPotentialQuantization) -> Result<FeagiByteStructure, FeagiDataProcessingError> {
+        let mut bytes: Vec<u8> = vec![0; self.max_number_bytes_needed_compressed()];
+        let wasted_space = self.overwrite_feagi_byte_structure_slice_compressed(&mut bytes, potential_quantization)?;
+        bytes.truncate(bytes.len() - wasted_space);
+        FeagiByteStructure::create_from_bytes(bytes)
+    }
+
+    /// Writes this neuron data into the given slice using the compressed format.
+    ///
+    /// # Arguments
+    /// * `slice` - Target to write to, must be at least `max_number_bytes_needed_compressed` long
+    /// * `potential_quantization` - How non-constant potentials should be stored
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - The number of bytes at the end of the slice that were not written to
+    /// * `Err(FeagiDataProcessingError)` - If there is no data, or the slice is too small
+    pub fn overwrite_feagi_byte_structure_slice_compressed(&self, slice: &mut [u8], potential_quantization: NeuronXYZPPotentialQuantization) -> Result<usize, FeagiDataProcessingError> {
+        if self.mappings.is_empty() {
+            return Err(FeagiBytesError::UnableToSerializeBytes("Cannot generate a byte structure export with an empty cortical mappings object!".into()).into())
+        }
+        let num_bytes_needed: usize = self.max_number_bytes_needed_compressed();
+        if slice.len() < num_bytes_needed {
+            return Err(FeagiBytesError::UnableToSerializeBytes(format!("Not enough space given to store compressed neuron XYZP data! Need {} bytes but given {}!", num_bytes_needed, slice.len())).into());
+        }
+
+        slice[0] = Self::COMPRESSED_BYTE_STRUCT_TYPE as u8;
+        slice[1] = Self::COMPRESSED_BYTE_STRUCT_VERSION;
+        LittleEndian::write_u16(&mut slice[2..4], self.mappings.len() as u16);
+
+        let mut write_index: usize = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + Self::NUMBER_BYTES_CORTICAL_COUNT_HEADER;
+        for (cortical_id, neuron_data) in &self.mappings {
+            write_index = write_compressed_cortical_area(cortical_id, neuron_data, potential_quantization, slice, write_index)?;
+        }
+        Ok(slice.len() - write_index)
+    }
+
+    /// Returns an upper bound of the number of bytes the compressed format may need.
+    ///
+    /// The actual size is usually far smaller, since the exact size is only known once the
+    /// neurons have been sorted and encoded.
+    pub fn max_number_bytes_needed_compressed(&self) -> usize {
+        let mut number_bytes_needed: usize = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + Self::NUMBER_BYTES_CORTICAL_COUNT_HEADER;
+        for neuron_data in self.iter() {
+            number_bytes_needed += Self::NUMBER_BYTES_PER_COMPRESSED_CORTICAL_HEADER + (size_of::<f32>() * 2)
+                + neuron_data.len() * ((Self::MAX_NUMBER_BYTES_PER_VARINT * 3) + size_of::<f32>());
+        }
+        number_bytes_needed
+    }
+
+    /// Reads neuron data from the bytes of a compressed byte structure, whose type and version
+    /// have already been verified.
+    pub(crate) fn new_from_compressed_bytes(bytes: &[u8]) -> Result<Self, FeagiDataProcessingError> {
+        let mut read_index: usize = FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES;
+        let number_cortical_areas = LittleEndian::read_u16(take_bytes(bytes, &mut read_index, Self::NUMBER_BYTES_CORTICAL_COUNT_HEADER)?) as usize;
+        let mut output = CorticalMappedXYZPNeuronData::new_with_capacity(number_cortical_areas);
+
+        for _cortical_index in 0..number_cortical_areas {
+            let header = take_bytes(bytes, &mut read_index, Self::NUMBER_BYTES_PER_COMPRESSED_CORTICAL_HEADER)?;
+            let cortical_id = CorticalID::from_bytes(<&[u8; 6]>::try_from(&header[0..6]).unwrap())?;
+            let number_neurons = LittleEndian::read_u32(&header[6..10]) as usize;
+            let number_coordinate_bytes = LittleEndian::read_u32(&header[10..14]) as usize;
+            let potential_encoding = PotentialEncoding::read(header[14], bytes, &mut read_index)?;
+
+            let coordinate_bytes = take_bytes(bytes, &mut read_index, number_coordinate_bytes)?;
+            // Every neuron needs at least 1 byte per coordinate, which also bounds our allocations by the input size
+            if number_neurons * 3 > coordinate_bytes.len() {
+                return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Compressed neuron data claims {} neurons but only has {} bytes of coordinates!", number_neurons, coordinate_bytes.len())).into());
+            }
+            let (x, y, z) = read_coordinates(coordinate_bytes, number_neurons)?;
+
+            let potential_bytes = take_bytes(bytes, &mut read_index, number_neurons * potential_encoding.number_bytes_per_potential())?;
+            let p = read_potentials(potential_bytes, number_neurons, potential_encoding);
+
+            _ = output.insert(cortical_id, NeuronXYZPArrays::new_from_vectors(x, y, z, p)?);
+        }
+        Ok(output)
+    }
+}
+
+//region helpers
+
+fn write_compressed_cortical_area(cortical_id: &CorticalID, neuron_data: &NeuronXYZPArrays, potential_quantization: NeuronXYZPPotentialQuantization,
+                                  slice: &mut [u8], start_index: usize) -> Result<usize, FeagiDataProcessingError> {
+    let (x, y, z, p) = neuron_data.borrow_xyzp_vectors();
+    let mut order: Vec<usize> = (0..x.len()).collect();
+    order.sort_unstable_by_key(|i| (x[*i], y[*i], z[*i]));
+
+    let sorted_potentials: Vec<f32> = order.iter().map(|i| p[*i]).collect();
+    let potential_encoding = PotentialEncoding::choose(&sorted_potentials, potential_quantization);
+
+    // Header, coordinate length is filled in once known
+    let header_index = start_index;
+    cortical_id.write_bytes_at(<&mut [u8; 6]>::try_from(&mut slice[header_index..header_index + 6]).unwrap())?;
+    LittleEndian::write_u32(&mut slice[header_index + 6..header_index + 10], order.len() as u32);
+    slice[header_index + 14] = potential_encoding.id();
+    let parameter_index = header_index + CorticalMappedXYZPNeuronData::NUMBER_BYTES_PER_COMPRESSED_CORTICAL_HEADER;
+    potential_encoding.write_parameters(&mut slice[parameter_index..]);
+
+    let coordinate_start = parameter_index + potential_encoding.number_parameter_bytes();
+    let mut write_index = coordinate_start;
+    let (mut previous_x, mut previous_y, mut previous_z) = (0u32, 0u32, 0u32);
+    for i in &order {
+        let (current_x, current_y, current_z) = (x[*i], y[*i], z[*i]);
+        write_varint(slice, &mut write_index, current_x - previous_x);
+        if current_x != previous_x {
+            write_varint(slice, &mut write_index, current_y);
+            write_varint(slice, &mut write_index, current_z);
+        } else if current_y != previous_y {
+            write_varint(slice, &mut write_index, current_y - previous_y);
+            write_varint(slice, &mut write_index, current_z);
+        } else {
+            write_varint(slice, &mut write_index, 0);
+            write_varint(slice, &mut write_index, current_z - previous_z);
+        }
+        (previous_x, previous_y, previous_z) = (current_x, current_y, current_z);
+    }
+    LittleEndian::write_u32(&mut slice[header_index + 10..header_index + 14], (write_index - coordinate_start) as u32);
+
+    for potential in &sorted_potentials {
+        match potential_encoding {
+            PotentialEncoding::Constant(_) => break,
+            PotentialEncoding::Float32 => LittleEndian::write_f32(&mut slice[write_index..write_index + 4], *potential),
+            PotentialEncoding::U16 { min, max } => LittleEndian::write_u16(&mut slice[write_index..write_index + 2], quantize(*potential, min, max, u16::MAX as f32) as u16),
+            PotentialEncoding::U8 { min, max } => slice[write_index] = quantize(*potential, min, max, u8::MAX as f32) as u8,
+        }
+        write_index += potential_encoding.number_bytes_per_potential();
+    }
+    Ok(write_index)
+}
+
+/// Separate X, Y, and Z coordinate vectors of equal length.
+type CoordinateVectors = (Vec<u32>, Vec<u32>, Vec<u32>);
+
+fn read_coordinates(bytes: &[u8], number_neurons: usize) -> Result<CoordinateVectors, FeagiBytesError> {
+    let mut x: Vec<u32> = Vec::with_capacity(number_neurons);
+    let mut y: Vec<u32> = Vec::with_capacity(number_neurons);
+    let mut z: Vec<u32> = Vec::with_capacity(number_neurons);
+    let overflow = || FeagiBytesError::UnableToDeserializeBytes("Compressed neuron coordinates overflow a u32!".into());
+
+    let mut read_index: usize = 0;
+    let (mut previous_x, mut previous_y, mut previous_z) = (0u32, 0u32, 0u32);
+    for _ in 0..number_neurons {
+        let delta_x = read_varint(bytes, &mut read_index)?;
+        let second = read_varint(bytes, &mut read_index)?;
+        let third = read_varint(bytes, &mut read_index)?;
+        let (current_x, current_y, current_z) = if delta_x != 0 {
+            (previous_x.checked_add(delta_x).ok_or_else(overflow)?, second, third)
+        } else if second != 0 {
+            (previous_x, previous_y.checked_add(second).ok_or_else(overflow)?, third)
+        } else {
+            (previous_x, previous_y, previous_z.checked_add(third).ok_or_else(overflow)?)
+        };
+        x.push(current_x);
+        y.push(current_y);
+        z.push(current_z);
+        (previous_x, previous_y, previous_z) = (current_x, current_y, current_z);
+    }
+    if read_index != bytes.len() {
+        return Err(FeagiBytesError::UnableToDeserializeBytes("Compressed neuron coordinates contain trailing bytes!".into()));
+    }
+    Ok((x, y, z))
+}
+
+fn read_potentials(bytes: &[u8], number_neurons: usize, potential_encoding: PotentialEncoding) -> Vec<f32> {
+    match potential_encoding {
+        PotentialEncoding::Constant(value) => vec![value; number_neurons],
+        PotentialEncoding::Float32 => bytes.chunks_exact(4).map(LittleEndian::read_f32).collect(),
+        PotentialEncoding::U16 { min, max } => bytes.chunks_exact(2).map(|b| dequantize(LittleEndian::read_u16(b) as f32, min, max, u16::MAX as f32)).collect(),
+        PotentialEncoding::U8 { min, max } => bytes.iter().map(|b| dequantize(*b as f32, min, max, u8::MAX as f32)).collect(),
+    }
+}
+
+fn quantize(value: f32, min: f32, max: f32, steps: f32) -> f32 {
+    (((value - min) / (max - min)) * steps).round().clamp(0.0, steps)
+}
+
+fn dequantize(value: f32, min: f32, max: f32, steps: f32) -> f32 {
+    min + ((value / steps) * (max - min))
+}
+
+fn write_varint(slice: &mut [u8], write_index: &mut usize, mut value: u32) {
+    while value >= 0x80 {
+        slice[*write_index] = (value as u8) | 0x80;
+        value >>= 7;
+        *write_index += 1;
+    }
+    slice[*write_index] = value as u8;
+    *write_index += 1;
+}
+
+fn read_varint(bytes: &[u8], read_index: &mut usize) -> Result<u32, FeagiBytesError> {
+    let mut value: u32 = 0;
+    for byte_number in 0..CorticalMappedXYZPNeuronData::MAX_NUMBER_BYTES_PER_VARINT {
+        let byte = *bytes.get(*read_index)
+            .ok_or_else(|| FeagiBytesError::UnableToDeserializeBytes("Compressed neuron coordinates end in the middle of a value!".into()))?;
+        *read_index += 1;
+        let shift = byte_number * 7;
+        if shift == 28 && byte > 0x0F {
+            return Err(FeagiBytesError::UnableToDeserializeBytes("Compressed neuron coordinates overflow a u32!".into()));
+        }
+        value |= ((byte & 0x7F) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(FeagiBytesError::UnableToDeserializeBytes("Compressed neuron coordinates overflow a u32!".into()))
+}
+
+fn take_bytes<'a>(bytes: &'a [u8], read_index: &mut usize, number_bytes: usize) -> Result<&'a [u8], FeagiBytesError> {
+    let end = read_index.checked_add(number_bytes).filter(|end| *end <= bytes.len())
+        .ok_or_else(|| FeagiBytesError::UnableToDeserializeBytes("Compressed neuron data is too short to fit the data its headers say it contains!".into()))?;
+    let taken = &bytes[*read_index..end];
+    *read_index = end;
+    Ok(taken)
+}
+
+//endregion
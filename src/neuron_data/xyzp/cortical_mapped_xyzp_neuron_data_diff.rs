@@ -0,0 +1,245 @@
+//! Structural diffs between two neuron bursts.
+//!
+//! Compares a "before" and "after" burst, such as those produced by two agent versions from
+//! the same input, reporting cortical areas added or removed, neurons appearing or disappearing,
+//! and potentials changing by more than an epsilon. The `Display` implementations render a
+//! readable report, which also makes diffs useful in golden tests of encoder output.
+//!
+//! Neurons are matched by their (x, y, z) coordinate. If a burst lists a coordinate more than
+//! once, its last potential is used. Reports are ordered by cortical ID bytes and then by
+//! coordinate, so equal inputs always give equal reports.
+
+use std::collections::HashMap;
+use crate::genomic_structures::CorticalID;
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZP, NeuronXYZPArrays};
+
+type Coordinate = (u32, u32, u32);
+
+//region NeuronXYZPArraysDiff
+
+/// Differences between the neurons of one cortical area in two bursts.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NeuronXYZPArraysDiff {
+    appeared: Vec<NeuronXYZP>,
+    disappeared: Vec<NeuronXYZP>,
+    potential_changes: Vec<(NeuronXYZP, NeuronXYZP)>,
+}
+
+impl std::fmt::Display for NeuronXYZPArraysDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} appeared, {} disappeared, {} potentials changed", self.appeared.len(), self.disappeared.len(), self.potential_changes.len())?;
+        for neuron in &self.appeared {
+            write!(f, "\n    + ({}, {}, {}) p={}", neuron.x, neuron.y, neuron.z, neuron.p)?;
+        }
+        for neuron in &self.disappeared {
+            write!(f, "\n    - ({}, {}, {}) p={}", neuron.x, neuron.y, neuron.z, neuron.p)?;
+        }
+        for (before, after) in &self.potential_changes {
+            write!(f, "\n    ~ ({}, {}, {}) p={} -> {}", before.x, before.y, before.z, before.p, after.p)?;
+        }
+        Ok(())
+    }
+}
+
+impl NeuronXYZPArraysDiff {
+
+    /// Returns true if both bursts hold the same neurons, with potentials within epsilon.
+    pub fn is_empty(&self) -> bool {
+        self.appeared.is_empty() && self.disappeared.is_empty() && self.potential_changes.is_empty()
+    }
+
+    /// Returns the neurons only present in the "after" burst, ordered by coordinate.
+    pub fn get_appeared(&self) -> &[NeuronXYZP] {
+        &self.appeared
+    }
+
+    /// Returns the neurons only present in the "before" burst, ordered by coordinate.
+    pub fn get_disappeared(&self) -> &[NeuronXYZP] {
+        &self.disappeared
+    }
+
+    /// Returns the (before, after) pairs of neurons present in both bursts whose potential
+    /// changed by more than epsilon, ordered by coordinate.
+    pub fn get_potential_changes(&self) -> &[(NeuronXYZP, NeuronXYZP)] {
+        &self.potential_changes
+    }
+}
+
+impl NeuronXYZPArrays {
+
+    /// Compares these neurons ("before") against another set ("after").
+    ///
+    /// # Arguments
+    /// * `after` - The neurons to compare against
+    /// * `epsilon` - Potentials differing by this much or less are considered equal. A NaN
+    ///   potential only equals another NaN
+    ///
+    /// # Examples
+    /// ```
+    /// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{NeuronXYZP, NeuronXYZPArrays};
+    ///
+    /// let before = NeuronXYZPArrays::new_from_vectors(vec![0, 1, 2], vec![0, 0, 0], vec![0, 0, 0], vec![0.5, 0.5, 0.5]).unwrap();
+    /// let after = NeuronXYZPArrays::new_from_vectors(vec![1, 2, 3], vec![0, 0, 0], vec![0, 0, 0], vec![0.5001, 0.9, 0.5]).unwrap();
+    ///
+    /// let diff = before.diff(&after, 0.001);
+    /// assert_eq!(diff.get_appeared(), &[NeuronXYZP::new(3, 0, 0, 0.5)]);
+    /// assert_eq!(diff.get_disappeared(), &[NeuronXYZP::new(0, 0, 0, 0.5)]);
+    /// assert_eq!(diff.get_potential_changes(), &[(NeuronXYZP::new(2, 0, 0, 0.5), NeuronXYZP::new(2, 0, 0, 0.9))]);
+    /// ```
+    pub fn diff(&self, after: &NeuronXYZPArrays, epsilon: f32) -> NeuronXYZPArraysDiff {
+        let before_potentials = potentials_by_coordinate(self);
+        let after_potentials = potentials_by_coordinate(after);
+        let mut diff = NeuronXYZPArraysDiff::default();
+
+        for (&coordinate, &before_potential) in &before_potentials {
+            match after_potentials.get(&coordinate) {
+                None => diff.disappeared.push(neuron_at(coordinate, before_potential)),
+                Some(&after_potential) => {
+                    if !potentials_equal(before_potential, after_potential, epsilon) {
+                        diff.potential_changes.push((neuron_at(coordinate, before_potential), neuron_at(coordinate, after_potential)));
+                    }
+                }
+            }
+        }
+        for (&coordinate, &after_potential) in &after_potentials {
+            if !before_potentials.contains_key(&coordinate) {
+                diff.appeared.push(neuron_at(coordinate, after_potential));
+            }
+        }
+
+        diff.appeared.sort_by_key(|neuron| (neuron.x, neuron.y, neuron.z));
+        diff.disappeared.sort_by_key(|neuron| (neuron.x, neuron.y, neuron.z));
+        diff.potential_changes.sort_by_key(|(neuron, _)| (neuron.x, neuron.y, neuron.z));
+        diff
+    }
+}
+
+fn potentials_by_coordinate(neurons: &NeuronXYZPArrays) -> HashMap<Coordinate, f32> {
+    let (x, y, z, p) = neurons.borrow_xyzp_vectors();
+    let mut potentials: HashMap<Coordinate, f32> = HashMap::with_capacity(neurons.len());
+    for i in 0..neurons.len() {
+        potentials.insert((x[i], y[i], z[i]), p[i]);
+    }
+    potentials
+}
+
+fn potentials_equal(before: f32, after: f32, epsilon: f32) -> bool {
+    if before.is_nan() || after.is_nan() {
+        return before.is_nan() && after.is_nan();
+    }
+    (before - after).abs() <= epsilon
+}
+
+fn neuron_at(coordinate: Coordinate, potential: f32) -> NeuronXYZP {
+    NeuronXYZP::new(coordinate.0, coordinate.1, coordinate.2, potential)
+}
+
+//endregion
+
+//region CorticalMappedXYZPNeuronDataDiff
+
+/// Differences between two bursts of neuron data across all cortical areas.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CorticalMappedXYZPNeuronDataDiff {
+    added_areas: Vec<CorticalID>,
+    removed_areas: Vec<CorticalID>,
+    area_diffs: Vec<(CorticalID, NeuronXYZPArraysDiff)>,
+}
+
+impl std::fmt::Display for CorticalMappedXYZPNeuronDataDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "CorticalMappedXYZPNeuronDataDiff(no differences)");
+        }
+        write!(f, "CorticalMappedXYZPNeuronDataDiff({} areas added, {} removed, {} differing)", self.added_areas.len(), self.removed_areas.len(), self.area_diffs.len())?;
+        for (cortical_id, area_diff) in &self.area_diffs {
+            let marker = if self.added_areas.contains(cortical_id) {
+                "+"
+            } else if self.removed_areas.contains(cortical_id) {
+                "-"
+            } else {
+                "~"
+            };
+            write!(f, "\n{} {}: {}", marker, cortical_id, area_diff)?;
+        }
+        Ok(())
+    }
+}
+
+impl CorticalMappedXYZPNeuronDataDiff {
+
+    /// Returns true if both bursts have the same cortical areas, neurons, and potentials within epsilon.
+    pub fn is_empty(&self) -> bool {
+        self.area_diffs.is_empty()
+    }
+
+    /// Returns the cortical areas only present in the "after" burst.
+    pub fn get_added_areas(&self) -> &[CorticalID] {
+        &self.added_areas
+    }
+
+    /// Returns the cortical areas only present in the "before" burst.
+    pub fn get_removed_areas(&self) -> &[CorticalID] {
+        &self.removed_areas
+    }
+
+    /// Returns the neuron differences of every cortical area that differs, including added areas
+    /// (all neurons appeared) and removed areas (all neurons disappeared).
+    pub fn get_area_diffs(&self) -> &[(CorticalID, NeuronXYZPArraysDiff)] {
+        &self.area_diffs
+    }
+
+    /// Returns the neuron differences of the given cortical area, or None if it does not differ.
+    pub fn get_area_diff(&self, cortical_id: &CorticalID) -> Option<&NeuronXYZPArraysDiff> {
+        self.area_diffs.iter().find(|(id, _)| id == cortical_id).map(|(_, area_diff)| area_diff)
+    }
+}
+
+impl CorticalMappedXYZPNeuronData {
+
+    /// Compares this burst ("before") against another ("after").
+    ///
+    /// # Arguments
+    /// * `after` - The burst to compare against
+    /// * `epsilon` - Potentials differing by this much or less are considered equal
+    ///
+    /// # Examples
+    /// ```
+    /// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPArrays};
+    /// use feagi_core_data_structures_and_processing::genomic_structures::CorticalID;
+    ///
+    /// let camera = CorticalID::from_string("iic400".to_string()).unwrap();
+    /// let mut before = CorticalMappedXYZPNeuronData::new();
+    /// before.insert(camera, NeuronXYZPArrays::new_from_vectors(vec![0], vec![0], vec![0], vec![0.5]).unwrap());
+    /// let mut after = CorticalMappedXYZPNeuronData::new();
+    /// after.insert(camera, NeuronXYZPArrays::new_from_vectors(vec![0], vec![0], vec![0], vec![0.75]).unwrap());
+    ///
+    /// let diff = before.diff(&after, 0.01);
+    /// assert!(diff.get_added_areas().is_empty());
+    /// assert_eq!(diff.get_area_diff(&camera).unwrap().get_potential_changes().len(), 1);
+    /// println!("{}", diff);
+    /// ```
+    pub fn diff(&self, after: &CorticalMappedXYZPNeuronData, epsilon: f32) -> CorticalMappedXYZPNeuronDataDiff {
+        let empty = NeuronXYZPArrays::new();
+        let mut cortical_ids: Vec<CorticalID> = self.mappings.keys().chain(after.mappings.keys().filter(|id| !self.mappings.contains_key(id))).copied().collect();
+        cortical_ids.sort_by_key(|cortical_id| *cortical_id.as_bytes());
+
+        let mut diff = CorticalMappedXYZPNeuronDataDiff::default();
+        for cortical_id in cortical_ids {
+            let before_neurons = self.mappings.get(&cortical_id);
+            let after_neurons = after.mappings.get(&cortical_id);
+            match (before_neurons, after_neurons) {
+                (None, Some(_)) => diff.added_areas.push(cortical_id),
+                (Some(_), None) => diff.removed_areas.push(cortical_id),
+                _ => {}
+            }
+            let area_diff = before_neurons.unwrap_or(&empty).diff(after_neurons.unwrap_or(&empty), epsilon);
+            if !area_diff.is_empty() || before_neurons.is_none() || after_neurons.is_none() {
+                diff.area_diffs.push((cortical_id, area_diff));
+            }
+        }
+        diff
+    }
+}
+
+//endregion
@@ -0,0 +1,208 @@
+//! Export and import of neuron data as NumPy `.npz` archives and CSV, for sharing datasets with
+//! Python notebooks and other tooling. Requires the `interop` feature.
+//!
+//! # NPZ Layout
+//!
+//! Each cortical area is stored as four 1D arrays, named by the ASCII string of its cortical ID
+//! followed by the component: `<id>_x`, `<id>_y`, `<id>_z` (`uint32`) and `<id>_p` (`float32`).
+//! ```python
+//! import numpy as np
+//! burst = np.load("burst.npz")
+//! x, y, z, p = (burst[f"iic400_{c}"] for c in "xyzp")
+//! ```
+//!
+//! # CSV Layout
+//!
+//! A `cortical_id,x,y,z,p` header, followed by one row per neuron. Potentials are written with
+//! the shortest representation that reads back to the same f32, so a round trip is lossless,
+//! except that cortical areas without any neurons are dropped.
+//! ```text
+//! cortical_id,x,y,z,p
+//! iic400,0,1,0,0.5
+//! ```
+
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, Write};
+use ndarray::{Array1, ArrayView1};
+use ndarray_npy::{NpzReader, NpzWriter};
+use crate::error::{FeagiDataProcessingError, NeuronError};
+use crate::genomic_structures::CorticalID;
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZP, NeuronXYZPArrays};
+
+const CSV_HEADER: &str = "cortical_id,x,y,z,p";
+
+impl CorticalMappedXYZPNeuronData {
+
+    /// Writes every cortical area to an uncompressed `.npz` archive, as with `numpy.savez`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPArrays};
+    /// use feagi_core_data_structures_and_processing::genomic_structures::CorticalID;
+    ///
+    /// let camera = CorticalID::from_string("iic400".to_string()).unwrap();
+    /// let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    /// neuron_data.insert(camera, NeuronXYZPArrays::new_from_vectors(vec![0, 1], vec![2, 3], vec![0, 0], vec![0.5, 1.0]).unwrap());
+    ///
+    /// let npz = neuron_data.write_npz(Cursor::new(Vec::new())).unwrap();
+    /// let read = CorticalMappedXYZPNeuronData::new_from_npz(npz).unwrap();
+    /// assert!(neuron_data.diff(&read, 0.0).is_empty());
+    /// ```
+    pub fn write_npz<W: Write + Seek>(&self, writer: W) -> Result<W, FeagiDataProcessingError> {
+        self.write_npz_with(NpzWriter::new(writer))
+    }
+
+    /// Writes every cortical area to a deflate compressed `.npz` archive, as with `numpy.savez_compressed`.
+    pub fn write_npz_compressed<W: Write + Seek>(&self, writer: W) -> Result<W, FeagiDataProcessingError> {
+        self.write_npz_with(NpzWriter::new_compressed(writer))
+    }
+
+    /// Reads neuron data from a compressed or uncompressed `.npz` archive in the layout written
+    /// by [`write_npz`](Self::write_npz).
+    ///
+    /// # Returns
+    /// * `Ok(CorticalMappedXYZPNeuronData)` - The neuron data of every cortical area in the archive
+    /// * `Err(FeagiDataProcessingError)` - If the archive cannot be read, holds arrays not named
+    ///   `<cortical id>_<x|y|z|p>`, lacks one of an area's arrays, or an area's arrays differ in length
+    pub fn new_from_npz<R: Read + Seek>(reader: R) -> Result<Self, FeagiDataProcessingError> {
+        let mut npz = NpzReader::new(reader).map_err(npz_read_error)?;
+        let names = npz.names().map_err(npz_read_error)?;
+        let mut cortical_ids: Vec<CorticalID> = Vec::new();
+        for name in &names {
+            let (cortical_id_string, _) = name.rsplit_once('_')
+                .filter(|(_, component)| matches!(*component, "x" | "y" | "z" | "p"))
+                .ok_or_else(|| NeuronError::UnableToParseFromNeuronData(format!("NPZ array '{}' is not named '<cortical id>_<x|y|z|p>'!", name)))?;
+            let cortical_id = CorticalID::from_string(cortical_id_string.to_string())?;
+            if !cortical_ids.contains(&cortical_id) {
+                cortical_ids.push(cortical_id);
+            }
+        }
+        for cortical_id in &cortical_ids {
+            for component in ["x", "y", "z", "p"] {
+                let name = format!("{}_{}", cortical_id.as_ascii_string(), component);
+                if !names.contains(&name) {
+                    return Err(NeuronError::UnableToParseFromNeuronData(format!("NPZ archive lacks the array '{}'!", name)).into());
+                }
+            }
+        }
+
+        let mut neuron_data = CorticalMappedXYZPNeuronData::new_with_capacity(cortical_ids.len());
+        for cortical_id in cortical_ids {
+            let id_string = cortical_id.as_ascii_string();
+            let mut read_component = |component: &str| -> Result<Array1<u32>, FeagiDataProcessingError> {
+                npz.by_name(&format!("{}_{}", id_string, component)).map_err(npz_read_error)
+            };
+            let x = read_component("x")?;
+            let y = read_component("y")?;
+            let z = read_component("z")?;
+            let p: Array1<f32> = npz.by_name(&format!("{}_p", id_string)).map_err(npz_read_error)?;
+            neuron_data.insert(cortical_id, NeuronXYZPArrays::new_from_vectors(x.to_vec(), y.to_vec(), z.to_vec(), p.to_vec())?);
+        }
+        Ok(neuron_data)
+    }
+
+    /// Writes every neuron as a row of CSV, with a `cortical_id,x,y,z,p` header.
+    ///
+    /// Cortical areas without any neurons have no rows, and so are not read back by
+    /// [`new_from_csv`](Self::new_from_csv). Use [`write_npz`](Self::write_npz) to keep them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPArrays};
+    /// use feagi_core_data_structures_and_processing::genomic_structures::CorticalID;
+    ///
+    /// let camera = CorticalID::from_string("iic400".to_string()).unwrap();
+    /// let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    /// neuron_data.insert(camera, NeuronXYZPArrays::new_from_vectors(vec![0, 1], vec![2, 3], vec![0, 0], vec![0.5, 1.0]).unwrap());
+    ///
+    /// let mut csv: Vec<u8> = Vec::new();
+    /// neuron_data.write_csv(&mut csv).unwrap();
+    /// assert_eq!(String::from_utf8(csv.clone()).unwrap(), "cortical_id,x,y,z,p\niic400,0,2,0,0.5\niic400,1,3,0,1\n");
+    ///
+    /// let read = CorticalMappedXYZPNeuronData::new_from_csv(csv.as_slice()).unwrap();
+    /// assert!(neuron_data.diff(&read, 0.0).is_empty());
+    /// ```
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), FeagiDataProcessingError> {
+        let mut writer = BufWriter::new(writer);
+        writeln!(writer, "{}", CSV_HEADER).map_err(csv_write_error)?;
+        for cortical_id in self.sorted_cortical_ids() {
+            let id_string = cortical_id.as_ascii_string();
+            let (x, y, z, p) = self.mappings[&cortical_id].borrow_xyzp_vectors();
+            for i in 0..x.len() {
+                writeln!(writer, "{},{},{},{},{}", id_string, x[i], y[i], z[i], p[i]).map_err(csv_write_error)?;
+            }
+        }
+        writer.flush().map_err(csv_write_error)
+    }
+
+    /// Reads neuron data from CSV in the layout written by [`write_csv`](Self::write_csv).
+    ///
+    /// Rows of a cortical area do not need to be contiguous. Blank lines are ignored.
+    ///
+    /// # Returns
+    /// * `Ok(CorticalMappedXYZPNeuronData)` - The neuron data of every cortical area listed
+    /// * `Err(FeagiDataProcessingError)` - If the header is missing, or a row cannot be parsed
+    pub fn new_from_csv<R: Read>(reader: R) -> Result<Self, FeagiDataProcessingError> {
+        let mut lines = BufReader::new(reader).lines();
+        match lines.next() {
+            Some(Ok(header)) if header.trim() == CSV_HEADER => {}
+            Some(Err(err)) => return Err(csv_read_error(err)),
+            _ => return Err(NeuronError::UnableToParseFromNeuronData(format!("CSV must start with a '{}' header!", CSV_HEADER)).into()),
+        }
+
+        let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+        for (line_index, line) in lines.enumerate() {
+            let line = line.map_err(csv_read_error)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row_error = || NeuronError::UnableToParseFromNeuronData(format!("Unable to parse CSV row {} '{}'!", line_index + 2, line));
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [cortical_id_string, x, y, z, p] = fields.as_slice() else {
+                return Err(row_error().into());
+            };
+            let cortical_id = CorticalID::from_string(cortical_id_string.to_string())?;
+            let x: u32 = x.parse().map_err(|_| row_error())?;
+            let y: u32 = y.parse().map_err(|_| row_error())?;
+            let z: u32 = z.parse().map_err(|_| row_error())?;
+            let p: f32 = p.parse().map_err(|_| row_error())?;
+            neuron_data.mappings.entry(cortical_id).or_insert_with(NeuronXYZPArrays::new).push(&NeuronXYZP::new(x, y, z, p));
+        }
+        Ok(neuron_data)
+    }
+
+    fn write_npz_with<W: Write + Seek>(&self, mut npz: NpzWriter<W>) -> Result<W, FeagiDataProcessingError> {
+        for cortical_id in self.sorted_cortical_ids() {
+            let id_string = cortical_id.as_ascii_string();
+            let (x, y, z, p) = self.mappings[&cortical_id].borrow_xyzp_vectors();
+            for (component, values) in [("x", x), ("y", y), ("z", z)] {
+                npz.add_array(format!("{}_{}", id_string, component), &ArrayView1::from(values.as_slice())).map_err(npz_write_error)?;
+            }
+            npz.add_array(format!("{}_p", id_string), &ArrayView1::from(p.as_slice())).map_err(npz_write_error)?;
+        }
+        npz.finish().map_err(npz_write_error)
+    }
+
+    /// Cortical IDs ordered by their bytes, so exports of equal data are identical.
+    fn sorted_cortical_ids(&self) -> Vec<CorticalID> {
+        let mut cortical_ids: Vec<CorticalID> = self.mappings.keys().copied().collect();
+        cortical_ids.sort_by_key(|cortical_id| *cortical_id.as_bytes());
+        cortical_ids
+    }
+}
+
+fn npz_write_error(err: impl std::fmt::Display) -> FeagiDataProcessingError {
+    NeuronError::UnableToConvertNeuronFormat(format!("Unable to write NPZ: {}", err)).into()
+}
+
+fn npz_read_error(err: impl std::fmt::Display) -> FeagiDataProcessingError {
+    NeuronError::UnableToParseFromNeuronData(format!("Unable to read NPZ: {}", err)).into()
+}
+
+fn csv_write_error(err: std::io::Error) -> FeagiDataProcessingError {
+    NeuronError::UnableToConvertNeuronFormat(format!("Unable to write CSV: {}", err)).into()
+}
+
+fn csv_read_error(err: std::io::Error) -> FeagiDataProcessingError {
+    NeuronError::UnableToParseFromNeuronData(format!("Unable to read CSV: {}", err)).into()
+}
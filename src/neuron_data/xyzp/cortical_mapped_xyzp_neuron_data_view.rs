@@ -0,0 +1,154 @@
+//! Borrowed, zero-copy view over `NeuronCategoricalXYZP` byte structures.
+//!
+//! Deserializing a [`CorticalMappedXYZPNeuronData`] copies every neuron of every cortical area.
+//! [`CorticalMappedXYZPNeuronDataView`] instead borrows the byte structure, reads cortical
+//! subheaders only when asked, and hands out [`NeuronXYZPArraysView`]s that decode neurons
+//! straight from the buffer. A receiver interested in a single cortical area therefore only
+//! pays for that area.
+//!
+//! Only the uncompressed format can be viewed, as compressed areas must be decoded as a whole.
+
+use byteorder::{ByteOrder, LittleEndian};
+use crate::error::{FeagiBytesError, FeagiDataProcessingError};
+use crate::genomic_structures::CorticalID;
+use crate::io_processing::byte_structures::FeagiByteStructure;
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPArraysView};
+
+/// Borrowed view over a serialized [`CorticalMappedXYZPNeuronData`].
+///
+/// Creating the view only checks the global header and that the subheader table fits in the
+/// buffer. Each cortical area's data range is validated when that area is accessed.
+///
+/// # Example
+/// ```rust
+/// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, CorticalMappedXYZPNeuronDataView, NeuronXYZP, NeuronXYZPArrays};
+/// use feagi_core_data_structures_and_processing::genomic_structures::{CorticalID, MotorCorticalType};
+/// use feagi_core_data_structures_and_processing::io_processing::byte_structures::FeagiByteStructureCompatible;
+///
+/// let motor_id = CorticalID::new_motor_cortical_area_id(MotorCorticalType::RotaryMotor, 0.into()).unwrap();
+/// let mut neurons = NeuronXYZPArrays::with_capacity(1);
+/// neurons.push(&NeuronXYZP::new(1, 0, 3, 1.0));
+/// let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+/// neuron_data.insert(motor_id, neurons);
+/// let byte_structure = neuron_data.as_new_feagi_byte_structure().unwrap();
+///
+/// let view = CorticalMappedXYZPNeuronDataView::new(&byte_structure).unwrap();
+/// let motor_neurons = view.get_neurons_of(&motor_id).unwrap().unwrap();
+/// assert_eq!(motor_neurons.get(0).unwrap(), NeuronXYZP::new(1, 0, 3, 1.0));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CorticalMappedXYZPNeuronDataView<'a> {
+    bytes: &'a [u8],
+    number_cortical_areas: usize,
+}
+
+impl<'a> CorticalMappedXYZPNeuronDataView<'a> {
+
+    /// Creates a view over a `NeuronCategoricalXYZP` byte structure.
+    ///
+    /// # Returns
+    /// * `Ok(CorticalMappedXYZPNeuronDataView)` - View borrowing the structure's buffer
+    /// * `Err(FeagiDataProcessingError)` - If the structure is of another type or version, or
+    ///   is too short to hold its cortical subheaders
+    pub fn new(feagi_byte_structure: &'a FeagiByteStructure) -> Result<Self, FeagiDataProcessingError> {
+        FeagiByteStructure::verify_matching_structure_type_and_version(feagi_byte_structure,
+                                                                       CorticalMappedXYZPNeuronData::BYTE_STRUCT_TYPE,
+                                                                       CorticalMappedXYZPNeuronData::BYTE_STRUCT_VERSION)?;
+        let bytes = feagi_byte_structure.borrow_data_as_slice();
+        let subheaders_start = Self::subheader_start(0);
+        if bytes.len() < subheaders_start {
+            return Err(FeagiBytesError::UnableToDeserializeBytes("Byte structure for NeuronCategoricalXYZPV1 is too short to fit the cortical count header!".into()).into());
+        }
+        let number_cortical_areas = LittleEndian::read_u16(&bytes[FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES..subheaders_start]) as usize;
+        let min_array_length_with_cortical_headers = Self::subheader_start(number_cortical_areas);
+        if bytes.len() < min_array_length_with_cortical_headers {
+            return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Byte structure for NeuronCategoricalXYZPV1 needs a length of {} to fit just the cortical details header, but is a length of {}",
+                                                                         min_array_length_with_cortical_headers, bytes.len())).into());
+        }
+        Ok(CorticalMappedXYZPNeuronDataView { bytes, number_cortical_areas })
+    }
+
+    /// Returns the number of cortical areas in the viewed structure.
+    pub fn len(&self) -> usize {
+        self.number_cortical_areas
+    }
+
+    /// Returns true if the viewed structure contains no cortical areas.
+    pub fn is_empty(&self) -> bool {
+        self.number_cortical_areas == 0
+    }
+
+    /// Checks if a cortical area is present, without validating its neuron data.
+    pub fn contains_cortical_id(&self, cortical_id: &CorticalID) -> bool {
+        self.find_area_index(cortical_id).is_some()
+    }
+
+    /// Returns an iterator over the cortical IDs in the order they were serialized.
+    pub fn cortical_ids(&self) -> impl Iterator<Item=Result<CorticalID, FeagiDataProcessingError>> + 'a {
+        let view = *self;
+        (0..self.number_cortical_areas).map(move |area_index| view.read_cortical_id(area_index))
+    }
+
+    /// Returns a view of the neurons of a single cortical area.
+    ///
+    /// # Returns
+    /// * `Ok(Some(NeuronXYZPArraysView))` - The area's neurons, borrowed from the buffer
+    /// * `Ok(None)` - If the cortical area is not in the structure
+    /// * `Err(FeagiDataProcessingError)` - If the area's data range is invalid
+    pub fn get_neurons_of(&self, cortical_id: &CorticalID) -> Result<Option<NeuronXYZPArraysView<'a>>, FeagiDataProcessingError> {
+        match self.find_area_index(cortical_id) {
+            Some(area_index) => Ok(Some(self.read_neurons(area_index)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns an iterator over every cortical area and a view of its neurons.
+    pub fn iter(&self) -> impl Iterator<Item=Result<(CorticalID, NeuronXYZPArraysView<'a>), FeagiDataProcessingError>> + 'a {
+        let view = *self;
+        (0..self.number_cortical_areas).map(move |area_index| {
+            Ok((view.read_cortical_id(area_index)?, view.read_neurons(area_index)?))
+        })
+    }
+
+    /// Copies all viewed cortical areas into a new owned [`CorticalMappedXYZPNeuronData`].
+    pub fn to_cortical_mapped_xyzp_neuron_data(&self) -> Result<CorticalMappedXYZPNeuronData, FeagiDataProcessingError> {
+        let mut output = CorticalMappedXYZPNeuronData::new_with_capacity(self.number_cortical_areas);
+        for area in self.iter() {
+            let (cortical_id, neurons) = area?;
+            _ = output.insert(cortical_id, neurons.to_neuron_xyzp_arrays());
+        }
+        Ok(output)
+    }
+
+    //region Internal Functions
+
+    fn subheader_start(area_index: usize) -> usize {
+        FeagiByteStructure::GLOBAL_BYTE_HEADER_BYTE_SIZE_IN_BYTES + CorticalMappedXYZPNeuronData::NUMBER_BYTES_CORTICAL_COUNT_HEADER
+            + (area_index * CorticalMappedXYZPNeuronData::NUMBER_BYTES_PER_CORTICAL_ID_HEADER)
+    }
+
+    fn subheader(&self, area_index: usize) -> &'a [u8] {
+        let start = Self::subheader_start(area_index);
+        &self.bytes[start..start + CorticalMappedXYZPNeuronData::NUMBER_BYTES_PER_CORTICAL_ID_HEADER]
+    }
+
+    fn find_area_index(&self, cortical_id: &CorticalID) -> Option<usize> {
+        (0..self.number_cortical_areas).find(|area_index| &self.subheader(*area_index)[0..CorticalID::NUMBER_OF_BYTES] == cortical_id.as_bytes())
+    }
+
+    fn read_cortical_id(&self, area_index: usize) -> Result<CorticalID, FeagiDataProcessingError> {
+        CorticalID::from_bytes(<&[u8; CorticalID::NUMBER_OF_BYTES]>::try_from(&self.subheader(area_index)[0..CorticalID::NUMBER_OF_BYTES]).unwrap())
+    }
+
+    fn read_neurons(&self, area_index: usize) -> Result<NeuronXYZPArraysView<'a>, FeagiDataProcessingError> {
+        let subheader = self.subheader(area_index);
+        let data_start_reading = LittleEndian::read_u32(&subheader[6..10]) as usize;
+        let number_bytes_to_read = LittleEndian::read_u32(&subheader[10..14]) as usize;
+        if self.bytes.len() < data_start_reading + number_bytes_to_read {
+            return Err(FeagiBytesError::UnableToDeserializeBytes("Byte structure for NeuronCategoricalXYZPV1 is too short to fit the data the header says it contains!".into()).into());
+        }
+        Ok(NeuronXYZPArraysView::new_from_bytes(&self.bytes[data_start_reading..data_start_reading + number_bytes_to_read])?)
+    }
+
+    //endregion
+}
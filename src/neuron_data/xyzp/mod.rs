@@ -27,11 +27,23 @@
 //! - **NeuronXYZPArrays**: Array-based storage for high-performance batch operations
 //! - Parallel arrays for X, Y, Z, P values enabling vectorized processing
 //! - Memory-efficient layout for large neuron populations of a single cortical area
+//! - Conversion to and from dense `Array3<f32>` potential volumes of a cortical area
+//! - Set algebra (merge, intersection, difference) by neuron coordinate
+//! - **NeuronXYZPSpatialIndex**: Grid index over a NeuronXYZPArrays for box, radius and nearest neighbor queries
+//! - **NeuronXYZPStats**: Per burst summary statistics (active count, potentials, centroid, bounds, histogram)
 //!
 //! ## Cortical Organization
 //! - **CorticalMappedXYZPNeuronData**: Maps cortical areas to neuron collections
 //! - Hierarchical organization matching biological brain structure
 //! - Support for network serialization and distribution
+//! - Merging bursts from several agents, and filtering areas by cortical type
+//! - Summarizing the statistics of every cortical area for monitoring
+//! - Diffing two bursts (**CorticalMappedXYZPNeuronDataDiff**), with a readable report
+//! - Export and import as NumPy `.npz` archives and CSV, with the `interop` feature
+//!
+//! ## Borrowed Views
+//! - **CorticalMappedXYZPNeuronDataView**: Zero-copy view over a serialized neuron byte structure
+//! - **NeuronXYZPArraysView**: Decodes the neurons of one cortical area straight from the buffer
 //!
 //! # Neural Encoding/Decoding System
 //!
@@ -52,7 +64,7 @@
 //! The XYZP system is optimized for:
 //! - **Cache efficiency**: Array-of-structures vs structure-of-arrays based on usage
 //! - **Vectorization**: SIMD-friendly data layouts where possible
-//! - **Network transmission**: Compact serialization formats
+//! - **Network transmission**: Compact serialization formats, with an optional compressed format for large areas
 //! - **Parallel processing**: Thread-safe operations for concurrent neural simulation
 //!
 //! # Usage Patterns
@@ -104,12 +116,27 @@
 
 mod neuron_xyzp;
 mod neuron_xyzp_arrays;
+mod neuron_xyzp_arrays_view;
+mod neuron_xyzp_arrays_dense;
+mod neuron_xyzp_set_operations;
+mod neuron_xyzp_spatial_index;
+mod neuron_xyzp_stats;
 mod cortical_mapped_xyzp_neuron_data;
+mod cortical_mapped_xyzp_neuron_data_compressed;
+mod cortical_mapped_xyzp_neuron_data_view;
+mod cortical_mapped_xyzp_neuron_data_diff;
+#[cfg(feature = "interop")]
+mod cortical_mapped_xyzp_neuron_data_interop;
 mod coders;
 
 pub use neuron_xyzp::NeuronXYZP;
 pub use neuron_xyzp_arrays::NeuronXYZPArrays;
+pub use neuron_xyzp_arrays_view::NeuronXYZPArraysView;
+pub use neuron_xyzp_arrays_dense::NeuronXYZPDuplicateHandling;
+pub use neuron_xyzp_spatial_index::NeuronXYZPSpatialIndex;
+pub use neuron_xyzp_stats::NeuronXYZPStats;
 pub use cortical_mapped_xyzp_neuron_data::CorticalMappedXYZPNeuronData;
-pub use coders::{NeuronCoderVariantType};
-
-pub(crate) use coders::{NeuronXYZPEncoder, encoders};
\ No newline at end of file
+pub use cortical_mapped_xyzp_neuron_data_compressed::NeuronXYZPPotentialQuantization;
+pub use cortical_mapped_xyzp_neuron_data_view::CorticalMappedXYZPNeuronDataView;
+pub use cortical_mapped_xyzp_neuron_data_diff::{CorticalMappedXYZPNeuronDataDiff, NeuronXYZPArraysDiff};
+pub use coders::{NeuronCoderVariantType, NeuronXYZPEncoder, NeuronXYZPDecoder, NeuronCoderID, NeuronCoderParameters, NeuronCoderRegistry, NeuronXYZPEncoderConstructor, NeuronXYZPDecoderConstructor};
@@ -0,0 +1,144 @@
+//! Conversion between sparse neuron lists and dense potential volumes.
+//!
+//! A dense volume is an `Array3<f32>` indexed `[x, y, z]`, holding the potential of every
+//! neuron of a cortical area, with 0 where no neuron is present. This gives analysis code and
+//! image decoders a volumetric view of a cortical area, at the cost of memory proportional to
+//! the area's full resolution rather than its number of active neurons.
+
+use ndarray::Array3;
+use crate::error::{FeagiDataProcessingError, IODataError, NeuronError};
+use crate::neuron_data::xyzp::NeuronXYZPArrays;
+
+/// How neurons sharing a coordinate are combined when writing a dense volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NeuronXYZPDuplicateHandling {
+    /// The largest potential at the coordinate is kept.
+    Max,
+    /// The potentials at the coordinate are added together.
+    Sum,
+    /// The potential of the neuron appearing last in the list is kept.
+    Last,
+}
+
+impl std::fmt::Display for NeuronXYZPDuplicateHandling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Max => "Max",
+            Self::Sum => "Sum",
+            Self::Last => "Last",
+        };
+        write!(f, "NeuronXYZPDuplicateHandling({})", name)
+    }
+}
+
+impl NeuronXYZPArrays {
+
+    /// Creates a NeuronXYZPArrays holding every voxel of a dense volume above a threshold.
+    ///
+    /// Neurons are listed in x, then y, then z order. NaN potentials are never included.
+    ///
+    /// # Arguments
+    /// * `volume` - Potentials indexed `[x, y, z]`
+    /// * `threshold` - Voxels with a potential strictly above this become neurons
+    ///
+    /// # Returns
+    /// * `Ok(NeuronXYZPArrays)` - The neurons of all voxels above the threshold
+    /// * `Err(FeagiDataProcessingError)` - If the volume is larger than u32 coordinates can address
+    ///
+    /// # Examples
+    /// ```
+    /// use ndarray::Array3;
+    /// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{NeuronXYZP, NeuronXYZPArrays, NeuronXYZPDuplicateHandling};
+    ///
+    /// let mut volume = Array3::<f32>::zeros((4, 3, 2));
+    /// volume[[1, 2, 0]] = 0.75;
+    /// volume[[3, 0, 1]] = 0.05;
+    ///
+    /// let neurons = NeuronXYZPArrays::new_from_dense_volume(&volume, 0.1).unwrap();
+    /// assert_eq!(neurons.copy_as_neuron_xyzp_vec(), vec![NeuronXYZP::new(1, 2, 0, 0.75)]);
+    ///
+    /// let dense = neurons.to_dense_volume((4, 3, 2), NeuronXYZPDuplicateHandling::Max).unwrap();
+    /// assert_eq!(dense[[1, 2, 0]], 0.75);
+    /// assert_eq!(dense[[3, 0, 1]], 0.0);
+    /// ```
+    pub fn new_from_dense_volume(volume: &Array3<f32>, threshold: f32) -> Result<Self, FeagiDataProcessingError> {
+        let (width, height, depth) = volume.dim();
+        if width > u32::MAX as usize || height > u32::MAX as usize || depth > u32::MAX as usize {
+            return Err(IODataError::InvalidParameters(format!("Dense volume of shape ({}, {}, {}) exceeds the maximum neuron coordinate of {}!", width, height, depth, u32::MAX)).into());
+        }
+
+        let mut x: Vec<u32> = Vec::new();
+        let mut y: Vec<u32> = Vec::new();
+        let mut z: Vec<u32> = Vec::new();
+        let mut p: Vec<f32> = Vec::new();
+        for ((voxel_x, voxel_y, voxel_z), &potential) in volume.indexed_iter() {
+            if potential > threshold {
+                x.push(voxel_x as u32);
+                y.push(voxel_y as u32);
+                z.push(voxel_z as u32);
+                p.push(potential);
+            }
+        }
+        Ok(NeuronXYZPArrays::new_from_vectors(x, y, z, p)?)
+    }
+
+    /// Creates a dense volume of the given resolution from these neurons.
+    ///
+    /// Voxels without a neuron are 0.
+    ///
+    /// # Arguments
+    /// * `resolution` - The (x, y, z) dimensions of the cortical area
+    /// * `duplicate_handling` - How neurons sharing a coordinate are combined
+    ///
+    /// # Returns
+    /// * `Ok(Array3<f32>)` - Potentials indexed `[x, y, z]`
+    /// * `Err(FeagiDataProcessingError)` - If any neuron lies outside the resolution
+    pub fn to_dense_volume(&self, resolution: (usize, usize, usize), duplicate_handling: NeuronXYZPDuplicateHandling) -> Result<Array3<f32>, FeagiDataProcessingError> {
+        let mut volume: Array3<f32> = Array3::zeros(resolution);
+        self.write_to_dense_volume(&mut volume, duplicate_handling)?;
+        Ok(volume)
+    }
+
+    /// Writes these neurons into an existing dense volume, which is first zeroed.
+    ///
+    /// Allows reusing the volume allocation across bursts. See `to_dense_volume`.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If all neurons were written
+    /// * `Err(FeagiDataProcessingError)` - If any neuron lies outside the volume, in which case the
+    ///   volume is left zeroed
+    pub fn write_to_dense_volume(&self, volume: &mut Array3<f32>, duplicate_handling: NeuronXYZPDuplicateHandling) -> Result<(), FeagiDataProcessingError> {
+        let (width, height, depth) = volume.dim();
+        let (x, y, z, p) = self.borrow_xyzp_vectors();
+        if let Some(index) = (0..self.len()).find(|&i| x[i] as usize >= width || y[i] as usize >= height || z[i] as usize >= depth) {
+            volume.fill(0.0);
+            return Err(NeuronError::UnableToConvertNeuronFormat(format!("Neuron at ({}, {}, {}) lies outside of the dense volume of shape ({}, {}, {})!",
+                                                                        x[index], y[index], z[index], width, height, depth)).into());
+        }
+
+        match duplicate_handling {
+            NeuronXYZPDuplicateHandling::Max => {
+                // Start below any potential so negative potentials are kept, then clear untouched voxels
+                volume.fill(f32::NEG_INFINITY);
+                for i in 0..self.len() {
+                    let voxel = &mut volume[[x[i] as usize, y[i] as usize, z[i] as usize]];
+                    *voxel = voxel.max(p[i]);
+                }
+                volume.mapv_inplace(|potential| if potential == f32::NEG_INFINITY { 0.0 } else { potential });
+            }
+            NeuronXYZPDuplicateHandling::Sum => {
+                volume.fill(0.0);
+                for i in 0..self.len() {
+                    volume[[x[i] as usize, y[i] as usize, z[i] as usize]] += p[i];
+                }
+            }
+            NeuronXYZPDuplicateHandling::Last => {
+                volume.fill(0.0);
+                for i in 0..self.len() {
+                    volume[[x[i] as usize, y[i] as usize, z[i] as usize]] = p[i];
+                }
+            }
+        }
+        Ok(())
+    }
+}
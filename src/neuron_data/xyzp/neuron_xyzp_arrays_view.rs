@@ -0,0 +1,132 @@
+//! Borrowed, zero-copy view over serialized neuron XYZP data.
+//!
+//! This module provides `NeuronXYZPArraysView`, which reads neuron data directly out of the
+//! byte layout written by `NeuronXYZPArrays::write_neural_data_to_bytes`, without copying it
+//! into new vectors.
+
+use byteorder::{ByteOrder, LittleEndian};
+use crate::error::{FeagiBytesError, FeagiDataProcessingError, IODataError};
+use crate::neuron_data::xyzp::{NeuronXYZP, NeuronXYZPArrays};
+
+/// Borrowed view over the serialized neurons of a single cortical area.
+///
+/// The serialized layout stores all X values, then all Y values, then all Z values, then all
+/// P values, each as 4 little-endian bytes. This view splits the given bytes into those four
+/// sections and decodes values only as they are accessed.
+///
+/// # Example
+/// ```rust
+/// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{NeuronXYZP, NeuronXYZPArrays, NeuronXYZPArraysView};
+///
+/// let mut arrays = NeuronXYZPArrays::with_capacity(2);
+/// arrays.push(&NeuronXYZP::new(1, 2, 3, 0.5));
+/// arrays.push(&NeuronXYZP::new(4, 5, 6, 0.7));
+/// let mut bytes = vec![0u8; arrays.get_size_in_number_of_bytes()];
+/// arrays.write_neural_data_to_bytes(&mut bytes).unwrap();
+///
+/// let view = NeuronXYZPArraysView::new_from_bytes(&bytes).unwrap();
+/// assert_eq!(view.len(), 2);
+/// assert_eq!(view.get(1).unwrap(), NeuronXYZP::new(4, 5, 6, 0.7));
+/// assert_eq!(view.iter_z().sum::<u32>(), 9);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NeuronXYZPArraysView<'a> {
+    x: &'a [u8],
+    y: &'a [u8],
+    z: &'a [u8],
+    p: &'a [u8],
+}
+
+impl<'a> NeuronXYZPArraysView<'a> {
+    const NUMBER_BYTES_PER_VALUE: usize = 4;
+
+    /// Creates a view over serialized neuron data.
+    ///
+    /// # Arguments
+    /// * `bytes` - Neuron data as written by `NeuronXYZPArrays::write_neural_data_to_bytes`
+    ///
+    /// # Returns
+    /// * `Ok(NeuronXYZPArraysView)` - View over the given bytes
+    /// * `Err(FeagiBytesError)` - If the length is not a multiple of 16 bytes
+    pub fn new_from_bytes(bytes: &'a [u8]) -> Result<Self, FeagiBytesError> {
+        if !bytes.len().is_multiple_of(NeuronXYZP::NUMBER_BYTES_PER_NEURON) {
+            return Err(FeagiBytesError::UnableToDeserializeBytes(format!("Neuron data must be a multiple of {} bytes long, but is {} bytes long!", NeuronXYZP::NUMBER_BYTES_PER_NEURON, bytes.len())));
+        }
+        let section_length = bytes.len() / 4;
+        let (x, rest) = bytes.split_at(section_length);
+        let (y, rest) = rest.split_at(section_length);
+        let (z, p) = rest.split_at(section_length);
+        Ok(NeuronXYZPArraysView { x, y, z, p })
+    }
+
+    /// Returns the number of neurons in this view.
+    pub fn len(&self) -> usize {
+        self.x.len() / Self::NUMBER_BYTES_PER_VALUE
+    }
+
+    /// Returns true if this view contains no neurons.
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty()
+    }
+
+    /// Returns the neuron at the given index.
+    ///
+    /// # Returns
+    /// * `Ok(NeuronXYZP)` - The neuron at the index
+    /// * `Err(FeagiDataProcessingError)` - If the index is out of bounds
+    pub fn get(&self, index: usize) -> Result<NeuronXYZP, FeagiDataProcessingError> {
+        if index >= self.len() {
+            return Err(IODataError::InvalidParameters(format!("Given index {} is exceeds NeuronXYZPArraysView length of {}!", index, self.len())).into())
+        }
+        let start = index * Self::NUMBER_BYTES_PER_VALUE;
+        let end = start + Self::NUMBER_BYTES_PER_VALUE;
+        Ok(NeuronXYZP::new(
+            LittleEndian::read_u32(&self.x[start..end]),
+            LittleEndian::read_u32(&self.y[start..end]),
+            LittleEndian::read_u32(&self.z[start..end]),
+            LittleEndian::read_f32(&self.p[start..end]),
+        ))
+    }
+
+    /// Returns an iterator decoding each neuron as it is reached.
+    pub fn iter(&self) -> impl Iterator<Item=NeuronXYZP> + 'a {
+        self.iter_x().zip(self.iter_y()).zip(self.iter_z()).zip(self.iter_p())
+            .map(|(((x, y), z), p)| NeuronXYZP::new(x, y, z, p))
+    }
+
+    /// Returns an iterator over only the X coordinates.
+    pub fn iter_x(&self) -> impl Iterator<Item=u32> + 'a {
+        self.x.chunks_exact(Self::NUMBER_BYTES_PER_VALUE).map(LittleEndian::read_u32)
+    }
+
+    /// Returns an iterator over only the Y coordinates.
+    pub fn iter_y(&self) -> impl Iterator<Item=u32> + 'a {
+        self.y.chunks_exact(Self::NUMBER_BYTES_PER_VALUE).map(LittleEndian::read_u32)
+    }
+
+    /// Returns an iterator over only the Z coordinates.
+    pub fn iter_z(&self) -> impl Iterator<Item=u32> + 'a {
+        self.z.chunks_exact(Self::NUMBER_BYTES_PER_VALUE).map(LittleEndian::read_u32)
+    }
+
+    /// Returns an iterator over only the potentials.
+    pub fn iter_p(&self) -> impl Iterator<Item=f32> + 'a {
+        self.p.chunks_exact(Self::NUMBER_BYTES_PER_VALUE).map(LittleEndian::read_f32)
+    }
+
+    /// Borrows the raw little-endian bytes of the X, Y, Z, and P sections.
+    pub fn borrow_xyzp_bytes(&self) -> (&'a [u8], &'a [u8], &'a [u8], &'a [u8]) {
+        (self.x, self.y, self.z, self.p)
+    }
+
+    /// Copies the viewed neurons into a new owned `NeuronXYZPArrays`.
+    pub fn to_neuron_xyzp_arrays(&self) -> NeuronXYZPArrays {
+        // Lengths are equal by construction, so this cannot fail
+        NeuronXYZPArrays::new_from_vectors(
+            self.iter_x().collect(),
+            self.iter_y().collect(),
+            self.iter_z().collect(),
+            self.iter_p().collect(),
+        ).unwrap()
+    }
+}
@@ -0,0 +1,294 @@
+//! Set algebra over neuron coordinates.
+//!
+//! Treats neuron collections as sets keyed by their (x, y, z) coordinate, for combining bursts
+//! from several agents or subtracting a baseline. Potentials of neurons sharing a coordinate are
+//! combined with a [`NeuronXYZPDuplicateHandling`], where `Last` means later neurons (and those
+//! of the right hand side) overwrite earlier ones.
+//!
+//! When both inputs are sorted by coordinate (see `NeuronXYZPArrays::sort_by_coordinate`), the
+//! operations run as a single linear merge pass and output sorted neurons. Otherwise they fall
+//! back to hashing coordinates, and output neurons in order of first appearance.
+
+use std::collections::{HashMap, HashSet};
+use crate::genomic_structures::CorticalType;
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZP, NeuronXYZPArrays, NeuronXYZPDuplicateHandling};
+
+type Coordinate = (u32, u32, u32);
+
+//region NeuronXYZPArrays
+
+impl NeuronXYZPArrays {
+
+    /// Returns true if the neurons are in ascending (x, y, z) order. Equal coordinates are allowed.
+    pub fn is_sorted_by_coordinate(&self) -> bool {
+        (1..self.len()).all(|i| self.coordinate_at(i - 1) <= self.coordinate_at(i))
+    }
+
+    /// Sorts the neurons in ascending (x, y, z) order, keeping the order of equal coordinates.
+    pub fn sort_by_coordinate(&mut self) {
+        if self.is_sorted_by_coordinate() {
+            return;
+        }
+        let mut neurons = self.copy_as_neuron_xyzp_vec();
+        neurons.sort_by_key(|neuron| (neuron.x, neuron.y, neuron.z));
+        self.clear();
+        for neuron in &neurons {
+            self.push(neuron);
+        }
+    }
+
+    /// Returns the union of both neuron sets, one neuron per coordinate.
+    ///
+    /// # Arguments
+    /// * `other` - The neurons to merge in, which come after these for `Last`
+    /// * `duplicate_handling` - How potentials sharing a coordinate (within or across inputs) are combined
+    ///
+    /// # Examples
+    /// ```
+    /// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{NeuronXYZP, NeuronXYZPArrays, NeuronXYZPDuplicateHandling};
+    ///
+    /// let a = NeuronXYZPArrays::new_from_vectors(vec![0, 1], vec![0, 0], vec![0, 0], vec![0.5, 0.25]).unwrap();
+    /// let b = NeuronXYZPArrays::new_from_vectors(vec![1, 2], vec![0, 0], vec![0, 0], vec![0.5, 1.0]).unwrap();
+    ///
+    /// let union = a.merge(&b, NeuronXYZPDuplicateHandling::Sum);
+    /// assert_eq!(union.copy_as_neuron_xyzp_vec(), vec![
+    ///     NeuronXYZP::new(0, 0, 0, 0.5), NeuronXYZP::new(1, 0, 0, 0.75), NeuronXYZP::new(2, 0, 0, 1.0)
+    /// ]);
+    /// assert_eq!(a.intersection(&b, NeuronXYZPDuplicateHandling::Last).copy_as_neuron_xyzp_vec(), vec![NeuronXYZP::new(1, 0, 0, 0.5)]);
+    /// assert_eq!(a.difference(&b).copy_as_neuron_xyzp_vec(), vec![NeuronXYZP::new(0, 0, 0, 0.5)]);
+    /// ```
+    pub fn merge(&self, other: &NeuronXYZPArrays, duplicate_handling: NeuronXYZPDuplicateHandling) -> NeuronXYZPArrays {
+        self.combine_by_coordinate(other, duplicate_handling, |_in_self, _in_other| true)
+    }
+
+    /// Returns the neurons at coordinates present in both sets, one neuron per coordinate.
+    ///
+    /// # Arguments
+    /// * `other` - The neurons to intersect with, which come after these for `Last`
+    /// * `duplicate_handling` - How potentials sharing a coordinate (within or across inputs) are combined
+    pub fn intersection(&self, other: &NeuronXYZPArrays, duplicate_handling: NeuronXYZPDuplicateHandling) -> NeuronXYZPArrays {
+        self.combine_by_coordinate(other, duplicate_handling, |in_self, in_other| in_self && in_other)
+    }
+
+    /// Returns these neurons, except those at a coordinate present in `other`.
+    ///
+    /// Potentials in `other` are irrelevant, and neurons are otherwise kept as is, including any
+    /// sharing a coordinate.
+    pub fn difference(&self, other: &NeuronXYZPArrays) -> NeuronXYZPArrays {
+        let mut output = NeuronXYZPArrays::with_capacity(self.len());
+        if self.is_sorted_by_coordinate() && other.is_sorted_by_coordinate() {
+            let mut other_index: usize = 0;
+            for index in 0..self.len() {
+                let coordinate = self.coordinate_at(index);
+                while other_index < other.len() && other.coordinate_at(other_index) < coordinate {
+                    other_index += 1;
+                }
+                if other_index >= other.len() || other.coordinate_at(other_index) != coordinate {
+                    output.push(&self.neuron_at(index));
+                }
+            }
+        }
+        else {
+            let excluded: HashSet<Coordinate> = (0..other.len()).map(|index| other.coordinate_at(index)).collect();
+            for index in 0..self.len() {
+                if !excluded.contains(&self.coordinate_at(index)) {
+                    output.push(&self.neuron_at(index));
+                }
+            }
+        }
+        output
+    }
+
+    fn combine_by_coordinate<F>(&self, other: &NeuronXYZPArrays, duplicate_handling: NeuronXYZPDuplicateHandling, keep: F) -> NeuronXYZPArrays
+    where
+        F: Fn(bool, bool) -> bool
+    {
+        let mut output = NeuronXYZPArrays::with_capacity(self.len().max(other.len()));
+        if self.is_sorted_by_coordinate() && other.is_sorted_by_coordinate() {
+            let (mut self_index, mut other_index) = (0usize, 0usize);
+            while self_index < self.len() || other_index < other.len() {
+                let coordinate = match (self_index < self.len(), other_index < other.len()) {
+                    (true, true) => self.coordinate_at(self_index).min(other.coordinate_at(other_index)),
+                    (true, false) => self.coordinate_at(self_index),
+                    _ => other.coordinate_at(other_index),
+                };
+                let mut potential: Option<f32> = None;
+                let in_self = self.fold_run(&mut self_index, coordinate, duplicate_handling, &mut potential);
+                let in_other = other.fold_run(&mut other_index, coordinate, duplicate_handling, &mut potential);
+                if keep(in_self, in_other) {
+                    output.push(&NeuronXYZP::new(coordinate.0, coordinate.1, coordinate.2, potential.unwrap()));
+                }
+            }
+            return output;
+        }
+
+        // Unsorted, index every coordinate by its first appearance
+        let mut combined: Vec<(Coordinate, f32, bool, bool)> = Vec::with_capacity(self.len().max(other.len()));
+        let mut positions: HashMap<Coordinate, usize> = HashMap::with_capacity(combined.capacity());
+        for (source, is_other) in [(self, false), (other, true)] {
+            for index in 0..source.len() {
+                let coordinate = source.coordinate_at(index);
+                let potential = source.borrow_xyzp_vectors().3[index];
+                match positions.get(&coordinate) {
+                    Some(&position) => {
+                        let entry = &mut combined[position];
+                        entry.1 = combine_potentials(duplicate_handling, entry.1, potential);
+                        if is_other { entry.3 = true } else { entry.2 = true }
+                    }
+                    None => {
+                        positions.insert(coordinate, combined.len());
+                        combined.push((coordinate, potential, !is_other, is_other));
+                    }
+                }
+            }
+        }
+        for (coordinate, potential, in_self, in_other) in combined {
+            if keep(in_self, in_other) {
+                output.push(&NeuronXYZP::new(coordinate.0, coordinate.1, coordinate.2, potential));
+            }
+        }
+        output
+    }
+
+    /// Folds the run of neurons at the given coordinate starting at `index` into `potential`,
+    /// advancing `index` past it. Returns true if the run was not empty.
+    fn fold_run(&self, index: &mut usize, coordinate: Coordinate, duplicate_handling: NeuronXYZPDuplicateHandling, potential: &mut Option<f32>) -> bool {
+        let potentials = self.borrow_xyzp_vectors().3;
+        let start = *index;
+        while *index < self.len() && self.coordinate_at(*index) == coordinate {
+            *potential = Some(match *potential {
+                Some(existing) => combine_potentials(duplicate_handling, existing, potentials[*index]),
+                None => potentials[*index],
+            });
+            *index += 1;
+        }
+        *index != start
+    }
+
+    fn coordinate_at(&self, index: usize) -> Coordinate {
+        let (x, y, z, _) = self.borrow_xyzp_vectors();
+        (x[index], y[index], z[index])
+    }
+
+    fn neuron_at(&self, index: usize) -> NeuronXYZP {
+        let (x, y, z, p) = self.borrow_xyzp_vectors();
+        NeuronXYZP::new(x[index], y[index], z[index], p[index])
+    }
+}
+
+fn combine_potentials(duplicate_handling: NeuronXYZPDuplicateHandling, existing: f32, incoming: f32) -> f32 {
+    match duplicate_handling {
+        NeuronXYZPDuplicateHandling::Max => existing.max(incoming),
+        NeuronXYZPDuplicateHandling::Sum => existing + incoming,
+        NeuronXYZPDuplicateHandling::Last => incoming,
+    }
+}
+
+//endregion
+
+//region CorticalMappedXYZPNeuronData
+
+impl CorticalMappedXYZPNeuronData {
+
+    /// Returns the union of both, merging the neurons of cortical areas present in both.
+    ///
+    /// See `NeuronXYZPArrays::merge`.
+    ///
+    /// # Examples
+    /// ```
+    /// use feagi_core_data_structures_and_processing::genomic_structures::{CorticalID, CorticalType};
+    /// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZP, NeuronXYZPArrays, NeuronXYZPDuplicateHandling};
+    ///
+    /// let camera = CorticalID::from_string("iic400".to_string()).unwrap();
+    /// let custom = CorticalID::from_bytes(b"cAAAAA").unwrap();
+    /// let mut agent_a = CorticalMappedXYZPNeuronData::new();
+    /// agent_a.insert(camera, NeuronXYZPArrays::new_from_vectors(vec![0], vec![0], vec![0], vec![0.5]).unwrap());
+    /// let mut agent_b = CorticalMappedXYZPNeuronData::new();
+    /// agent_b.insert(camera, NeuronXYZPArrays::new_from_vectors(vec![0], vec![0], vec![0], vec![0.75]).unwrap());
+    /// agent_b.insert(custom, NeuronXYZPArrays::new_from_vectors(vec![1], vec![1], vec![1], vec![1.0]).unwrap());
+    ///
+    /// let merged = agent_a.merge(&agent_b, NeuronXYZPDuplicateHandling::Max);
+    /// assert_eq!(merged.get_neurons_of(&camera).unwrap().copy_as_neuron_xyzp_vec(), vec![NeuronXYZP::new(0, 0, 0, 0.75)]);
+    ///
+    /// let sensors_only = merged.filter_by_cortical_type(CorticalType::is_type_sensor);
+    /// assert!(sensors_only.contains_cortical_id(&camera));
+    /// assert!(!sensors_only.contains_cortical_id(&custom));
+    /// ```
+    pub fn merge(&self, other: &CorticalMappedXYZPNeuronData, duplicate_handling: NeuronXYZPDuplicateHandling) -> CorticalMappedXYZPNeuronData {
+        let mut output = CorticalMappedXYZPNeuronData::new_with_capacity(self.len().max(other.len()));
+        for (cortical_id, neurons) in &self.mappings {
+            let merged = match other.get_neurons_of(cortical_id) {
+                Some(other_neurons) => neurons.merge(other_neurons, duplicate_handling),
+                None => neurons.clone(),
+            };
+            output.insert(*cortical_id, merged);
+        }
+        for (cortical_id, neurons) in &other.mappings {
+            if !self.contains_cortical_id(cortical_id) {
+                output.insert(*cortical_id, neurons.clone());
+            }
+        }
+        output
+    }
+
+    /// Returns the cortical areas present in both, holding the intersection of their neurons.
+    ///
+    /// See `NeuronXYZPArrays::intersection`. Areas whose intersection is empty are kept, empty.
+    pub fn intersection(&self, other: &CorticalMappedXYZPNeuronData, duplicate_handling: NeuronXYZPDuplicateHandling) -> CorticalMappedXYZPNeuronData {
+        let mut output = CorticalMappedXYZPNeuronData::new();
+        for (cortical_id, neurons) in &self.mappings {
+            if let Some(other_neurons) = other.get_neurons_of(cortical_id) {
+                output.insert(*cortical_id, neurons.intersection(other_neurons, duplicate_handling));
+            }
+        }
+        output
+    }
+
+    /// Returns every cortical area of this, without the neurons at coordinates present in the same
+    /// area of `other`. Useful for subtracting a baseline.
+    ///
+    /// See `NeuronXYZPArrays::difference`. Areas left without neurons are kept, empty.
+    pub fn difference(&self, other: &CorticalMappedXYZPNeuronData) -> CorticalMappedXYZPNeuronData {
+        let mut output = CorticalMappedXYZPNeuronData::new_with_capacity(self.len());
+        for (cortical_id, neurons) in &self.mappings {
+            let remaining = match other.get_neurons_of(cortical_id) {
+                Some(other_neurons) => neurons.difference(other_neurons),
+                None => neurons.clone(),
+            };
+            output.insert(*cortical_id, remaining);
+        }
+        output
+    }
+
+    /// Returns only the cortical areas whose type matches the predicate, such as
+    /// `CorticalType::is_type_sensor`.
+    pub fn filter_by_cortical_type<F>(&self, predicate: F) -> CorticalMappedXYZPNeuronData
+    where
+        F: Fn(&CorticalType) -> bool
+    {
+        let mut output = CorticalMappedXYZPNeuronData::new();
+        for (cortical_id, neurons) in &self.mappings {
+            if predicate(&cortical_id.get_cortical_type()) {
+                output.insert(*cortical_id, neurons.clone());
+            }
+        }
+        output
+    }
+
+    /// Removes all cortical areas whose type does not match the predicate.
+    pub fn retain_by_cortical_type<F>(&mut self, predicate: F)
+    where
+        F: Fn(&CorticalType) -> bool
+    {
+        self.mappings.retain(|cortical_id, _| predicate(&cortical_id.get_cortical_type()));
+    }
+
+    /// Sorts the neurons of every cortical area by coordinate, so set operations run as merges.
+    pub fn sort_by_coordinate(&mut self) {
+        for neurons in self.iter_mut() {
+            neurons.sort_by_coordinate();
+        }
+    }
+}
+
+//endregion
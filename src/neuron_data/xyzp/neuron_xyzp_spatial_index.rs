@@ -0,0 +1,310 @@
+//! Uniform grid spatial index over a NeuronXYZPArrays.
+//!
+//! `NeuronXYZPArrays::filter_neurons_by_location_bounds` scans every neuron on each call. When
+//! the same burst is queried many times (region of interest lookups, neighborhood activity),
+//! building a [`NeuronXYZPSpatialIndex`] once buckets the neurons into cubic grid cells, so
+//! each query only visits the cells it overlaps.
+//!
+//! All queries return neuron indices into the indexed arrays, or copies of those neurons as a
+//! new NeuronXYZPArrays. Box and radius queries return neurons in their original order, so a
+//! box query gives the same result as `filter_neurons_by_location_bounds`.
+
+use std::collections::{BinaryHeap, HashMap};
+use std::ops::{Range, RangeInclusive};
+use crate::error::{FeagiDataProcessingError, IODataError};
+use crate::neuron_data::xyzp::{NeuronXYZP, NeuronXYZPArrays};
+
+type Cell = (u32, u32, u32);
+
+/// Spatial index over the neurons of a NeuronXYZPArrays, supporting box, radius and k nearest
+/// neighbor queries.
+///
+/// The index borrows the neurons it was built from, so they cannot change while it exists.
+///
+/// # Example
+/// ```rust
+/// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{NeuronXYZP, NeuronXYZPArrays};
+///
+/// let neurons = NeuronXYZPArrays::new_from_vectors(
+///     vec![0, 10, 11, 50],
+///     vec![0, 10, 12, 50],
+///     vec![0, 0, 0, 0],
+///     vec![0.1, 0.2, 0.3, 0.4]
+/// ).unwrap();
+/// let index = neurons.build_spatial_index();
+///
+/// assert_eq!(index.query_box_indices(5..=20, 5..=20, 0..=0), vec![1, 2]);
+/// assert_eq!(index.query_radius_indices((10, 10, 0), 2.5), vec![1, 2]);
+/// assert_eq!(index.query_k_nearest((48, 48, 0), 1).copy_as_neuron_xyzp_vec(), vec![NeuronXYZP::new(50, 50, 0, 0.4)]);
+/// ```
+pub struct NeuronXYZPSpatialIndex<'a> {
+    neurons: &'a NeuronXYZPArrays,
+    cell_size: u32,
+    cells: HashMap<Cell, Range<usize>>,
+    sorted_indices: Vec<usize>,
+}
+
+impl<'a> NeuronXYZPSpatialIndex<'a> {
+
+    /// Target average number of neurons per occupied cell when choosing a cell size automatically.
+    const TARGET_NEURONS_PER_CELL: f64 = 4.0;
+
+    /// Builds an index with a cell size chosen from the neuron count and their bounding box.
+    pub fn new(neurons: &'a NeuronXYZPArrays) -> Self {
+        let cell_size = Self::choose_cell_size(neurons);
+        Self::build(neurons, cell_size)
+    }
+
+    /// Builds an index with the given cell size.
+    ///
+    /// Smaller cells make small queries cheaper, at the cost of visiting more cells for large ones.
+    ///
+    /// # Returns
+    /// * `Ok(NeuronXYZPSpatialIndex)` - The built index
+    /// * `Err(FeagiDataProcessingError)` - If the cell size is 0
+    pub fn new_with_cell_size(neurons: &'a NeuronXYZPArrays, cell_size: u32) -> Result<Self, FeagiDataProcessingError> {
+        if cell_size == 0 {
+            return Err(IODataError::InvalidParameters("Spatial index cell size cannot be 0!".into()).into());
+        }
+        Ok(Self::build(neurons, cell_size))
+    }
+
+    /// Returns the neurons this index was built from.
+    pub fn borrow_neurons(&self) -> &'a NeuronXYZPArrays {
+        self.neurons
+    }
+
+    /// Returns the edge length of the cubic grid cells.
+    pub fn get_cell_size(&self) -> u32 {
+        self.cell_size
+    }
+
+    /// Returns the number of grid cells holding at least one neuron.
+    pub fn get_occupied_cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns the indices of all neurons within the given inclusive bounds, in ascending order.
+    pub fn query_box_indices(&self, x_range: RangeInclusive<u32>, y_range: RangeInclusive<u32>, z_range: RangeInclusive<u32>) -> Vec<usize> {
+        let mut output: Vec<usize> = Vec::new();
+        if x_range.is_empty() || y_range.is_empty() || z_range.is_empty() {
+            return output;
+        }
+        let (x, y, z, _) = self.neurons.borrow_xyzp_vectors();
+        self.for_each_cell_in_box(&x_range, &y_range, &z_range, |indices| {
+            output.extend(indices.iter().copied().filter(|&i| x_range.contains(&x[i]) && y_range.contains(&y[i]) && z_range.contains(&z[i])));
+        });
+        output.sort_unstable();
+        output
+    }
+
+    /// Returns copies of all neurons within the given inclusive bounds, in their original order.
+    ///
+    /// This is the indexed equivalent of `NeuronXYZPArrays::filter_neurons_by_location_bounds`.
+    pub fn query_box(&self, x_range: RangeInclusive<u32>, y_range: RangeInclusive<u32>, z_range: RangeInclusive<u32>) -> NeuronXYZPArrays {
+        self.copy_neurons_at(&self.query_box_indices(x_range, y_range, z_range))
+    }
+
+    /// Returns the indices of all neurons within the given Euclidean distance (inclusive) of a
+    /// point, in ascending order. A negative or NaN radius matches nothing.
+    pub fn query_radius_indices(&self, center: (u32, u32, u32), radius: f32) -> Vec<usize> {
+        if radius.is_nan() || radius < 0.0 {
+            return Vec::new();
+        }
+        let reach = radius.floor().min(u32::MAX as f32) as u32;
+        let bounds = |value: u32| value.saturating_sub(reach)..=value.saturating_add(reach);
+        let radius_squared = radius as f64 * radius as f64;
+        let mut output = self.query_box_indices(bounds(center.0), bounds(center.1), bounds(center.2));
+        output.retain(|&i| self.distance_squared_to(i, center) as f64 <= radius_squared);
+        output
+    }
+
+    /// Returns copies of all neurons within the given Euclidean distance (inclusive) of a point,
+    /// in their original order.
+    pub fn query_radius(&self, center: (u32, u32, u32), radius: f32) -> NeuronXYZPArrays {
+        self.copy_neurons_at(&self.query_radius_indices(center, radius))
+    }
+
+    /// Returns the indices of the `k` neurons nearest to a point, nearest first. Neurons at equal
+    /// distance are ordered by index. Fewer than `k` are returned if there are not enough neurons.
+    pub fn query_k_nearest_indices(&self, center: (u32, u32, u32), k: usize) -> Vec<usize> {
+        if k == 0 || self.neurons.is_empty() {
+            return Vec::new();
+        }
+
+        // Max heap of the best candidates so far, so the worst one can be replaced
+        let mut nearest: BinaryHeap<(u128, usize)> = BinaryHeap::with_capacity(k + 1);
+
+        // Search outwards in shells of cells around the center cell, until no unvisited cell can
+        // be closer than the current worst candidate. Fall back to checking every neuron once
+        // the shells would visit more cells than are occupied.
+        let center_cell = self.cell_of(center);
+        let mut visited_cells: u128 = 0;
+        let mut ring: u32 = 0;
+        loop {
+            visited_cells += Self::ring_cell_count(ring);
+            if visited_cells > self.cells.len() as u128 {
+                nearest.clear();
+                (0..self.neurons.len()).for_each(|i| self.consider_nearest(&mut nearest, k, center, i));
+                break;
+            }
+            self.for_each_cell_in_ring(center_cell, ring, |indices| indices.iter().for_each(|&i| self.consider_nearest(&mut nearest, k, center, i)));
+
+            if nearest.len() == k {
+                // Any neuron in the next ring is at least this far away along one axis
+                let next_ring_minimum_distance = ring as u128 * self.cell_size as u128 + 1;
+                if next_ring_minimum_distance * next_ring_minimum_distance > nearest.peek().unwrap().0 {
+                    break;
+                }
+            }
+            ring += 1;
+        }
+
+        nearest.into_sorted_vec().into_iter().map(|(_, index)| index).collect()
+    }
+
+    /// Returns copies of the `k` neurons nearest to a point, nearest first.
+    pub fn query_k_nearest(&self, center: (u32, u32, u32), k: usize) -> NeuronXYZPArrays {
+        self.copy_neurons_at(&self.query_k_nearest_indices(center, k))
+    }
+
+    /// Returns copies of the neurons at the given indices, in the given order.
+    ///
+    /// # Panics
+    /// If any index is out of bounds of the indexed neurons.
+    pub fn copy_neurons_at(&self, indices: &[usize]) -> NeuronXYZPArrays {
+        let (x, y, z, p) = self.neurons.borrow_xyzp_vectors();
+        let mut output = NeuronXYZPArrays::with_capacity(indices.len());
+        for &i in indices {
+            output.push(&NeuronXYZP::new(x[i], y[i], z[i], p[i]));
+        }
+        output
+    }
+
+    fn build(neurons: &'a NeuronXYZPArrays, cell_size: u32) -> Self {
+        let (x, y, z, _) = neurons.borrow_xyzp_vectors();
+
+        // Group neuron indices by cell into one contiguous vector, each cell owning a range of it
+        let mut keyed: Vec<(Cell, usize)> = (0..neurons.len())
+            .map(|i| ((x[i] / cell_size, y[i] / cell_size, z[i] / cell_size), i))
+            .collect();
+        keyed.sort_unstable();
+        let mut cells: HashMap<Cell, Range<usize>> = HashMap::new();
+        let mut start: usize = 0;
+        for chunk in keyed.chunk_by(|a, b| a.0 == b.0) {
+            cells.insert(chunk[0].0, start..start + chunk.len());
+            start += chunk.len();
+        }
+        let sorted_indices: Vec<usize> = keyed.into_iter().map(|(_, i)| i).collect();
+
+        NeuronXYZPSpatialIndex {
+            neurons,
+            cell_size,
+            cells,
+            sorted_indices,
+        }
+    }
+
+    fn choose_cell_size(neurons: &NeuronXYZPArrays) -> u32 {
+        if neurons.is_empty() {
+            return 1;
+        }
+        let (x, y, z, _) = neurons.borrow_xyzp_vectors();
+        let extent = |values: &Vec<u32>| (values.iter().max().unwrap() - values.iter().min().unwrap()) as f64 + 1.0;
+        let volume_per_cell = extent(x) * extent(y) * extent(z) * Self::TARGET_NEURONS_PER_CELL / neurons.len() as f64;
+        volume_per_cell.cbrt().clamp(1.0, u32::MAX as f64) as u32
+    }
+
+    fn cell_of(&self, position: (u32, u32, u32)) -> Cell {
+        (position.0 / self.cell_size, position.1 / self.cell_size, position.2 / self.cell_size)
+    }
+
+    fn distance_squared_to(&self, index: usize, center: (u32, u32, u32)) -> u128 {
+        let (x, y, z, _) = self.neurons.borrow_xyzp_vectors();
+        let squared = |a: u32, b: u32| (a.abs_diff(b) as u128).pow(2);
+        squared(x[index], center.0) + squared(y[index], center.1) + squared(z[index], center.2)
+    }
+
+    fn indices_of_cell(&self, cell: &Cell) -> Option<&[usize]> {
+        self.cells.get(cell).map(|range| &self.sorted_indices[range.clone()])
+    }
+
+    /// Calls `action` with the neuron indices of every occupied cell overlapping the given box.
+    fn for_each_cell_in_box(&self, x_range: &RangeInclusive<u32>, y_range: &RangeInclusive<u32>, z_range: &RangeInclusive<u32>, mut action: impl FnMut(&[usize])) {
+        let cell_range = |range: &RangeInclusive<u32>| (range.start() / self.cell_size)..=(range.end() / self.cell_size);
+        let (cells_x, cells_y, cells_z) = (cell_range(x_range), cell_range(y_range), cell_range(z_range));
+        let cell_count = |range: &RangeInclusive<u32>| (range.end() - range.start()) as u128 + 1;
+
+        // Large boxes are cheaper to answer by going through the occupied cells instead
+        if cell_count(&cells_x) * cell_count(&cells_y) * cell_count(&cells_z) > self.cells.len() as u128 {
+            for (cell, range) in &self.cells {
+                if cells_x.contains(&cell.0) && cells_y.contains(&cell.1) && cells_z.contains(&cell.2) {
+                    action(&self.sorted_indices[range.clone()]);
+                }
+            }
+            return;
+        }
+        for cell_x in cells_x {
+            for cell_y in cells_y.clone() {
+                for cell_z in cells_z.clone() {
+                    if let Some(indices) = self.indices_of_cell(&(cell_x, cell_y, cell_z)) {
+                        action(indices);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Calls `action` with the neuron indices of every occupied cell at exactly the given
+    /// Chebyshev distance (in cells) from the center cell.
+    fn for_each_cell_in_ring(&self, center: Cell, ring: u32, mut action: impl FnMut(&[usize])) {
+        let ring = ring as i64;
+        let (center_x, center_y, center_z) = (center.0 as i64, center.1 as i64, center.2 as i64);
+        let in_bounds = |value: i64| value >= 0 && value <= u32::MAX as i64;
+        for cell_x in (center_x - ring)..=(center_x + ring) {
+            for cell_y in (center_y - ring)..=(center_y + ring) {
+                if !in_bounds(cell_x) || !in_bounds(cell_y) {
+                    continue;
+                }
+                // Inside the x and y faces, only the two z faces lie on the ring
+                let on_xy_face = (cell_x - center_x).abs() == ring || (cell_y - center_y).abs() == ring;
+                let z_step = if on_xy_face || ring == 0 { 1 } else { 2 * ring as usize };
+                for cell_z in ((center_z - ring)..=(center_z + ring)).step_by(z_step) {
+                    if !in_bounds(cell_z) {
+                        continue;
+                    }
+                    if let Some(indices) = self.indices_of_cell(&(cell_x as u32, cell_y as u32, cell_z as u32)) {
+                        action(indices);
+                    }
+                }
+            }
+        }
+    }
+
+    fn consider_nearest(&self, nearest: &mut BinaryHeap<(u128, usize)>, k: usize, center: (u32, u32, u32), index: usize) {
+        let candidate = (self.distance_squared_to(index, center), index);
+        if nearest.len() < k {
+            nearest.push(candidate);
+        } else if candidate < *nearest.peek().unwrap() {
+            nearest.pop();
+            nearest.push(candidate);
+        }
+    }
+
+    fn ring_cell_count(ring: u32) -> u128 {
+        if ring == 0 {
+            return 1;
+        }
+        let outer = 2 * ring as u128 + 1;
+        outer.pow(3) - (outer - 2).pow(3)
+    }
+}
+
+impl NeuronXYZPArrays {
+
+    /// Builds a spatial index over these neurons, for repeated box, radius and nearest neighbor
+    /// queries. See [`NeuronXYZPSpatialIndex`].
+    pub fn build_spatial_index(&self) -> NeuronXYZPSpatialIndex<'_> {
+        NeuronXYZPSpatialIndex::new(self)
+    }
+}
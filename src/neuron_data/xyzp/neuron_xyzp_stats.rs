@@ -0,0 +1,238 @@
+//! Summary statistics of neuron bursts, for monitoring and logging.
+//!
+//! [`NeuronXYZPStats`] is computed in a single pass over a NeuronXYZPArrays, so it is cheap
+//! enough to compute for every burst. `CorticalMappedXYZPNeuronData::summarize` computes it for
+//! every cortical area at once.
+
+use std::collections::HashMap;
+use crate::error::{FeagiDataProcessingError, IODataError};
+use crate::genomic_structures::CorticalID;
+use crate::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPArrays};
+
+type Coordinate = (u32, u32, u32);
+
+/// Summary statistics of the neurons of one cortical area.
+///
+/// Since neuron arrays are sparse, every listed neuron counts as active. NaN potentials are
+/// counted as active, but excluded from the potential statistics and the histogram.
+///
+/// # Example
+/// ```rust
+/// use feagi_core_data_structures_and_processing::neuron_data::xyzp::NeuronXYZPArrays;
+///
+/// let neurons = NeuronXYZPArrays::new_from_vectors(
+///     vec![0, 2, 4],
+///     vec![1, 1, 7],
+///     vec![0, 0, 3],
+///     vec![0.25, 0.5, 1.0]
+/// ).unwrap();
+/// let stats = neurons.compute_stats();
+///
+/// assert_eq!(stats.get_active_count(), 3);
+/// assert_eq!(stats.get_max_potential(), Some(1.0));
+/// assert_eq!(stats.get_centroid(), Some((2.0, 3.0, 1.0)));
+/// assert_eq!(stats.get_bounding_box(), Some(((0, 1, 0), (4, 7, 3))));
+/// println!("{}", stats);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NeuronXYZPStats {
+    active_count: usize,
+    potential_count: usize,
+    potential_sum: f64,
+    min_potential: f32,
+    max_potential: f32,
+    centroid: Option<(f32, f32, f32)>,
+    bounding_box: Option<(Coordinate, Coordinate)>,
+    histogram_range: (f32, f32),
+    histogram: Vec<u32>,
+}
+
+impl std::fmt::Display for NeuronXYZPStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.active_count == 0 {
+            return write!(f, "NeuronXYZPStats(active: 0)");
+        }
+        write!(f, "NeuronXYZPStats(active: {}", self.active_count)?;
+        if let (Some(mean), Some(min), Some(max)) = (self.get_mean_potential(), self.get_min_potential(), self.get_max_potential()) {
+            write!(f, ", potential: mean {:.4} min {:.4} max {:.4}", mean, min, max)?;
+        }
+        if let (Some((cx, cy, cz)), Some(((min_x, min_y, min_z), (max_x, max_y, max_z)))) = (self.centroid, self.bounding_box) {
+            write!(f, ", centroid: ({:.2}, {:.2}, {:.2}), bounds: ({}, {}, {})..=({}, {}, {})", cx, cy, cz, min_x, min_y, min_z, max_x, max_y, max_z)?;
+        }
+        write!(f, ", histogram [{}, {}]: {:?})", self.histogram_range.0, self.histogram_range.1, self.histogram)
+    }
+}
+
+impl NeuronXYZPStats {
+
+    /// Number of histogram bins used by `NeuronXYZPArrays::compute_stats`.
+    pub const DEFAULT_HISTOGRAM_BIN_COUNT: usize = 10;
+
+    /// Potential range covered by the histogram of `NeuronXYZPArrays::compute_stats`.
+    pub const DEFAULT_HISTOGRAM_RANGE: (f32, f32) = (0.0, 1.0);
+
+    /// Returns the number of neurons in the burst.
+    pub fn get_active_count(&self) -> usize {
+        self.active_count
+    }
+
+    /// Returns the mean potential, or None if there are no (non NaN) potentials.
+    pub fn get_mean_potential(&self) -> Option<f32> {
+        if self.potential_count == 0 {
+            return None;
+        }
+        Some((self.potential_sum / self.potential_count as f64) as f32)
+    }
+
+    /// Returns the smallest potential, or None if there are no (non NaN) potentials.
+    pub fn get_min_potential(&self) -> Option<f32> {
+        if self.potential_count == 0 {
+            return None;
+        }
+        Some(self.min_potential)
+    }
+
+    /// Returns the largest potential, or None if there are no (non NaN) potentials.
+    pub fn get_max_potential(&self) -> Option<f32> {
+        if self.potential_count == 0 {
+            return None;
+        }
+        Some(self.max_potential)
+    }
+
+    /// Returns the mean (x, y, z) position of the neurons, unweighted by potential, or None if
+    /// there are no neurons.
+    pub fn get_centroid(&self) -> Option<(f32, f32, f32)> {
+        self.centroid
+    }
+
+    /// Returns the inclusive (min, max) corners of the box holding every neuron, or None if
+    /// there are no neurons.
+    pub fn get_bounding_box(&self) -> Option<(Coordinate, Coordinate)> {
+        self.bounding_box
+    }
+
+    /// Returns the (start, end) potential range the histogram covers.
+    pub fn get_histogram_range(&self) -> (f32, f32) {
+        self.histogram_range
+    }
+
+    /// Returns the number of potentials in each equally sized bin of the histogram range.
+    ///
+    /// Potentials outside the range are counted in the first or last bin.
+    pub fn get_histogram(&self) -> &[u32] {
+        &self.histogram
+    }
+
+    fn compute(neurons: &NeuronXYZPArrays, histogram_bin_count: usize, histogram_range: (f32, f32)) -> Self {
+        let (x, y, z, p) = neurons.borrow_xyzp_vectors();
+        let mut stats = NeuronXYZPStats {
+            active_count: neurons.len(),
+            potential_count: 0,
+            potential_sum: 0.0,
+            min_potential: f32::INFINITY,
+            max_potential: f32::NEG_INFINITY,
+            centroid: None,
+            bounding_box: None,
+            histogram_range,
+            histogram: vec![0; histogram_bin_count],
+        };
+        if neurons.is_empty() {
+            return stats;
+        }
+
+        let bin_scale = histogram_bin_count as f32 / (histogram_range.1 - histogram_range.0);
+        let last_bin = histogram_bin_count - 1;
+        let mut position_sum: (u64, u64, u64) = (0, 0, 0);
+        let mut minimum: (u32, u32, u32) = (u32::MAX, u32::MAX, u32::MAX);
+        let mut maximum: (u32, u32, u32) = (0, 0, 0);
+        for i in 0..neurons.len() {
+            position_sum = (position_sum.0 + x[i] as u64, position_sum.1 + y[i] as u64, position_sum.2 + z[i] as u64);
+            minimum = (minimum.0.min(x[i]), minimum.1.min(y[i]), minimum.2.min(z[i]));
+            maximum = (maximum.0.max(x[i]), maximum.1.max(y[i]), maximum.2.max(z[i]));
+
+            let potential = p[i];
+            if potential.is_nan() {
+                continue;
+            }
+            stats.potential_count += 1;
+            stats.potential_sum += potential as f64;
+            stats.min_potential = stats.min_potential.min(potential);
+            stats.max_potential = stats.max_potential.max(potential);
+            // Float to int casts saturate, so out of range potentials land in the end bins
+            let bin = (((potential - histogram_range.0) * bin_scale) as usize).min(last_bin);
+            stats.histogram[bin] += 1;
+        }
+
+        let count = neurons.len() as f64;
+        stats.centroid = Some(((position_sum.0 as f64 / count) as f32, (position_sum.1 as f64 / count) as f32, (position_sum.2 as f64 / count) as f32));
+        stats.bounding_box = Some((minimum, maximum));
+        stats
+    }
+}
+
+impl NeuronXYZPArrays {
+
+    /// Computes summary statistics of these neurons, with a histogram of
+    /// `NeuronXYZPStats::DEFAULT_HISTOGRAM_BIN_COUNT` bins over
+    /// `NeuronXYZPStats::DEFAULT_HISTOGRAM_RANGE`.
+    pub fn compute_stats(&self) -> NeuronXYZPStats {
+        NeuronXYZPStats::compute(self, NeuronXYZPStats::DEFAULT_HISTOGRAM_BIN_COUNT, NeuronXYZPStats::DEFAULT_HISTOGRAM_RANGE)
+    }
+
+    /// Computes summary statistics of these neurons, with a potential histogram of the given
+    /// number of bins over the given (start, end) range.
+    ///
+    /// # Returns
+    /// * `Ok(NeuronXYZPStats)` - The computed statistics
+    /// * `Err(FeagiDataProcessingError)` - If the bin count is 0, or the range is empty or not finite
+    pub fn compute_stats_with_histogram(&self, histogram_bin_count: usize, histogram_range: (f32, f32)) -> Result<NeuronXYZPStats, FeagiDataProcessingError> {
+        validate_histogram_parameters(histogram_bin_count, histogram_range)?;
+        Ok(NeuronXYZPStats::compute(self, histogram_bin_count, histogram_range))
+    }
+}
+
+impl CorticalMappedXYZPNeuronData {
+
+    /// Computes summary statistics of the neurons of every cortical area.
+    ///
+    /// # Example
+    /// ```rust
+    /// use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPArrays};
+    /// use feagi_core_data_structures_and_processing::genomic_structures::CorticalID;
+    ///
+    /// let camera = CorticalID::from_string("iic400".to_string()).unwrap();
+    /// let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    /// neuron_data.insert(camera, NeuronXYZPArrays::new_from_vectors(vec![0, 1], vec![0, 0], vec![0, 0], vec![0.2, 0.4]).unwrap());
+    ///
+    /// let summary = neuron_data.summarize();
+    /// assert_eq!(summary[&camera].get_active_count(), 2);
+    /// for (cortical_id, stats) in &summary {
+    ///     println!("{}: {}", cortical_id, stats);
+    /// }
+    /// ```
+    pub fn summarize(&self) -> HashMap<CorticalID, NeuronXYZPStats> {
+        self.mappings.iter().map(|(cortical_id, neurons)| (*cortical_id, neurons.compute_stats())).collect()
+    }
+
+    /// Computes summary statistics of the neurons of every cortical area, with potential
+    /// histograms of the given number of bins over the given (start, end) range.
+    ///
+    /// # Returns
+    /// * `Ok(HashMap<CorticalID, NeuronXYZPStats>)` - The statistics of each cortical area
+    /// * `Err(FeagiDataProcessingError)` - If the bin count is 0, or the range is empty or not finite
+    pub fn summarize_with_histogram(&self, histogram_bin_count: usize, histogram_range: (f32, f32)) -> Result<HashMap<CorticalID, NeuronXYZPStats>, FeagiDataProcessingError> {
+        validate_histogram_parameters(histogram_bin_count, histogram_range)?;
+        Ok(self.mappings.iter().map(|(cortical_id, neurons)| (*cortical_id, NeuronXYZPStats::compute(neurons, histogram_bin_count, histogram_range))).collect())
+    }
+}
+
+fn validate_histogram_parameters(histogram_bin_count: usize, histogram_range: (f32, f32)) -> Result<(), FeagiDataProcessingError> {
+    if histogram_bin_count == 0 {
+        return Err(IODataError::InvalidParameters("Histogram bin count cannot be 0!".into()).into());
+    }
+    if !histogram_range.0.is_finite() || !histogram_range.1.is_finite() || histogram_range.0 >= histogram_range.1 {
+        return Err(IODataError::InvalidParameters(format!("Histogram range ({}, {}) must be finite, with a start below its end!", histogram_range.0, histogram_range.1)).into());
+    }
+    Ok(())
+}
@@ -91,7 +91,7 @@ macro_rules! sensor_definition {
                 #[doc = "Middle Right peripheral image camera input."]
                 ImageCameraMiddleRight => {
                     friendly_name: "Middle Right Image Camera Input",
-                    base_ascii: b"iic400",
+                    base_ascii: b"iic500",
                     channel_dimension_range: SingleChannelDimensionRange::new(1..u32::MAX, 1..u32::MAX, 1..5),
                     default_coder_type: NeuronCoderVariantType::ImageFrame,
                 },
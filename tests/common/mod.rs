@@ -0,0 +1,15 @@
+//! Fixtures shared between integration tests.
+
+use feagi_core_data_structures_and_processing::genomic_structures::CorticalID;
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZP, NeuronXYZPArrays};
+
+/// Creates neuron data for a single cortical area, holding neurons (i, i + 1, i + 2) with a potential of 0.5.
+pub fn create_neuron_data(cortical_id: &[u8; 6], neuron_count: u32) -> CorticalMappedXYZPNeuronData {
+    let mut neurons = NeuronXYZPArrays::with_capacity(neuron_count as usize);
+    for i in 0..neuron_count {
+        neurons.push(&NeuronXYZP::new(i, i + 1, i + 2, 0.5));
+    }
+    let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    neuron_data.insert(CorticalID::from_bytes(cortical_id).unwrap(), neurons);
+    neuron_data
+}
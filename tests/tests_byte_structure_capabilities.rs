@@ -0,0 +1,120 @@
+mod common;
+
+use serde_json::json;
+use feagi_core_data_structures_and_processing::io_data::FeagiJSON;
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::NeuronXYZPPotentialQuantization;
+use feagi_core_data_structures_and_processing::io_processing::byte_structures::{FeagiByteStructure, FeagiByteStructureCapabilities, FeagiByteStructureCompatible, FeagiByteStructurePayload, FeagiByteStructureType};
+use common::create_neuron_data;
+
+/// Capabilities of a release predating compressed neurons and version 2 multistructs.
+fn create_old_release_capabilities() -> FeagiByteStructureCapabilities {
+    let mut capabilities = FeagiByteStructureCapabilities::new();
+    capabilities.add_supported_version(FeagiByteStructureType::JSON, 1);
+    capabilities.add_supported_version(FeagiByteStructureType::MultiStructHolder, 1);
+    capabilities.add_supported_version(FeagiByteStructureType::NeuronCategoricalXYZP, 1);
+    capabilities
+}
+
+#[test]
+fn test_capabilities_round_trip() {
+    let local = FeagiByteStructureCapabilities::new_local();
+    assert_eq!(local.get_supported_versions(FeagiByteStructureType::MultiStructHolder), vec![1, 2]);
+    assert!(local.is_supported(FeagiByteStructureType::Capabilities, 1));
+
+    let structure = local.as_new_feagi_byte_structure().unwrap();
+    assert_eq!(structure.try_get_structure_type().unwrap(), FeagiByteStructureType::Capabilities);
+    let received = FeagiByteStructureCapabilities::new_from_feagi_byte_structure(&structure).unwrap();
+    assert_eq!(received, local);
+    assert!(received.is_little_endian());
+
+    match FeagiByteStructurePayload::try_from_feagi_byte_structure(&structure).unwrap() {
+        FeagiByteStructurePayload::Capabilities(payload) => assert_eq!(payload, local),
+        _ => panic!("Expected a capabilities payload!"),
+    }
+
+    // Types from a newer release are skipped rather than rejected
+    let mut newer = structure.copy_out_as_byte_vector();
+    newer.extend_from_slice(&[200, 1]);
+    let pair_count = u16::from_le_bytes([newer[4], newer[5]]) + 1;
+    newer[4..6].copy_from_slice(&pair_count.to_le_bytes());
+    let received = FeagiByteStructureCapabilities::new_from_feagi_byte_structure(&FeagiByteStructure::create_from_bytes(newer).unwrap()).unwrap();
+    assert_eq!(received, local);
+
+    // A length not matching the pair count is rejected
+    let mut truncated = structure.copy_out_as_byte_vector();
+    truncated.pop();
+    assert!(FeagiByteStructureCapabilities::new_from_feagi_byte_structure(&FeagiByteStructure::create_from_bytes(truncated).unwrap()).is_err());
+}
+
+#[test]
+fn test_negotiation_with_older_release() {
+    let agreed = FeagiByteStructureCapabilities::new_local().negotiate(&create_old_release_capabilities()).unwrap();
+    assert_eq!(agreed, create_old_release_capabilities());
+    assert_eq!(agreed.get_best_version(FeagiByteStructureType::MultiStructHolder), Some(1));
+
+    let neuron_data = create_neuron_data(b"cAAAAA", 2);
+    let json = FeagiJSON::from_json_value(json!({"agent": "a"}));
+    let neurons = agreed.create_outgoing(&neuron_data).unwrap();
+    let json = agreed.create_outgoing(&json).unwrap();
+
+    let compressed = neuron_data.as_new_feagi_byte_structure_compressed(NeuronXYZPPotentialQuantization::U8).unwrap();
+    assert!(agreed.verify_structure_is_supported(&compressed).is_err());
+    assert!(agreed.create_outgoing_multistruct(vec![&json, &compressed]).is_err());
+
+    let outgoing = agreed.create_outgoing_multistruct(vec![&json, &neurons]).unwrap();
+    assert_eq!(outgoing.try_get_version().unwrap(), 1);
+    assert!(agreed.verify_structure_is_supported(&outgoing).is_ok());
+
+    // Older releases cannot receive more structures than a version 1 multistruct holds
+    let many: Vec<&FeagiByteStructure> = std::iter::repeat_n(&json, 300).collect();
+    assert!(agreed.create_outgoing_multistruct(many.clone()).is_err());
+    let agreed_locally = FeagiByteStructureCapabilities::new_local().negotiate(&FeagiByteStructureCapabilities::new_local()).unwrap();
+    let outgoing = agreed_locally.create_outgoing_multistruct(many).unwrap();
+    assert_eq!(outgoing.try_get_version().unwrap(), 2);
+    assert_eq!(outgoing.contained_structure_count().unwrap(), 300);
+}
+
+/// Builds a version 1 multistruct by hand, as the combining functions never nest version 1 containers.
+fn create_v1_multistruct(children: &[&[u8]]) -> FeagiByteStructure {
+    let mut bytes = vec![FeagiByteStructureType::MultiStructHolder as u8, 1, children.len() as u8];
+    let mut data_position = bytes.len() + 8 * children.len();
+    for child in children {
+        bytes.extend_from_slice(&(data_position as u32).to_le_bytes());
+        bytes.extend_from_slice(&(child.len() as u32).to_le_bytes());
+        data_position += child.len();
+    }
+    for child in children {
+        bytes.extend_from_slice(child);
+    }
+    FeagiByteStructure::create_from_bytes(bytes).unwrap()
+}
+
+#[test]
+fn test_outgoing_multistructs_are_repacked_without_nesting() {
+    let agreed = FeagiByteStructureCapabilities::new_local().negotiate(&create_old_release_capabilities()).unwrap();
+    let json = FeagiJSON::from_json_value(json!({"agent": "a"})).as_new_feagi_byte_structure().unwrap();
+    let neurons = create_neuron_data(b"cAAAAA", 2).as_new_feagi_byte_structure().unwrap();
+    let inner = create_v1_multistruct(&[json.borrow_data_as_slice(), neurons.borrow_data_as_slice()]);
+    let nested = create_v1_multistruct(&[inner.borrow_data_as_slice(), json.borrow_data_as_slice()]);
+    let relay = FeagiByteStructure::create_nested_from_multiple_existing(vec![&inner, &json]).unwrap();
+
+    // Nested inputs, alone or combined, go out as a flat version 1 container of their leaves
+    for existing in [vec![&nested], vec![&relay], vec![&nested, &json]] {
+        let outgoing = agreed.create_outgoing_multistruct(existing).unwrap();
+        assert_eq!(outgoing.try_get_version().unwrap(), FeagiByteStructure::SUPPORTED_VERSION_MULTI_STRUCT);
+        let types = outgoing.get_ordered_object_types().unwrap();
+        assert!(types.len() >= 3);
+        assert!(types.iter().all(|structure_type| *structure_type != FeagiByteStructureType::MultiStructHolder));
+    }
+}
+
+#[test]
+fn test_negotiation_rejects_big_endian_peer() {
+    let mut bytes = create_old_release_capabilities().as_new_feagi_byte_structure().unwrap().copy_out_as_byte_vector();
+    bytes.swap(2, 3);
+    bytes.swap(4, 5);
+    let big_endian = FeagiByteStructureCapabilities::new_from_feagi_byte_structure(&FeagiByteStructure::create_from_bytes(bytes).unwrap()).unwrap();
+    assert!(!big_endian.is_little_endian());
+    assert_eq!(big_endian.get_supported_pairs(), create_old_release_capabilities().get_supported_pairs());
+    assert!(FeagiByteStructureCapabilities::new_local().negotiate(&big_endian).is_err());
+}
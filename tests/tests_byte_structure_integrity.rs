@@ -0,0 +1,109 @@
+mod common;
+
+use feagi_core_data_structures_and_processing::error::{FeagiBytesError, FeagiDataProcessingError};
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::CorticalMappedXYZPNeuronData;
+use feagi_core_data_structures_and_processing::genomic_structures::CorticalID;
+use feagi_core_data_structures_and_processing::io_processing::byte_structures::{FeagiByteStructure, FeagiByteStructureCompatible, FeagiByteStructureReader, FeagiByteStructureType, FeagiByteStructureWriter};
+use common::create_neuron_data;
+
+const KEY: &[u8] = b"agent shared secret";
+
+fn create_neuron_structure() -> FeagiByteStructure {
+    create_neuron_data(b"cAAAAA", 3).as_new_feagi_byte_structure().unwrap()
+}
+
+fn is_integrity_error(result: Result<FeagiByteStructure, FeagiDataProcessingError>) -> bool {
+    matches!(result, Err(FeagiDataProcessingError::FeagiBytes(FeagiBytesError::IntegrityCheckFailed(_))))
+}
+
+#[test]
+fn test_sealed_structure_detects_corruption() {
+    let original = create_neuron_structure();
+    let sealed = FeagiByteStructure::create_sealed(&original, None).unwrap();
+    assert_eq!(sealed.try_get_structure_type().unwrap(), FeagiByteStructureType::IntegritySealed);
+
+    // Loading sealed bytes verifies and unwraps them
+    let loaded = FeagiByteStructure::create_from_bytes(sealed.copy_out_as_byte_vector()).unwrap();
+    assert_eq!(loaded.borrow_data_as_slice(), original.borrow_data_as_slice());
+    let neuron_data = CorticalMappedXYZPNeuronData::new_from_feagi_byte_structure(&loaded).unwrap();
+    assert_eq!(neuron_data.get_neurons_of(&CorticalID::from_bytes(b"cAAAAA").unwrap()).unwrap().len(), 3);
+
+    // Flipping a single potential bit keeps all lengths valid, but fails the checksum
+    let mut corrupted = sealed.copy_out_as_byte_vector();
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 0b0000_0100;
+    assert!(is_integrity_error(FeagiByteStructure::create_from_bytes(corrupted)));
+
+    let mut truncated = sealed.copy_out_as_byte_vector();
+    truncated.truncate(truncated.len() - 4);
+    assert!(is_integrity_error(FeagiByteStructure::create_from_bytes(truncated)));
+
+    assert!(FeagiByteStructure::create_sealed(&sealed, None).is_err());
+}
+
+#[test]
+fn test_sealed_structure_authentication() {
+    let original = create_neuron_structure();
+    let tagged = FeagiByteStructure::create_sealed(&original, Some(KEY)).unwrap();
+    let untagged = FeagiByteStructure::create_sealed(&original, None).unwrap();
+
+    let loaded = FeagiByteStructure::create_from_bytes_authenticated(tagged.copy_out_as_byte_vector(), KEY).unwrap();
+    assert_eq!(loaded.borrow_data_as_slice(), original.borrow_data_as_slice());
+    assert!(is_integrity_error(FeagiByteStructure::create_from_bytes_authenticated(tagged.copy_out_as_byte_vector(), b"some other key")));
+    assert!(is_integrity_error(FeagiByteStructure::create_from_bytes_authenticated(untagged.copy_out_as_byte_vector(), KEY)));
+    assert!(is_integrity_error(FeagiByteStructure::create_from_bytes_authenticated(original.copy_out_as_byte_vector(), KEY)));
+
+    // Tampering with the payload while fixing up the checksum is still caught by the tag
+    let mut tampered = tagged.copy_out_as_byte_vector();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 0b0000_0100;
+    let crc = crc32_ieee(&tampered[39..]);
+    tampered[3..7].copy_from_slice(&crc.to_le_bytes());
+    assert!(is_integrity_error(FeagiByteStructure::create_from_bytes_authenticated(tampered.clone(), KEY)));
+
+    // Without a key, tagged envelopes are rejected rather than accepted on their checksum alone
+    assert!(is_integrity_error(FeagiByteStructure::create_from_bytes(tampered)));
+    assert!(is_integrity_error(FeagiByteStructure::create_from_bytes(tagged.copy_out_as_byte_vector())));
+}
+
+#[test]
+fn test_sealed_structures_through_multistruct_and_stream() {
+    let original = create_neuron_structure();
+    let sealed = FeagiByteStructure::create_sealed(&original, Some(KEY)).unwrap();
+    let untagged = FeagiByteStructure::create_sealed(&original, None).unwrap();
+
+    let multistruct = FeagiByteStructure::create_from_2_existing(&untagged, &sealed).unwrap();
+    let first = multistruct.copy_out_single_byte_structure_from_multistruct(0).unwrap();
+    assert_eq!(first.borrow_data_as_slice(), original.borrow_data_as_slice());
+    assert!(is_integrity_error(multistruct.copy_out_single_byte_structure_from_multistruct(1)));
+
+    let mut writer = FeagiByteStructureWriter::new(Vec::new());
+    writer.write_structure(&sealed).unwrap();
+    writer.write_structure(&original).unwrap();
+    let stream = writer.into_inner();
+    let mut reader = FeagiByteStructureReader::new(stream.as_slice());
+    reader.set_authentication_key(Some(KEY.to_vec()));
+    assert_eq!(reader.read_next().unwrap().unwrap().borrow_data_as_slice(), original.borrow_data_as_slice());
+    assert!(reader.read_next().is_err());
+}
+
+#[test]
+fn test_truncated_sealed_headers_are_rejected() {
+    let sealed_type = FeagiByteStructureType::IntegritySealed as u8;
+    for bytes in [vec![sealed_type], vec![sealed_type, 1]] {
+        assert!(FeagiByteStructure::create_from_bytes(bytes.clone()).is_err());
+        assert!(is_integrity_error(FeagiByteStructure::create_from_bytes_authenticated(bytes, KEY)));
+    }
+}
+
+/// Bitwise CRC32 (IEEE), so the test can forge checksums independently of the library.
+fn crc32_ieee(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
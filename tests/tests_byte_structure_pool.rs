@@ -0,0 +1,57 @@
+mod common;
+
+use serde_json::json;
+use feagi_core_data_structures_and_processing::io_data::FeagiJSON;
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::CorticalMappedXYZPNeuronData;
+use feagi_core_data_structures_and_processing::io_processing::byte_structures::{FeagiByteStructureCompatible, FeagiByteStructurePool};
+use common::create_neuron_data;
+
+#[test]
+fn test_pooled_structures_match_newly_allocated() {
+    let mut pool = FeagiByteStructurePool::new_with_max_pooled_count(2);
+    let json = FeagiJSON::from_json_value(json!({"name": "agent \"a\"", "values": [1.5, -2, null], "nested": {"ok": true}}));
+
+    // Shrinking and growing data through the same buffers always yields exactly the serialized bytes
+    for neuron_count in [100, 3, 50, 0, 200] {
+        let neuron_data = create_neuron_data(b"cAAAAA", neuron_count);
+        let neurons = pool.take_serialized(&neuron_data).unwrap();
+        let status = pool.take_serialized(&json).unwrap();
+        assert_eq!(neurons.borrow_data_as_slice(), neuron_data.as_new_feagi_byte_structure().unwrap().borrow_data_as_slice());
+        assert_eq!(status.borrow_data_as_slice(), json.as_new_feagi_byte_structure().unwrap().borrow_data_as_slice());
+        assert_eq!(status.borrow_data_as_slice().len(), json.max_number_bytes_needed());
+        pool.recycle(neurons);
+        pool.recycle(status);
+        assert_eq!(pool.get_pooled_count(), 2);
+    }
+
+    // Pooled buffers keep their capacity, and the pool does not grow past its maximum
+    let structure = pool.take_empty();
+    assert!(structure.get_wasted_capacity_count() > 0);
+    pool.recycle(structure);
+    let extra = json.as_new_feagi_byte_structure().unwrap();
+    pool.recycle(extra);
+    assert_eq!(pool.get_pooled_count(), 2);
+}
+
+#[test]
+fn test_failed_serialization_keeps_buffer() {
+    let mut pool = FeagiByteStructurePool::new();
+    let structure = pool.take_serialized(&create_neuron_data(b"cAAAAA", 10)).unwrap();
+    pool.recycle(structure);
+    assert_eq!(pool.get_pooled_count(), 1);
+
+    // Empty neuron data cannot be serialized
+    assert!(pool.take_serialized(&CorticalMappedXYZPNeuronData::new()).is_err());
+    assert_eq!(pool.get_pooled_count(), 1);
+
+    let mut target = pool.take_serialized(&create_neuron_data(b"cAAAAA", 10)).unwrap();
+    assert!(CorticalMappedXYZPNeuronData::new().overwrite_feagi_byte_structure(&mut target).is_err());
+    assert!(target.borrow_data_as_slice().is_empty());
+
+    // JSON refuses to write into a slice too small for it
+    let json = FeagiJSON::from_json_value(json!({"key": "value"}));
+    let mut too_small = vec![0u8; json.max_number_bytes_needed() - 1];
+    assert!(json.overwrite_feagi_byte_structure_slice(&mut too_small).is_err());
+    let mut larger = vec![0u8; json.max_number_bytes_needed() + 5];
+    assert_eq!(json.overwrite_feagi_byte_structure_slice(&mut larger).unwrap(), 5);
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use std::io::{ErrorKind, Read};
+use serde_json::json;
+use feagi_core_data_structures_and_processing::io_data::FeagiJSON;
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::CorticalMappedXYZPNeuronData;
+use feagi_core_data_structures_and_processing::genomic_structures::CorticalID;
+use feagi_core_data_structures_and_processing::io_processing::byte_structures::{FeagiByteStructureCompatible, FeagiByteStructureReader, FeagiByteStructureType, FeagiByteStructureWriter};
+use common::create_neuron_data;
+
+/// Reader handing out at most a few bytes per call, and reporting `WouldBlock` whenever it runs
+/// out of the bytes that have "arrived" so far.
+struct TrickleReader {
+    bytes: Vec<u8>,
+    position: usize,
+    arrived: usize,
+    max_per_read: usize,
+}
+
+impl Read for TrickleReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.position == self.arrived {
+            return Err(ErrorKind::WouldBlock.into());
+        }
+        let count = buf.len().min(self.max_per_read).min(self.arrived - self.position);
+        buf[..count].copy_from_slice(&self.bytes[self.position..self.position + count]);
+        self.position += count;
+        Ok(count)
+    }
+}
+
+#[test]
+fn test_stream_more_structures_than_multistruct_allows() {
+    let mut writer = FeagiByteStructureWriter::new(Vec::new());
+    for i in 0..300u32 {
+        writer.write_compatible(&create_neuron_data(b"cAAAAA", i % 7)).unwrap();
+    }
+    writer.write_compatible(&FeagiJSON::from_json_value(json!({"burst": 300}))).unwrap();
+    let stream = writer.into_inner();
+
+    let mut reader = FeagiByteStructureReader::new(stream.as_slice());
+    for i in 0..300u32 {
+        let structure = reader.read_next().unwrap().unwrap();
+        let neuron_data = CorticalMappedXYZPNeuronData::new_from_feagi_byte_structure(&structure).unwrap();
+        assert_eq!(neuron_data.get_neurons_of(&CorticalID::from_bytes(b"cAAAAA").unwrap()).unwrap().len(), (i % 7) as usize);
+    }
+    let structure = reader.read_next().unwrap().unwrap();
+    assert_eq!(structure.try_get_structure_type().unwrap(), FeagiByteStructureType::JSON);
+    assert!(reader.read_next().unwrap().is_none());
+    assert!(reader.is_end_of_stream());
+}
+
+#[test]
+fn test_stream_resumes_partial_frames() {
+    let mut writer = FeagiByteStructureWriter::new(Vec::new());
+    writer.write_compatible(&create_neuron_data(b"cAAAAA", 50)).unwrap();
+    writer.write_compatible(&create_neuron_data(b"cBBBBB", 3)).unwrap();
+    let stream = writer.into_inner();
+    let total_length = stream.len();
+
+    let mut reader = FeagiByteStructureReader::new(TrickleReader { bytes: stream, position: 0, arrived: 0, max_per_read: 3 });
+    let mut received = Vec::new();
+    for arrived in (0..=total_length).step_by(17).chain(std::iter::once(total_length)) {
+        reader.get_mut().arrived = arrived;
+        while let Some(structure) = reader.read_next().unwrap() {
+            received.push(structure.copy_out_as_byte_vector());
+        }
+        assert!(!reader.is_end_of_stream());
+    }
+    let expected: Vec<Vec<u8>> = [create_neuron_data(b"cAAAAA", 50), create_neuron_data(b"cBBBBB", 3)].iter()
+        .map(|neuron_data| neuron_data.as_new_feagi_byte_structure().unwrap().copy_out_as_byte_vector()).collect();
+    assert_eq!(received, expected);
+    assert_eq!(reader.get_buffered_byte_count(), 0);
+}
+
+#[test]
+fn test_stream_pushed_bytes_and_invalid_frames() {
+    let mut writer = FeagiByteStructureWriter::new(Vec::new());
+    writer.write_compatible(&create_neuron_data(b"cAAAAA", 2)).unwrap();
+    let stream = writer.into_inner();
+
+    // Bytes pushed from elsewhere are parsed once the frame completes
+    let mut reader = FeagiByteStructureReader::new(std::io::empty());
+    reader.push_bytes(&stream[..10]);
+    assert!(reader.try_take_buffered().unwrap().is_none());
+    reader.push_bytes(&stream[10..]);
+    assert!(reader.try_take_buffered().unwrap().is_some());
+
+    // A stream ending inside a frame is an error
+    let mut reader = FeagiByteStructureReader::new(&stream[..stream.len() - 1]);
+    assert!(reader.read_next().is_err());
+
+    // An unknown structure type is rejected as soon as its header arrives
+    let mut reader = FeagiByteStructureReader::new(std::io::empty());
+    reader.push_bytes(&[100, 0, 0, 0, 255]);
+    assert!(reader.try_take_buffered().is_err());
+
+    // So are frames claiming to be longer than allowed
+    let mut reader = FeagiByteStructureReader::new(stream.as_slice());
+    reader.set_max_frame_length(8).unwrap();
+    assert!(reader.read_next().is_err());
+    assert!(reader.set_max_frame_length(2).is_err());
+}
@@ -5,6 +5,8 @@ use feagi_core_data_structures_and_processing::genomic_structures::CorticalID;
 use feagi_core_data_structures_and_processing::io_processing::byte_structures::FeagiByteStructure;
 use feagi_core_data_structures_and_processing::io_processing::byte_structures::FeagiByteStructureType;
 use feagi_core_data_structures_and_processing::io_processing::byte_structures::FeagiByteStructureCompatible;
+use feagi_core_data_structures_and_processing::io_processing::byte_structures::FeagiByteStructurePayload;
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::NeuronXYZPPotentialQuantization;
 
 #[test]
 fn test_combined_neuron_json_multistruct_serialize_deserialize() {
@@ -169,4 +171,145 @@ fn test_multistruct_with_multiple_json_and_neuron_structures() {
     }
 
     println!("✓ Successfully handled multi-struct with 4 different structures!");
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_multistruct_with_more_than_255_structures() {
+    let structures: Vec<FeagiByteStructure> = (0..300)
+        .map(|i| FeagiJSON::from_json_value(json!({"agent": i})).as_new_feagi_byte_structure().unwrap())
+        .collect();
+
+    // Up to 255 structures keeps the version 1 format older receivers understand
+    let small = FeagiByteStructure::create_from_multiple_existing(structures.iter().take(255).collect()).unwrap();
+    assert_eq!(small.try_get_version().unwrap(), FeagiByteStructure::SUPPORTED_VERSION_MULTI_STRUCT);
+
+    let combined_structure = FeagiByteStructure::create_from_multiple_existing(structures.iter().collect()).unwrap();
+    assert_eq!(combined_structure.try_get_version().unwrap(), FeagiByteStructure::SUPPORTED_VERSION_MULTI_STRUCT_V2);
+
+    let received_structure = FeagiByteStructure::create_from_bytes(combined_structure.copy_out_as_byte_vector()).unwrap();
+    assert_eq!(received_structure.contained_structure_count().unwrap(), 300);
+    assert_eq!(received_structure.get_ordered_object_types().unwrap().len(), 300);
+    let last = received_structure.copy_out_single_byte_structure_from_multistruct(299).unwrap();
+    assert_eq!(last.borrow_data_as_slice(), structures[299].borrow_data_as_slice());
+    assert!(received_structure.copy_out_single_byte_structure_from_multistruct(300).is_err());
+
+    // Flattening a version 2 container into another still works
+    let flattened = FeagiByteStructure::create_from_2_existing(&received_structure, &structures[0]).unwrap();
+    assert_eq!(flattened.contained_structure_count().unwrap(), 301);
+}
+
+#[test]
+fn test_nested_multistruct_visitor() {
+    let json_a = FeagiJSON::from_json_value(json!({"agent": "a"})).as_new_feagi_byte_structure().unwrap();
+    let json_b = FeagiJSON::from_json_value(json!({"agent": "b"})).as_new_feagi_byte_structure().unwrap();
+    let mut neurons = NeuronXYZPArrays::with_capacity(1);
+    neurons.push(&NeuronXYZP::new(1, 2, 3, 0.5));
+    let mut neuron_mappings = CorticalMappedXYZPNeuronData::new();
+    neuron_mappings.insert(CorticalID::from_bytes(b"cAAAAA").unwrap(), neurons);
+    let neuron_bytes = neuron_mappings.as_new_feagi_byte_structure().unwrap();
+
+    // Two agents each send a multistruct, which a relay batches without merging them
+    let agent_a = FeagiByteStructure::create_from_2_existing(&json_a, &neuron_bytes).unwrap();
+    let agent_b = FeagiByteStructure::create_from_2_existing(&json_b, &neuron_bytes).unwrap();
+    let relay_batch = FeagiByteStructure::create_nested_from_multiple_existing(vec![&agent_a, &agent_b, &json_a]).unwrap();
+    let outer = FeagiByteStructure::create_nested_from_multiple_existing(vec![&relay_batch]).unwrap();
+
+    let received_structure = FeagiByteStructure::create_from_bytes(outer.copy_out_as_byte_vector()).unwrap();
+    assert_eq!(received_structure.contained_structure_count().unwrap(), 1);
+
+    let mut visited: Vec<(Vec<usize>, FeagiByteStructureType)> = Vec::new();
+    received_structure.visit_leaf_structures(|path, leaf| {
+        visited.push((path.to_vec(), leaf.get_structure_type()));
+        Ok(())
+    }).unwrap();
+    assert_eq!(visited, vec![
+        (vec![0, 0, 0], FeagiByteStructureType::JSON),
+        (vec![0, 0, 1], FeagiByteStructureType::NeuronCategoricalXYZP),
+        (vec![0, 1, 0], FeagiByteStructureType::JSON),
+        (vec![0, 1, 1], FeagiByteStructureType::NeuronCategoricalXYZP),
+        (vec![0, 2], FeagiByteStructureType::JSON),
+    ]);
+
+    let agent_b_json = received_structure.copy_out_structure_at_path(&[0, 1, 0]).unwrap();
+    assert_eq!(agent_b_json.borrow_data_as_slice(), json_b.borrow_data_as_slice());
+    assert!(received_structure.copy_out_structure_at_path(&[0, 2, 0]).is_err());
+    assert!(received_structure.copy_out_structure_at_path(&[0, 3]).is_err());
+
+    // Errors from the visitor stop the walk
+    let mut visit_count = 0;
+    let result = received_structure.visit_leaf_structures(|_, _| {
+        visit_count += 1;
+        Err(feagi_core_data_structures_and_processing::error::FeagiDataProcessingError::NotImplemented)
+    });
+    assert!(result.is_err());
+    assert_eq!(visit_count, 1);
+
+    // A single structure is its own only leaf
+    let mut leaf_count = 0;
+    json_a.visit_leaf_structures(|path, _| {
+        assert!(path.is_empty());
+        leaf_count += 1;
+        Ok(())
+    }).unwrap();
+    assert_eq!(leaf_count, 1);
+}
+
+#[test]
+fn test_flattening_nested_multistructs_reaches_leaves() {
+    let json_a = FeagiJSON::from_json_value(json!({"agent": "a"})).as_new_feagi_byte_structure().unwrap();
+    let json_b = FeagiJSON::from_json_value(json!({"agent": "b"})).as_new_feagi_byte_structure().unwrap();
+    let agent_a = FeagiByteStructure::create_from_2_existing(&json_a, &json_b).unwrap();
+    let relay = FeagiByteStructure::create_nested_from_multiple_existing(vec![&agent_a, &json_a]).unwrap();
+
+    // Flattening the version 2 relay leaves no nesting, so the result stays readable as version 1
+    let flattened = FeagiByteStructure::create_from_multiple_existing(vec![&relay, &json_b]).unwrap();
+    assert_eq!(flattened.try_get_version().unwrap(), FeagiByteStructure::SUPPORTED_VERSION_MULTI_STRUCT);
+    assert_eq!(flattened.contained_structure_count().unwrap(), 4);
+    let types = flattened.get_ordered_object_types().unwrap();
+    assert!(types.iter().all(|structure_type| *structure_type != FeagiByteStructureType::MultiStructHolder));
+    let expected = [&json_a, &json_b, &json_a, &json_b];
+    for (index, expected) in expected.iter().enumerate() {
+        assert_eq!(flattened.copy_out_single_byte_structure_from_multistruct(index).unwrap().borrow_data_as_slice(), expected.borrow_data_as_slice());
+    }
+}
+
+#[test]
+fn test_decode_all_typed_payloads() {
+    let json_structure = FeagiJSON::from_json_value(json!({"agent": "a"})).as_new_feagi_byte_structure().unwrap();
+    let mut neurons = NeuronXYZPArrays::with_capacity(2);
+    neurons.push(&NeuronXYZP::new(1, 2, 3, 0.5));
+    neurons.push(&NeuronXYZP::new(4, 5, 6, 1.0));
+    let mut neuron_mappings = CorticalMappedXYZPNeuronData::new();
+    neuron_mappings.insert(CorticalID::from_bytes(b"cAAAAA").unwrap(), neurons);
+    let neuron_structure = neuron_mappings.as_new_feagi_byte_structure().unwrap();
+    let compressed_structure = neuron_mappings.as_new_feagi_byte_structure_compressed(NeuronXYZPPotentialQuantization::Float32).unwrap();
+    let sealed_structure = FeagiByteStructure::create_sealed(&json_structure, None).unwrap();
+
+    let agent = FeagiByteStructure::create_from_2_existing(&json_structure, &neuron_structure).unwrap();
+    let relay_batch = FeagiByteStructure::create_nested_from_multiple_existing(vec![&agent, &compressed_structure, &sealed_structure]).unwrap();
+
+    let payloads = relay_batch.decode_all().unwrap();
+    assert_eq!(payloads.len(), 4);
+    let mut json_count = 0;
+    let mut neuron_count = 0;
+    for payload in &payloads {
+        match payload {
+            FeagiByteStructurePayload::Json(json) => {
+                assert_eq!(json.borrow_json_value()["agent"], "a");
+                json_count += 1;
+            }
+            FeagiByteStructurePayload::NeuronXYZP(neuron_data) => {
+                assert_eq!(neuron_data.get_neurons_of(&CorticalID::from_bytes(b"cAAAAA").unwrap()).unwrap().len(), 2);
+                neuron_count += 1;
+            }
+            _ => panic!("Unexpected payload type!"),
+        }
+    }
+    assert_eq!((json_count, neuron_count), (2, 2));
+    assert_eq!(payloads[2].get_structure_type(), FeagiByteStructureType::NeuronCategoricalXYZP);
+
+    // Single structures decode to a single payload, and multistructs cannot be a single payload
+    assert_eq!(json_structure.decode_all().unwrap().len(), 1);
+    assert!(FeagiByteStructurePayload::try_from_feagi_byte_structure(&agent).is_err());
+    let payload = FeagiByteStructurePayload::try_from_feagi_byte_structure(&sealed_structure).unwrap();
+    assert_eq!(payload.as_new_feagi_byte_structure().unwrap().borrow_data_as_slice(), json_structure.borrow_data_as_slice());
+}
@@ -0,0 +1,102 @@
+use ndarray::Array3;
+use feagi_core_data_structures_and_processing::io_data::{ImageFrame, ImageFramePixelQuantization, SegmentedImageFrame};
+use feagi_core_data_structures_and_processing::io_data::image_descriptors::{ColorChannelLayout, ColorSpace, MemoryOrderLayout, SegmentedFrameTargetResolutions};
+use feagi_core_data_structures_and_processing::io_processing::byte_structures::{FeagiByteStructure, FeagiByteStructureCompatible, FeagiByteStructurePayload, FeagiByteStructureType};
+
+fn create_gradient_frame(width: usize, height: usize, channel_count: usize, color_space: &ColorSpace) -> ImageFrame {
+    let array = Array3::from_shape_fn((height, width, channel_count), |(y, x, c)| {
+        ((y * width + x) * channel_count + c) as f32 / (width * height * channel_count) as f32
+    });
+    ImageFrame::from_array(array, color_space, &MemoryOrderLayout::HeightsWidthsChannels).unwrap()
+}
+
+fn max_pixel_difference(a: &Array3<f32>, b: &Array3<f32>) -> f32 {
+    a.iter().zip(b.iter()).map(|(a, b)| (a - b).abs()).fold(0.0, f32::max)
+}
+
+#[test]
+fn test_image_frame_byte_structure_round_trip() {
+    let frame = create_gradient_frame(7, 5, 3, &ColorSpace::Gamma);
+
+    let lossless = frame.as_new_feagi_byte_structure().unwrap();
+    assert_eq!(lossless.try_get_structure_type().unwrap(), FeagiByteStructureType::ImageFrame);
+    let received = ImageFrame::new_from_feagi_byte_structure(&lossless).unwrap();
+    assert_eq!(received.get_image_frame_properties(), frame.get_image_frame_properties());
+    assert_eq!(received.get_internal_data(), frame.get_internal_data());
+
+    let quantized = frame.as_new_feagi_byte_structure_quantized(ImageFramePixelQuantization::U8).unwrap();
+    assert_eq!(quantized.borrow_data_as_slice().len(), 13 + 7 * 5 * 3);
+    let received = ImageFrame::new_from_feagi_byte_structure(&quantized).unwrap();
+    assert_eq!(received.get_image_frame_properties(), frame.get_image_frame_properties());
+    assert!(max_pixel_difference(received.get_internal_data(), frame.get_internal_data()) <= 0.5 / 255.0 + f32::EPSILON);
+
+    // Through the typed payload as well
+    match FeagiByteStructurePayload::try_from_feagi_byte_structure(&lossless).unwrap() {
+        FeagiByteStructurePayload::ImageFrame(payload) => assert_eq!(payload.get_internal_data(), frame.get_internal_data()),
+        _ => panic!("Expected an image frame payload!"),
+    }
+}
+
+#[test]
+fn test_segmented_image_frame_byte_structure_round_trip() {
+    let resolutions = SegmentedFrameTargetResolutions::new((1, 2), (3, 4), (5, 6), (7, 8), (9, 10), (11, 12), (13, 14), (15, 16), (32, 24)).unwrap();
+    let mut segmented = SegmentedImageFrame::new(&resolutions, &ColorSpace::Linear, &ColorChannelLayout::RGB, &ColorChannelLayout::GrayScale).unwrap();
+    for (index, frame) in segmented.get_mut_ordered_image_frame_references().into_iter().enumerate() {
+        let (width, height) = frame.get_cartesian_width_height();
+        let channel_count = frame.get_color_channel_count();
+        let filled = create_gradient_frame(width, height, channel_count, &ColorSpace::Linear);
+        *frame = ImageFrame::from_array(filled.get_internal_data().mapv(|value| value * (index + 1) as f32 / 9.0), &ColorSpace::Linear, &MemoryOrderLayout::HeightsWidthsChannels).unwrap();
+    }
+
+    for quantization in [ImageFramePixelQuantization::Float32, ImageFramePixelQuantization::U8] {
+        let structure = segmented.as_new_feagi_byte_structure_quantized(quantization).unwrap();
+        assert_eq!(structure.try_get_structure_type().unwrap(), FeagiByteStructureType::SegmentedImageFrame);
+        let received = SegmentedImageFrame::new_from_feagi_byte_structure(&structure).unwrap();
+        assert_eq!(received.get_segmented_image_frame_properties(), segmented.get_segmented_image_frame_properties());
+        let tolerance = if quantization == ImageFramePixelQuantization::Float32 { 0.0 } else { 0.5 / 255.0 + f32::EPSILON };
+        for (received, sent) in received.get_image_internal_data().iter().zip(segmented.get_image_internal_data().iter()) {
+            assert!(max_pixel_difference(received, sent) <= tolerance);
+        }
+    }
+}
+
+#[test]
+fn test_image_frame_byte_structure_rejects_invalid_bytes() {
+    let frame = create_gradient_frame(4, 4, 1, &ColorSpace::Linear);
+    let bytes = frame.as_new_feagi_byte_structure_quantized(ImageFramePixelQuantization::U8).unwrap().copy_out_as_byte_vector();
+
+    let mut truncated = bytes.clone();
+    truncated.pop();
+    assert!(ImageFrame::new_from_feagi_byte_structure(&FeagiByteStructure::create_from_bytes(truncated).unwrap()).is_err());
+    assert!(ImageFrame::new_from_feagi_byte_structure(&FeagiByteStructure::create_from_bytes(bytes[..8].to_vec()).unwrap()).is_err());
+
+    let mut unknown_quantization = bytes.clone();
+    unknown_quantization[12] = 200;
+    assert!(ImageFrame::new_from_feagi_byte_structure(&FeagiByteStructure::create_from_bytes(unknown_quantization).unwrap()).is_err());
+
+    let mut zero_width = bytes.clone();
+    zero_width[2..6].copy_from_slice(&0u32.to_le_bytes());
+    assert!(ImageFrame::new_from_feagi_byte_structure(&FeagiByteStructure::create_from_bytes(zero_width).unwrap()).is_err());
+
+    let mut too_small = vec![0u8; 10];
+    assert!(frame.overwrite_feagi_byte_structure_slice_quantized(&mut too_small, ImageFramePixelQuantization::U8).is_err());
+}
+
+#[test]
+fn test_image_frame_byte_structure_rejects_oversized_resolutions() {
+    // A bare header claiming a huge resolution must fail before any pixels are allocated
+    let frame = create_gradient_frame(2, 2, 4, &ColorSpace::Linear);
+    let mut header = frame.as_new_feagi_byte_structure().unwrap().copy_out_as_byte_vector()[..13].to_vec();
+    header[2..6].copy_from_slice(&65535u32.to_le_bytes());
+    header[6..10].copy_from_slice(&65535u32.to_le_bytes());
+    header.extend_from_slice(&[0u8; 8]);
+    assert!(ImageFrame::new_from_feagi_byte_structure(&FeagiByteStructure::create_from_bytes(header).unwrap()).is_err());
+
+    let resolutions = SegmentedFrameTargetResolutions::create_with_same_sized_peripheral((2, 2), (1, 1)).unwrap();
+    let segmented = SegmentedImageFrame::new(&resolutions, &ColorSpace::Linear, &ColorChannelLayout::RGBA, &ColorChannelLayout::RGBA).unwrap();
+    let mut header = segmented.as_new_feagi_byte_structure().unwrap().copy_out_as_byte_vector()[..78].to_vec();
+    for resolution_bytes in header[6..].chunks_mut(4) {
+        resolution_bytes.copy_from_slice(&u32::MAX.to_le_bytes());
+    }
+    assert!(SegmentedImageFrame::new_from_feagi_byte_structure(&FeagiByteStructure::create_from_bytes(header).unwrap()).is_err());
+}
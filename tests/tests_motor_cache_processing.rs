@@ -0,0 +1,56 @@
+//! Test for motor cache decoding neuron data through stream processors into callbacks.
+//!
+//! This test demonstrates registering a rotary motor, feeding it neuron data as if it
+//! came from FEAGI, and reading the decoded and scaled value both directly and by callback.
+
+use std::sync::{Arc, Mutex};
+use feagi_core_data_structures_and_processing::genomic_structures::{CorticalID, MotorCorticalType};
+use feagi_core_data_structures_and_processing::io_data::IOTypeData;
+use feagi_core_data_structures_and_processing::io_processing::MotorCache;
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZP, NeuronXYZPArrays};
+
+#[test]
+fn test_rotary_motor_decoding_with_callbacks() -> Result<(), Box<dyn std::error::Error>> {
+    let mut motor_cache = MotorCache::new();
+
+    // Register rotary motor group 0 with 2 channels, mapping [-1, 1] to [-90, 90]
+    motor_cache.register_cortical_group_for_rotary_motor(0.into(), 2, 10, -90.0, 90.0)?;
+
+    // Before anything is decoded, the motor is at rest
+    assert_eq!(motor_cache.read_data_for_rotary_motor(0.into(), 1.into())?, 0.0);
+
+    let received: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(Vec::new()));
+    let received_clone = received.clone();
+    let callback_id = motor_cache.register_callback(MotorCorticalType::RotaryMotor, 0.into(), 1.into(), Box::new(move |value: &IOTypeData| {
+        received_clone.lock().unwrap().push(f32::try_from(value).unwrap());
+    }))?;
+
+    // Channel 1 occupies X indexes 2 (negative) and 3 (positive)
+    let cortical_id = CorticalID::new_motor_cortical_area_id(MotorCorticalType::RotaryMotor, 0.into())?;
+    let mut neurons = NeuronXYZPArrays::with_capacity(1);
+    neurons.push(&NeuronXYZP::new(3, 0, 0, 0.5));
+    let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    neuron_data.insert(cortical_id, neurons);
+
+    motor_cache.decode_from_neurons(&neuron_data)?;
+    assert_eq!(motor_cache.read_data_for_rotary_motor(0.into(), 1.into())?, 45.0);
+    assert_eq!(motor_cache.read_data_for_rotary_motor(0.into(), 0.into())?, 0.0);
+    assert_eq!(*received.lock().unwrap(), vec![45.0]);
+
+    // Negative direction
+    let mut neurons = NeuronXYZPArrays::with_capacity(1);
+    neurons.push(&NeuronXYZP::new(2, 0, 0, 1.0));
+    neuron_data.insert(cortical_id, neurons);
+    motor_cache.decode_from_neurons(&neuron_data)?;
+    assert_eq!(motor_cache.read_data_for_rotary_motor(0.into(), 1.into())?, -90.0);
+    assert_eq!(*received.lock().unwrap(), vec![45.0, -90.0]);
+
+    // Areas not present in the burst keep their previous value and do not fire callbacks
+    motor_cache.decode_from_neurons(&CorticalMappedXYZPNeuronData::new())?;
+    assert_eq!(motor_cache.read_data_for_rotary_motor(0.into(), 1.into())?, -90.0);
+    assert_eq!(received.lock().unwrap().len(), 2);
+
+    assert!(motor_cache.deregister_callback(MotorCorticalType::RotaryMotor, 0.into(), 1.into(), callback_id)?);
+    assert!(motor_cache.read_data_for_rotary_motor(0.into(), 2.into()).is_err());
+    Ok(())
+}
@@ -0,0 +1,128 @@
+//! Tests for encoding values into neurons with the built-in coders and decoding them back.
+
+use ndarray::Array3;
+use feagi_core_data_structures_and_processing::genomic_structures::{CorticalGroupingIndex, CorticalID, CorticalIOChannelIndex, MotorCorticalType, SensorCorticalType};
+use feagi_core_data_structures_and_processing::io_data::{ImageFrame, IOTypeData, IOTypeVariant, SegmentedImageFrame};
+use feagi_core_data_structures_and_processing::io_data::image_descriptors::{ColorChannelLayout, ColorSpace, ImageFrameProperties, MemoryOrderLayout, SegmentedFrameTargetResolutions, SegmentedImageFrameProperties};
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronCoderParameters, NeuronCoderRegistry, NeuronCoderVariantType, NeuronXYZPDecoder, NeuronXYZPEncoder};
+
+const EPSILON: f32 = 0.00001;
+
+type Coders = (Box<dyn NeuronXYZPEncoder + Sync + Send>, Box<dyn NeuronXYZPDecoder + Sync + Send>);
+
+fn create_coders(variant: NeuronCoderVariantType, cortical_ids: Vec<CorticalID>, data_type: IOTypeVariant, z_resolution: u32) -> Coders {
+    let registry = NeuronCoderRegistry::new();
+    let parameters = NeuronCoderParameters::new(cortical_ids, data_type, z_resolution).unwrap();
+    (registry.create_encoder(&variant.into(), &parameters).unwrap(), registry.create_decoder(&variant.into(), &parameters).unwrap())
+}
+
+fn round_trip(encoder: &dyn NeuronXYZPEncoder, decoder: &dyn NeuronXYZPDecoder, value: &IOTypeData, channel: CorticalIOChannelIndex) -> IOTypeData {
+    assert_eq!(encoder.get_encodable_data_type(), decoder.get_decoded_data_type());
+    let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    encoder.write_neuron_data_single_channel(value, channel, &mut neuron_data).unwrap();
+    decoder.read_neuron_data_single_channel(channel, &neuron_data).unwrap()
+}
+
+// Deterministic pseudo-random values in [0, 1), so failures are reproducible
+fn pseudo_random_values(count: usize, seed: u32) -> Vec<f32> {
+    let mut state: u32 = seed;
+    (0..count).map(|_| {
+        state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+        (state >> 8) as f32 / (1u32 << 24) as f32
+    }).collect()
+}
+
+/// Replaces the pixels of a frame, keeping its resolution, channel layout and color space.
+fn fill_frame(frame: &mut ImageFrame, fill: impl Fn(f32) -> f32, seed: u32) {
+    let shape = frame.get_internal_data().raw_dim();
+    let values = pseudo_random_values(frame.get_internal_data().len(), seed);
+    let pixels = Array3::from_shape_vec(shape, values.into_iter().map(fill).collect()).unwrap();
+    *frame = ImageFrame::from_array(pixels, frame.get_color_space(), &MemoryOrderLayout::HeightsWidthsChannels).unwrap();
+}
+
+#[test]
+fn test_f32_linear_round_trip_within_z_resolution() {
+    let cortical_id = CorticalID::new_sensor_cortical_area_id(SensorCorticalType::Proximity, CorticalGroupingIndex::from(0)).unwrap();
+    for z_resolution in [1u32, 3, 10, 64, 1000] {
+        let (encoder, decoder) = create_coders(NeuronCoderVariantType::F32Normalized0To1_Linear, vec![cortical_id], IOTypeVariant::F32Normalized0To1, z_resolution);
+        let quantization_step = 1.0 / z_resolution as f32;
+        for channel in [0u32, 5] {
+            for step in 0..=1000 {
+                let value = step as f32 / 1000.0;
+                let decoded = f32::try_from(round_trip(encoder.as_ref(), decoder.as_ref(), &IOTypeData::new_0_1_f32(value).unwrap(), channel.into())).unwrap();
+                // Encoding floors to the Z index, so the decoded value may only be below the original, by less than 1 step
+                assert!(decoded <= value + EPSILON, "z_res {} value {} decoded to {}", z_resolution, value, decoded);
+                assert!(value - decoded < quantization_step + EPSILON, "z_res {} value {} decoded to {}", z_resolution, value, decoded);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_f32_bidirectional_round_trips_are_exact() {
+    let cortical_id = CorticalID::new_motor_cortical_area_id(MotorCorticalType::RotaryMotor, CorticalGroupingIndex::from(0)).unwrap();
+    let (psp_encoder, psp_decoder) = create_coders(NeuronCoderVariantType::F32NormalizedM1To1_PSPBidirectional, vec![cortical_id], IOTypeVariant::F32NormalizedM1To1, 10);
+    let (split_encoder, split_decoder) = create_coders(NeuronCoderVariantType::F32NormalizedM1To1_SplitSignDivided, vec![cortical_id], IOTypeVariant::F32NormalizedM1To1, 10);
+    for channel in [0u32, 2, 7] {
+        for step in -1000..=1000 {
+            let value = step as f32 / 1000.0;
+            let wrapped = IOTypeData::new_m1_1_f32(value).unwrap();
+            let psp_decoded = f32::try_from(round_trip(psp_encoder.as_ref(), psp_decoder.as_ref(), &wrapped, channel.into())).unwrap();
+            let split_decoded = f32::try_from(round_trip(split_encoder.as_ref(), split_decoder.as_ref(), &wrapped, channel.into())).unwrap();
+            assert_eq!(psp_decoded, value);
+            assert_eq!(split_decoded, value);
+        }
+    }
+}
+
+#[test]
+fn test_decoders_return_rest_values_for_missing_areas() {
+    let cortical_id = CorticalID::new_motor_cortical_area_id(MotorCorticalType::RotaryMotor, CorticalGroupingIndex::from(0)).unwrap();
+    let empty = CorticalMappedXYZPNeuronData::new();
+    let (_, linear) = create_coders(NeuronCoderVariantType::F32Normalized0To1_Linear, vec![cortical_id], IOTypeVariant::F32Normalized0To1, 10);
+    let (_, split) = create_coders(NeuronCoderVariantType::F32NormalizedM1To1_SplitSignDivided, vec![cortical_id], IOTypeVariant::F32NormalizedM1To1, 10);
+    assert_eq!(f32::try_from(linear.read_neuron_data_single_channel(0.into(), &empty).unwrap()).unwrap(), 0.0);
+    assert_eq!(f32::try_from(split.read_neuron_data_single_channel(0.into(), &empty).unwrap()).unwrap(), 0.0);
+}
+
+#[test]
+fn test_image_frame_round_trip() {
+    let cortical_id = CorticalID::new_sensor_cortical_area_id(SensorCorticalType::ImageCameraCenter, CorticalGroupingIndex::from(0)).unwrap();
+    let properties = ImageFrameProperties::new((5, 4), ColorSpace::Linear, ColorChannelLayout::RGB).unwrap();
+    let (encoder, decoder) = create_coders(NeuronCoderVariantType::ImageFrame, vec![cortical_id], IOTypeVariant::ImageFrame(Some(properties)), 1);
+
+    let mut image = ImageFrame::from_image_frame_properties(&properties).unwrap();
+    fill_frame(&mut image, |value| if value < 0.2 { 0.0 } else { value }, 42); // leave some pixels dark
+
+    for channel in [0u32, 3] {
+        let decoded = round_trip(encoder.as_ref(), decoder.as_ref(), &IOTypeData::ImageFrame(image.clone()), channel.into());
+        let decoded: ImageFrame = decoded.try_into().unwrap();
+        assert_eq!(decoded.get_internal_data(), image.get_internal_data());
+    }
+}
+
+#[test]
+fn test_segmented_image_frame_round_trip() {
+    let cortical_ids = SegmentedImageFrame::create_ordered_cortical_ids_for_segmented_vision(CorticalGroupingIndex::from(1));
+    let resolutions = SegmentedFrameTargetResolutions::create_with_same_sized_peripheral((6, 5), (2, 3)).unwrap();
+    let properties = SegmentedImageFrameProperties::new(&resolutions, &ColorChannelLayout::RGB, &ColorChannelLayout::GrayScale, &ColorSpace::Linear);
+    let (encoder, decoder) = create_coders(NeuronCoderVariantType::SegmentedImageFrame, cortical_ids.to_vec(), IOTypeVariant::SegmentedImageFrame(Some(properties)), 1);
+
+    let mut segmented_image = SegmentedImageFrame::from_segmented_image_frame_properties(&properties).unwrap();
+    for (index, segment) in segmented_image.get_mut_ordered_image_frame_references().into_iter().enumerate() {
+        fill_frame(segment, |value| value, index as u32);
+    }
+
+    let decoded = round_trip(encoder.as_ref(), decoder.as_ref(), &IOTypeData::SegmentedImageFrame(segmented_image.clone()), 0.into());
+    let decoded: SegmentedImageFrame = decoded.try_into().unwrap();
+    for (original, decoded) in segmented_image.get_image_internal_data().iter().zip(decoded.get_image_internal_data().iter()) {
+        for (original_pixel, decoded_pixel) in original.iter().zip(decoded.iter()) {
+            // Near zero pixels are not written as neurons
+            if *original_pixel > 0.0001 {
+                assert_eq!(original_pixel, decoded_pixel);
+            } else {
+                assert_eq!(*decoded_pixel, 0.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Test for plugging user defined neuron coders into the coder registry and sensor cache.
+//!
+//! This test demonstrates writing a custom encoder outside the crate, handing it directly to
+//! a sensor cache, registering it in a coder registry under a custom ID, and replacing a
+//! built-in coder so the by-type registration calls pick it up.
+
+use std::time::Instant;
+use feagi_core_data_structures_and_processing::error::FeagiDataProcessingError;
+use feagi_core_data_structures_and_processing::genomic_structures::{CorticalID, CorticalIOChannelIndex, CorticalType, MotorCorticalType, SensorCorticalType};
+use feagi_core_data_structures_and_processing::io_data::{IOTypeData, IOTypeVariant};
+use feagi_core_data_structures_and_processing::io_processing::processors::{IdentityFloatProcessor, LinearScaleTo0And1Processor};
+use feagi_core_data_structures_and_processing::io_processing::{SensorCache, StreamCacheProcessor};
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronCoderID, NeuronCoderParameters, NeuronCoderRegistry, NeuronCoderVariantType, NeuronXYZP, NeuronXYZPEncoder};
+
+/// Encodes a normalized value as a bar of neurons along Z, filling every neuron up to the value.
+struct ThermometerEncoder {
+    cortical_id: CorticalID,
+    z_resolution: u32,
+}
+
+impl NeuronXYZPEncoder for ThermometerEncoder {
+    fn get_encodable_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32Normalized0To1
+    }
+
+    fn write_neuron_data_single_channel(&self, wrapped_value: &IOTypeData, cortical_channel: CorticalIOChannelIndex, write_target: &mut CorticalMappedXYZPNeuronData) -> Result<(), FeagiDataProcessingError> {
+        let value = f32::try_from(wrapped_value)?;
+        let filled = (value * self.z_resolution as f32).round() as u32;
+        let neurons = write_target.ensure_clear_and_borrow_mut(&self.cortical_id, filled as usize);
+        for z in 0..filled {
+            neurons.push(&NeuronXYZP::new(*cortical_channel, 0, z, 1.0));
+        }
+        Ok(())
+    }
+}
+
+fn proximity_cortical_id() -> CorticalID {
+    CorticalID::new_sensor_cortical_area_id(SensorCorticalType::Proximity, 0.into()).unwrap()
+}
+
+fn proximity_chain() -> Vec<Box<dyn StreamCacheProcessor + Sync + Send>> {
+    vec![Box::new(LinearScaleTo0And1Processor::new(0.0, 100.0, 0.0).unwrap())]
+}
+
+#[test]
+fn test_sensor_cache_with_user_encoder() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sensor_cache = SensorCache::new();
+    let encoder = Box::new(ThermometerEncoder { cortical_id: proximity_cortical_id(), z_resolution: 10 });
+    sensor_cache.register_cortical_area_with_encoder(SensorCorticalType::Proximity, 0.into(), encoder, vec![proximity_chain()], false)?;
+
+    sensor_cache.send_data_for_proximity(40.0, 0.into(), 0.into())?;
+    let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    sensor_cache.encode_to_neurons(Instant::now(), &mut neuron_data)?;
+
+    let neurons = neuron_data.get_neurons_of(&proximity_cortical_id()).unwrap();
+    assert_eq!(neurons.len(), 4);
+    assert!(neurons.iter().all(|neuron| neuron.x == 0 && neuron.z < 4));
+    Ok(())
+}
+
+#[test]
+fn test_sensor_cache_rejects_chain_not_matching_user_encoder() {
+    let mut sensor_cache = SensorCache::new();
+    let encoder = Box::new(ThermometerEncoder { cortical_id: proximity_cortical_id(), z_resolution: 10 });
+    let chain: Vec<Box<dyn StreamCacheProcessor + Sync + Send>> = vec![Box::new(IdentityFloatProcessor::new(0.0).unwrap())];
+    assert!(sensor_cache.register_cortical_area_with_encoder(SensorCorticalType::Proximity, 0.into(), encoder, vec![chain], false).is_err());
+
+    // Nothing was left registered, so the area can still be registered correctly
+    let encoder = Box::new(ThermometerEncoder { cortical_id: proximity_cortical_id(), z_resolution: 10 });
+    assert!(sensor_cache.register_cortical_area_with_encoder(SensorCorticalType::Proximity, 0.into(), encoder, vec![proximity_chain()], false).is_ok());
+}
+
+#[test]
+fn test_registry_custom_coder_ids() -> Result<(), Box<dyn std::error::Error>> {
+    let mut registry = NeuronCoderRegistry::new();
+    let coder_id = NeuronCoderID::Custom("thermometer".into());
+    registry.register_encoder(coder_id.clone(), Box::new(|parameters| {
+        Ok(Box::new(ThermometerEncoder { cortical_id: parameters.get_single_cortical_id()?, z_resolution: parameters.get_z_resolution() }))
+    }))?;
+    assert!(registry.contains_encoder(&coder_id));
+    assert!(!registry.contains_decoder(&coder_id));
+
+    // The same ID cannot be registered twice
+    assert!(registry.register_encoder(coder_id.clone(), Box::new(|_| Err(FeagiDataProcessingError::NotImplemented))).is_err());
+
+    let parameters = NeuronCoderParameters::new(vec![proximity_cortical_id()], IOTypeVariant::F32Normalized0To1, 5)?;
+    let encoder = registry.create_encoder(&coder_id, &parameters)?;
+    let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    encoder.write_neuron_data_single_channel(&IOTypeData::new_0_1_f32(1.0)?, 0.into(), &mut neuron_data)?;
+    assert_eq!(neuron_data.get_neurons_of(&proximity_cortical_id()).unwrap().len(), 5);
+
+    assert!(registry.deregister_encoder(&coder_id));
+    assert!(registry.create_encoder(&coder_id, &parameters).is_err());
+    assert!(NeuronCoderRegistry::new_empty().create_encoder(&NeuronCoderVariantType::F32Normalized0To1_Linear.into(), &parameters).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_registry_resolves_cortical_types() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = NeuronCoderRegistry::new();
+
+    let sensor_type: CorticalType = SensorCorticalType::Proximity.into();
+    let parameters = NeuronCoderParameters::new(vec![proximity_cortical_id()], IOTypeVariant::F32Normalized0To1, 10)?;
+    let encoder = registry.create_encoder_for_cortical_type(&sensor_type, &parameters)?;
+    assert_eq!(encoder.get_encodable_data_type(), IOTypeVariant::F32Normalized0To1);
+    assert!(registry.create_decoder_for_cortical_type(&sensor_type, &parameters).is_err());
+
+    let motor_type: CorticalType = MotorCorticalType::RotaryMotor.into();
+    let motor_id = CorticalID::new_motor_cortical_area_id(MotorCorticalType::RotaryMotor, 0.into())?;
+    let parameters = NeuronCoderParameters::new(vec![motor_id], IOTypeVariant::F32NormalizedM1To1, 10)?;
+    let decoder = registry.create_decoder_for_cortical_type(&motor_type, &parameters)?;
+    assert_eq!(decoder.get_decoded_data_type(), IOTypeVariant::F32NormalizedM1To1);
+
+    // Built-in coders validate the parameters they are given
+    let wrong_type = NeuronCoderParameters::new(vec![motor_id], IOTypeVariant::F32, 10)?;
+    assert!(registry.create_decoder_for_cortical_type(&motor_type, &wrong_type).is_err());
+    assert!(NeuronCoderParameters::new(vec![], IOTypeVariant::F32, 10).is_err());
+    assert!(NeuronCoderParameters::new(vec![motor_id], IOTypeVariant::F32, 0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_sensor_cache_uses_replaced_built_in_encoder() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sensor_cache = SensorCache::new();
+    let linear_id: NeuronCoderID = NeuronCoderVariantType::F32Normalized0To1_Linear.into();
+    let registry = sensor_cache.get_coder_registry_mut();
+    assert!(registry.deregister_encoder(&linear_id));
+    registry.register_encoder(linear_id, Box::new(|parameters| {
+        Ok(Box::new(ThermometerEncoder { cortical_id: parameters.get_single_cortical_id()?, z_resolution: parameters.get_z_resolution() }))
+    }))?;
+
+    sensor_cache.register_cortical_group_for_proximity(0.into(), 1, false, 10, 0.0, 100.0)?;
+    sensor_cache.send_data_for_proximity(70.0, 0.into(), 0.into())?;
+    let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    sensor_cache.encode_to_neurons(Instant::now(), &mut neuron_data)?;
+    assert_eq!(neuron_data.get_neurons_of(&proximity_cortical_id()).unwrap().len(), 7);
+    Ok(())
+}
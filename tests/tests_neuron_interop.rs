@@ -0,0 +1,101 @@
+//! Tests for exporting and importing neuron data as NPZ and CSV. Requires the `interop`
+//! feature, run with `cargo test --features interop`.
+#![cfg(feature = "interop")]
+
+use std::io::Cursor;
+use ndarray::{array, Array1};
+use ndarray_npy::{NpzReader, NpzWriter};
+use feagi_core_data_structures_and_processing::error::FeagiDataProcessingError;
+use feagi_core_data_structures_and_processing::genomic_structures::{CoreCorticalType, CorticalID};
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPArrays};
+
+fn create_neuron_data() -> CorticalMappedXYZPNeuronData {
+    let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    let camera = CorticalID::from_string("iic400".to_string()).unwrap();
+    let death = CorticalID::new_core_cortical_area_id(CoreCorticalType::Death).unwrap();
+    let empty = CorticalID::from_string("ipro00".to_string()).unwrap();
+    neuron_data.insert(camera, NeuronXYZPArrays::new_from_vectors(vec![0, 1, 4_000_000_000], vec![2, 3, 7], vec![0, 0, 1], vec![0.1, -2.5e-7, f32::NAN]).unwrap());
+    neuron_data.insert(death, NeuronXYZPArrays::new_from_vectors(vec![0], vec![0], vec![0], vec![1.0]).unwrap());
+    neuron_data.insert(empty, NeuronXYZPArrays::new());
+    neuron_data
+}
+
+#[test]
+fn test_npz_round_trip() -> Result<(), FeagiDataProcessingError> {
+    let neuron_data = create_neuron_data();
+    let uncompressed = neuron_data.write_npz(Cursor::new(Vec::new()))?;
+    let compressed = neuron_data.write_npz_compressed(Cursor::new(Vec::new()))?;
+
+    // Arrays are named by cortical ID and component, for easy access from NumPy
+    let mut npz = NpzReader::new(uncompressed.clone()).unwrap();
+    assert_eq!(npz.names().unwrap().len(), 12);
+    let death_p: Array1<f32> = npz.by_name("_death_p").unwrap();
+    assert_eq!(death_p, array![1.0f32]);
+    let camera_x: Array1<u32> = npz.by_name("iic400_x.npy").unwrap();
+    assert_eq!(camera_x, array![0u32, 1, 4_000_000_000]);
+
+    for npz in [uncompressed, compressed] {
+        let read = CorticalMappedXYZPNeuronData::new_from_npz(npz)?;
+        assert_eq!(read.len(), 3);
+        assert!(neuron_data.diff(&read, 0.0).is_empty());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_npz_import_rejects_malformed_archives() {
+    let write_arrays = |names: &[&str], lengths: &[usize]| {
+        let mut npz = NpzWriter::new(Cursor::new(Vec::new()));
+        for (name, length) in names.iter().zip(lengths) {
+            if name.ends_with("_p") {
+                npz.add_array(*name, &Array1::<f32>::zeros(*length)).unwrap();
+            } else {
+                npz.add_array(*name, &Array1::<u32>::zeros(*length)).unwrap();
+            }
+        }
+        npz.finish().unwrap()
+    };
+
+    assert!(CorticalMappedXYZPNeuronData::new_from_npz(write_arrays(&["iic400_x", "iic400_y", "iic400_z", "iic400_p"], &[2, 2, 2, 2])).is_ok());
+    assert!(CorticalMappedXYZPNeuronData::new_from_npz(write_arrays(&["iic400_x", "iic400_y", "iic400_z"], &[2, 2, 2])).is_err()); // Missing potentials
+    assert!(CorticalMappedXYZPNeuronData::new_from_npz(write_arrays(&["iic400_y", "iic400_z", "iic400_p"], &[2, 2, 2])).is_err()); // Missing x
+    assert!(CorticalMappedXYZPNeuronData::new_from_npz(write_arrays(&["iic400_x", "iic400_y", "iic400_z", "iic400_p"], &[2, 2, 3, 2])).is_err()); // Length mismatch
+    assert!(CorticalMappedXYZPNeuronData::new_from_npz(write_arrays(&["arr_0"], &[2])).is_err()); // Not a neuron array
+    assert!(CorticalMappedXYZPNeuronData::new_from_npz(Cursor::new(b"not a zip".to_vec())).is_err());
+}
+
+#[test]
+fn test_csv_round_trip() -> Result<(), FeagiDataProcessingError> {
+    let neuron_data = create_neuron_data();
+    let mut csv: Vec<u8> = Vec::new();
+    neuron_data.write_csv(&mut csv)?;
+    assert_eq!(String::from_utf8(csv.clone()).unwrap(),
+        "cortical_id,x,y,z,p\n_death,0,0,0,1\niic400,0,2,0,0.1\niic400,1,3,0,-0.00000025\niic400,4000000000,7,1,NaN\n");
+
+    // The empty area has no rows, so it is not read back
+    let read = CorticalMappedXYZPNeuronData::new_from_csv(csv.as_slice())?;
+    assert_eq!(read.len(), 2);
+    let diff = neuron_data.diff(&read, 0.0);
+    assert_eq!(diff.get_removed_areas(), &[CorticalID::from_string("ipro00".to_string())?]);
+    assert!(diff.get_area_diffs().iter().all(|(_, area_diff)| area_diff.is_empty()));
+
+    // Rows of an area may be interleaved with others, with whitespace and blank lines
+    let handwritten = "cortical_id,x,y,z,p\r\niic400, 0, 2, 0, 0.5\n_death,1,1,1,1\n\niic400,3,3,3,0.25\n";
+    let read = CorticalMappedXYZPNeuronData::new_from_csv(handwritten.as_bytes())?;
+    assert_eq!(read.get_neurons_of(&CorticalID::from_string("iic400".to_string())?).unwrap().len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_csv_import_rejects_malformed_rows() {
+    for csv in [
+        "",
+        "x,y,z,p\n0,0,0,0.5\n",
+        "cortical_id,x,y,z,p\niic400,0,0,0\n",
+        "cortical_id,x,y,z,p\niic400,-1,0,0,0.5\n",
+        "cortical_id,x,y,z,p\niic400,0,0,0,bright\n",
+        "cortical_id,x,y,z,p\nnope,0,0,0,0.5\n",
+    ] {
+        assert!(CorticalMappedXYZPNeuronData::new_from_csv(csv.as_bytes()).is_err(), "Accepted {:?}", csv);
+    }
+}
@@ -1,7 +1,8 @@
 use ndarray::prelude::*;
-use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPArrays, NeuronXYZP};
-use feagi_core_data_structures_and_processing::genomic_structures::CorticalID;
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, CorticalMappedXYZPNeuronDataView, NeuronXYZPArrays, NeuronXYZP, NeuronXYZPDuplicateHandling, NeuronXYZPPotentialQuantization, NeuronXYZPSpatialIndex, NeuronXYZPArraysDiff};
+use feagi_core_data_structures_and_processing::genomic_structures::{CorticalID, CorticalType};
 use feagi_core_data_structures_and_processing::io_processing::byte_structures::FeagiByteStructure;
+use feagi_core_data_structures_and_processing::io_processing::byte_structures::FeagiByteStructureType;
 use feagi_core_data_structures_and_processing::io_processing::byte_structures::FeagiByteStructureCompatible;
 
 #[test]
@@ -111,3 +112,377 @@ fn test_serialize_deserialize_neuron_mapped_areas() {
 
 }
 
+/// Creates an image like area, where every pixel of a width x height RGB frame above a threshold is active
+fn create_image_like_neurons(width: u32, height: u32) -> NeuronXYZPArrays {
+    let mut neurons = NeuronXYZPArrays::with_capacity((width * height * 3) as usize);
+    for x in 0..width {
+        for y in 0..height {
+            for z in 0..3 {
+                let value = ((x * 7 + y * 13 + z * 29) % 256) as f32 / 255.0;
+                if value > 0.25 {
+                    neurons.push(&NeuronXYZP::new(x, y, z, value));
+                }
+            }
+        }
+    }
+    neurons
+}
+
+fn sorted_neurons(neurons: &NeuronXYZPArrays) -> Vec<NeuronXYZP> {
+    let mut output = neurons.copy_as_neuron_xyzp_vec();
+    output.sort_by_key(|neuron| (neuron.x, neuron.y, neuron.z));
+    output
+}
+
+#[test]
+fn test_serialize_deserialize_compressed_neuron_mapped_areas() {
+    let cortical_id_image = CorticalID::new_custom_cortical_area_id("cIMAGE".to_string()).unwrap();
+    let cortical_id_constant = CorticalID::new_custom_cortical_area_id("cCONST".to_string()).unwrap();
+    let cortical_id_sparse = CorticalID::new_custom_cortical_area_id("cSPARS".to_string()).unwrap();
+
+    let image_neurons = create_image_like_neurons(32, 24);
+    let mut constant_neurons = NeuronXYZPArrays::new();
+    constant_neurons.push(&NeuronXYZP::new(5, 0, 9, 1.0));
+    constant_neurons.push(&NeuronXYZP::new(0, 3, 2, 1.0));
+    constant_neurons.push(&NeuronXYZP::new(5, 0, 9, 1.0)); // duplicates are kept
+    let mut sparse_neurons = NeuronXYZPArrays::new();
+    sparse_neurons.push(&NeuronXYZP::new(u32::MAX, u32::MAX, u32::MAX, -4.5));
+    sparse_neurons.push(&NeuronXYZP::new(0, 0, 0, 17.25));
+
+    let mut cortical_mappings = CorticalMappedXYZPNeuronData::new();
+    cortical_mappings.insert(cortical_id_image, image_neurons.clone());
+    cortical_mappings.insert(cortical_id_constant, constant_neurons.clone());
+    cortical_mappings.insert(cortical_id_sparse, sparse_neurons.clone());
+
+    for quantization in [NeuronXYZPPotentialQuantization::Float32, NeuronXYZPPotentialQuantization::U16, NeuronXYZPPotentialQuantization::U8] {
+        let sending_byte_structure = cortical_mappings.as_new_feagi_byte_structure_compressed(quantization).unwrap();
+        assert_eq!(sending_byte_structure.try_get_structure_type().unwrap(), FeagiByteStructureType::NeuronCategoricalXYZPCompressed);
+
+        let received_byte_structure = FeagiByteStructure::create_from_bytes(sending_byte_structure.copy_out_as_byte_vector()).unwrap();
+        let received_cortical_mappings = CorticalMappedXYZPNeuronData::new_from_feagi_byte_structure(&received_byte_structure).unwrap();
+        assert_eq!(received_cortical_mappings.len(), 3);
+
+        // Constant and 2 value areas are always exact
+        assert_eq!(sorted_neurons(received_cortical_mappings.get_neurons_of(&cortical_id_constant).unwrap()), sorted_neurons(&constant_neurons));
+        assert_eq!(sorted_neurons(received_cortical_mappings.get_neurons_of(&cortical_id_sparse).unwrap()), sorted_neurons(&sparse_neurons));
+
+        // Quantized potentials are within half a step of the original
+        let max_error = match quantization {
+            NeuronXYZPPotentialQuantization::Float32 => 0.0,
+            NeuronXYZPPotentialQuantization::U16 => 0.5 / u16::MAX as f32 + f32::EPSILON,
+            NeuronXYZPPotentialQuantization::U8 => 0.5 / u8::MAX as f32 + f32::EPSILON,
+        };
+        let expected = sorted_neurons(&image_neurons);
+        let received = sorted_neurons(received_cortical_mappings.get_neurons_of(&cortical_id_image).unwrap());
+        assert_eq!(expected.len(), received.len());
+        for (expected, received) in expected.iter().zip(received.iter()) {
+            assert_eq!((expected.x, expected.y, expected.z), (received.x, received.y, received.z));
+            assert!((expected.p - received.p).abs() <= max_error, "{} vs {}", expected.p, received.p);
+        }
+    }
+}
+
+#[test]
+fn test_compressed_neuron_format_is_smaller_and_rejects_truncation() {
+    let cortical_id = CorticalID::new_custom_cortical_area_id("cIMAGE".to_string()).unwrap();
+    let mut cortical_mappings = CorticalMappedXYZPNeuronData::new();
+    cortical_mappings.insert(cortical_id, create_image_like_neurons(64, 48));
+
+    let raw_size = cortical_mappings.as_new_feagi_byte_structure().unwrap().borrow_data_as_slice().len();
+    let compressed = cortical_mappings.as_new_feagi_byte_structure_compressed(NeuronXYZPPotentialQuantization::U8).unwrap();
+    let compressed_bytes = compressed.copy_out_as_byte_vector();
+    assert!(compressed_bytes.len() * 3 < raw_size, "compressed {} bytes vs raw {} bytes", compressed_bytes.len(), raw_size);
+
+    for cut in [4, 20, compressed_bytes.len() / 2, compressed_bytes.len() - 1] {
+        let truncated = FeagiByteStructure::create_from_bytes(compressed_bytes[..cut].to_vec()).unwrap();
+        assert!(CorticalMappedXYZPNeuronData::new_from_feagi_byte_structure(&truncated).is_err());
+    }
+    assert!(CorticalMappedXYZPNeuronData::new().as_new_feagi_byte_structure_compressed(NeuronXYZPPotentialQuantization::U8).is_err());
+}
+
+#[test]
+fn test_view_neuron_mapped_areas_without_copying() {
+    let cortical_id_image = CorticalID::new_custom_cortical_area_id("cIMAGE".to_string()).unwrap();
+    let cortical_id_motor = CorticalID::new_custom_cortical_area_id("cMOTOR".to_string()).unwrap();
+    let cortical_id_missing = CorticalID::new_custom_cortical_area_id("cNOPE0".to_string()).unwrap();
+    let mut motor_neurons = NeuronXYZPArrays::new();
+    motor_neurons.push(&NeuronXYZP::new(3, 0, 1, 0.75));
+    motor_neurons.push(&NeuronXYZP::new(2, 0, 4, 0.25));
+
+    let mut cortical_mappings = CorticalMappedXYZPNeuronData::new();
+    cortical_mappings.insert(cortical_id_image, create_image_like_neurons(16, 16));
+    cortical_mappings.insert(cortical_id_motor, motor_neurons.clone());
+    let byte_structure = cortical_mappings.as_new_feagi_byte_structure().unwrap();
+
+    let view = CorticalMappedXYZPNeuronDataView::new(&byte_structure).unwrap();
+    assert_eq!(view.len(), 2);
+    assert!(view.contains_cortical_id(&cortical_id_motor));
+    assert!(view.get_neurons_of(&cortical_id_missing).unwrap().is_none());
+
+    // Views keep the serialized order
+    let motor_view = view.get_neurons_of(&cortical_id_motor).unwrap().unwrap();
+    assert_eq!(motor_view.len(), 2);
+    assert_eq!(motor_view.iter().collect::<Vec<NeuronXYZP>>(), motor_neurons.copy_as_neuron_xyzp_vec());
+    assert_eq!(motor_view.iter_p().sum::<f32>(), 1.0);
+    assert!(motor_view.get(2).is_err());
+
+    let mut ids: Vec<CorticalID> = view.cortical_ids().map(|id| id.unwrap()).collect();
+    ids.sort_by_key(|id| id.as_ascii_string());
+    assert_eq!(ids, vec![cortical_id_image, cortical_id_motor]);
+
+    let copied = view.to_cortical_mapped_xyzp_neuron_data().unwrap();
+    assert_eq!(copied.get_neurons_of(&cortical_id_image).unwrap().len(), cortical_mappings.get_neurons_of(&cortical_id_image).unwrap().len());
+}
+
+#[test]
+fn test_view_validates_areas_lazily() {
+    let cortical_id_a = CorticalID::new_custom_cortical_area_id("cAAAAA".to_string()).unwrap();
+    let cortical_id_b = CorticalID::new_custom_cortical_area_id("cBBBBB".to_string()).unwrap();
+    let mut neurons = NeuronXYZPArrays::new();
+    neurons.push(&NeuronXYZP::new(1, 2, 3, 0.5));
+    let mut cortical_mappings = CorticalMappedXYZPNeuronData::new();
+    cortical_mappings.insert(cortical_id_a, neurons.clone());
+    cortical_mappings.insert(cortical_id_b, neurons);
+
+    // Corrupt the data length of whichever area comes first
+    let mut bytes = cortical_mappings.as_new_feagi_byte_structure().unwrap().copy_out_as_byte_vector();
+    let first_id = CorticalID::from_bytes(<&[u8; 6]>::try_from(&bytes[4..10]).unwrap()).unwrap();
+    let second_id = if first_id == cortical_id_a { cortical_id_b } else { cortical_id_a };
+    bytes[14] = 0xFF;
+    let byte_structure = FeagiByteStructure::create_from_bytes(bytes).unwrap();
+
+    let view = CorticalMappedXYZPNeuronDataView::new(&byte_structure).unwrap();
+    assert!(view.get_neurons_of(&first_id).is_err());
+    assert_eq!(view.get_neurons_of(&second_id).unwrap().unwrap().len(), 1);
+    assert!(CorticalMappedXYZPNeuronData::new_from_feagi_byte_structure(&byte_structure).is_err());
+
+    // The compressed format cannot be viewed
+    let compressed = cortical_mappings.as_new_feagi_byte_structure_compressed(NeuronXYZPPotentialQuantization::Float32).unwrap();
+    assert!(CorticalMappedXYZPNeuronDataView::new(&compressed).is_err());
+}
+
+#[test]
+fn test_dense_volume_round_trip_and_duplicates() {
+    let mut volume = Array3::<f32>::zeros((5, 4, 3));
+    volume[[0, 0, 0]] = 0.5;
+    volume[[4, 3, 2]] = 1.0;
+    volume[[2, 1, 0]] = -0.25;
+    volume[[3, 3, 1]] = f32::NAN;
+
+    // Only voxels strictly above the threshold become neurons, in x y z order
+    let neurons = NeuronXYZPArrays::new_from_dense_volume(&volume, 0.0).unwrap();
+    assert_eq!(neurons.copy_as_neuron_xyzp_vec(), vec![NeuronXYZP::new(0, 0, 0, 0.5), NeuronXYZP::new(4, 3, 2, 1.0)]);
+    let all_finite = NeuronXYZPArrays::new_from_dense_volume(&volume, f32::NEG_INFINITY).unwrap();
+    assert_eq!(all_finite.len(), 5 * 4 * 3 - 1);
+
+    let mut expected = volume.clone();
+    expected[[3, 3, 1]] = 0.0;
+    assert_eq!(all_finite.to_dense_volume((5, 4, 3), NeuronXYZPDuplicateHandling::Last).unwrap(), expected);
+
+    // Neurons sharing a coordinate
+    let mut duplicates = NeuronXYZPArrays::new();
+    duplicates.push(&NeuronXYZP::new(1, 1, 1, 0.25));
+    duplicates.push(&NeuronXYZP::new(1, 1, 1, 0.75));
+    duplicates.push(&NeuronXYZP::new(1, 1, 1, 0.5));
+    duplicates.push(&NeuronXYZP::new(0, 1, 0, -0.5));
+    let max = duplicates.to_dense_volume((2, 2, 2), NeuronXYZPDuplicateHandling::Max).unwrap();
+    let sum = duplicates.to_dense_volume((2, 2, 2), NeuronXYZPDuplicateHandling::Sum).unwrap();
+    let last = duplicates.to_dense_volume((2, 2, 2), NeuronXYZPDuplicateHandling::Last).unwrap();
+    assert_eq!((max[[1, 1, 1]], sum[[1, 1, 1]], last[[1, 1, 1]]), (0.75, 1.5, 0.5));
+    assert_eq!((max[[0, 1, 0]], max[[0, 0, 0]]), (-0.5, 0.0));
+    assert_eq!(sum.iter().filter(|potential| **potential != 0.0).count(), 2);
+
+    // Neurons outside the volume are rejected, leaving a reused volume zeroed
+    let mut reused = max.clone();
+    assert!(duplicates.write_to_dense_volume(&mut Array3::zeros((2, 2, 2)), NeuronXYZPDuplicateHandling::Max).is_ok());
+    assert!(duplicates.to_dense_volume((2, 1, 2), NeuronXYZPDuplicateHandling::Max).is_err());
+    let mut too_small = Array3::<f32>::ones((1, 2, 2));
+    assert!(duplicates.write_to_dense_volume(&mut too_small, NeuronXYZPDuplicateHandling::Sum).is_err());
+    assert!(too_small.iter().all(|potential| *potential == 0.0));
+    duplicates.write_to_dense_volume(&mut reused, NeuronXYZPDuplicateHandling::Last).unwrap();
+    assert_eq!(reused, last);
+}
+
+#[test]
+fn test_set_operations_sorted_and_unsorted_agree() {
+    let a = NeuronXYZPArrays::new_from_vectors(vec![3, 0, 1, 1, 5], vec![0, 0, 2, 2, 1], vec![0, 0, 0, 0, 4], vec![0.1, 0.2, 0.3, 0.4, 0.5]).unwrap();
+    let b = NeuronXYZPArrays::new_from_vectors(vec![1, 7, 0, 3], vec![2, 7, 0, 0], vec![0, 7, 0, 1], vec![1.0, 0.7, -0.2, 0.9]).unwrap();
+    let (mut sorted_a, mut sorted_b) = (a.clone(), b.clone());
+    sorted_a.sort_by_coordinate();
+    sorted_b.sort_by_coordinate();
+    assert!(!a.is_sorted_by_coordinate());
+    assert!(sorted_a.is_sorted_by_coordinate() && sorted_b.is_sorted_by_coordinate());
+
+    let sorted = |neurons: NeuronXYZPArrays| { let mut neurons = neurons; neurons.sort_by_coordinate(); neurons.copy_as_neuron_xyzp_vec() };
+    for handling in [NeuronXYZPDuplicateHandling::Max, NeuronXYZPDuplicateHandling::Sum, NeuronXYZPDuplicateHandling::Last] {
+        let merged = sorted_a.merge(&sorted_b, handling);
+        assert!(merged.is_sorted_by_coordinate());
+        assert_eq!(sorted(a.merge(&b, handling)), merged.copy_as_neuron_xyzp_vec());
+        assert_eq!(sorted(a.intersection(&b, handling)), sorted_a.intersection(&sorted_b, handling).copy_as_neuron_xyzp_vec());
+    }
+    assert_eq!(sorted(a.difference(&b)), sorted_a.difference(&sorted_b).copy_as_neuron_xyzp_vec());
+
+    // (1, 2, 0) appears twice in a and once in b, (0, 0, 0) once in each
+    let merged = sorted_a.merge(&sorted_b, NeuronXYZPDuplicateHandling::Sum);
+    assert_eq!(merged.len(), 6);
+    assert!(merged.copy_as_neuron_xyzp_vec().contains(&NeuronXYZP::new(1, 2, 0, 0.3 + 0.4 + 1.0)));
+    assert_eq!(a.intersection(&b, NeuronXYZPDuplicateHandling::Last).copy_as_neuron_xyzp_vec(), vec![NeuronXYZP::new(0, 0, 0, -0.2), NeuronXYZP::new(1, 2, 0, 1.0)]);
+    assert_eq!(a.intersection(&b, NeuronXYZPDuplicateHandling::Max).copy_as_neuron_xyzp_vec(), vec![NeuronXYZP::new(0, 0, 0, 0.2), NeuronXYZP::new(1, 2, 0, 1.0)]);
+    assert_eq!(a.difference(&b).copy_as_neuron_xyzp_vec(), vec![NeuronXYZP::new(3, 0, 0, 0.1), NeuronXYZP::new(5, 1, 4, 0.5)]);
+    assert!(a.difference(&a).is_empty());
+    assert_eq!(a.difference(&NeuronXYZPArrays::new()).len(), a.len());
+}
+
+#[test]
+fn test_cortical_mapped_set_operations() {
+    let camera = CorticalID::from_string("iic400".to_string()).unwrap();
+    let custom = CorticalID::new_custom_cortical_area_id("cAAAAA".to_string()).unwrap();
+    let baseline_only = CorticalID::new_custom_cortical_area_id("cBBBBB".to_string()).unwrap();
+
+    let mut burst = CorticalMappedXYZPNeuronData::new();
+    burst.insert(camera, NeuronXYZPArrays::new_from_vectors(vec![0, 1], vec![0, 0], vec![0, 0], vec![0.5, 0.5]).unwrap());
+    burst.insert(custom, NeuronXYZPArrays::new_from_vectors(vec![2], vec![2], vec![2], vec![1.0]).unwrap());
+    let mut baseline = CorticalMappedXYZPNeuronData::new();
+    baseline.insert(camera, NeuronXYZPArrays::new_from_vectors(vec![1], vec![0], vec![0], vec![0.1]).unwrap());
+    baseline.insert(baseline_only, NeuronXYZPArrays::new_from_vectors(vec![0], vec![0], vec![0], vec![0.1]).unwrap());
+
+    let merged = burst.merge(&baseline, NeuronXYZPDuplicateHandling::Max);
+    assert_eq!(merged.len(), 3);
+    assert_eq!(merged.get_neurons_of(&camera).unwrap().copy_as_neuron_xyzp_vec(), vec![NeuronXYZP::new(0, 0, 0, 0.5), NeuronXYZP::new(1, 0, 0, 0.5)]);
+
+    let common = burst.intersection(&baseline, NeuronXYZPDuplicateHandling::Last);
+    assert_eq!(common.len(), 1);
+    assert_eq!(common.get_neurons_of(&camera).unwrap().copy_as_neuron_xyzp_vec(), vec![NeuronXYZP::new(1, 0, 0, 0.1)]);
+
+    let difference = burst.difference(&baseline);
+    assert_eq!(difference.len(), 2);
+    assert_eq!(difference.get_neurons_of(&camera).unwrap().copy_as_neuron_xyzp_vec(), vec![NeuronXYZP::new(0, 0, 0, 0.5)]);
+    assert_eq!(difference.get_neurons_of(&custom).unwrap().len(), 1);
+
+    let sensors = merged.filter_by_cortical_type(CorticalType::is_type_sensor);
+    assert_eq!(sensors.keys().collect::<Vec<_>>(), vec![&camera]);
+    let mut custom_only = merged.clone();
+    custom_only.retain_by_cortical_type(CorticalType::is_type_custom);
+    assert_eq!(custom_only.len(), 2);
+    assert!(!custom_only.contains_cortical_id(&camera));
+}
+
+#[test]
+fn test_spatial_index_matches_linear_scan() {
+    // Deterministic scatter with clusters and duplicate coordinates
+    let mut neurons = NeuronXYZPArrays::new();
+    let mut seed: u32 = 12345;
+    for i in 0..2000u32 {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        let spread = if i % 4 == 0 { 200 } else { 20 };
+        neurons.push(&NeuronXYZP::new((seed >> 8) % spread, (seed >> 12) % spread, (seed >> 20) % 16, i as f32));
+    }
+    let distance_squared = |neuron: &NeuronXYZP, center: (u32, u32, u32)| {
+        (neuron.x.abs_diff(center.0) as u64).pow(2) + (neuron.y.abs_diff(center.1) as u64).pow(2) + (neuron.z.abs_diff(center.2) as u64).pow(2)
+    };
+
+    for index in [neurons.build_spatial_index(), NeuronXYZPSpatialIndex::new_with_cell_size(&neurons, 1).unwrap(), NeuronXYZPSpatialIndex::new_with_cell_size(&neurons, 64).unwrap()] {
+        for (x_range, y_range, z_range) in [(5..=15, 0..=19, 3..=3), (0..=u32::MAX, 0..=u32::MAX, 0..=u32::MAX), (150..=199, 0..=100, 0..=15), (300..=400, 0..=10, 0..=10)] {
+            let scanned = neurons.filter_neurons_by_location_bounds(x_range.clone(), y_range.clone(), z_range.clone()).unwrap();
+            assert_eq!(index.query_box(x_range, y_range, z_range).copy_as_neuron_xyzp_vec(), scanned.copy_as_neuron_xyzp_vec());
+        }
+
+        for center in [(10, 10, 8), (0, 0, 0), (190, 5, 15), (1000, 1000, 1000)] {
+            for radius in [0.0, 1.0, 3.5, 40.0] {
+                let scanned: Vec<usize> = neurons.enumerate().filter(|(_, neuron)| (distance_squared(neuron, center) as f32).sqrt() <= radius).map(|(i, _)| i).collect();
+                assert_eq!(index.query_radius_indices(center, radius), scanned);
+            }
+            for k in [1, 5, 37, 2500] {
+                let mut scanned: Vec<(u64, usize)> = neurons.enumerate().map(|(i, neuron)| (distance_squared(&neuron, center), i)).collect();
+                scanned.sort();
+                let expected: Vec<usize> = scanned.into_iter().take(k).map(|(_, i)| i).collect();
+                assert_eq!(index.query_k_nearest_indices(center, k), expected);
+            }
+        }
+    }
+
+    assert!(NeuronXYZPSpatialIndex::new_with_cell_size(&neurons, 0).is_err());
+    let empty = NeuronXYZPArrays::new();
+    let empty_index = empty.build_spatial_index();
+    assert!(empty_index.query_k_nearest_indices((0, 0, 0), 3).is_empty());
+    assert!(empty_index.query_box(0..=10, 0..=10, 0..=10).is_empty());
+    assert!(neurons.build_spatial_index().query_radius_indices((10, 10, 8), -1.0).is_empty());
+}
+
+#[test]
+fn test_neuron_stats_and_summary() {
+    let neurons = NeuronXYZPArrays::new_from_vectors(vec![1, 3, 5, 7], vec![0, 4, 4, 0], vec![2, 2, 2, 2], vec![-0.5, 0.05, 0.55, f32::NAN]).unwrap();
+    let stats = neurons.compute_stats();
+    assert_eq!(stats.get_active_count(), 4);
+    assert_eq!(stats.get_min_potential(), Some(-0.5));
+    assert_eq!(stats.get_max_potential(), Some(0.55));
+    assert!((stats.get_mean_potential().unwrap() - 0.1 / 3.0).abs() < 1e-6);
+    assert_eq!(stats.get_centroid(), Some((4.0, 2.0, 2.0)));
+    assert_eq!(stats.get_bounding_box(), Some(((1, 0, 2), (7, 4, 2))));
+    assert_eq!(stats.get_histogram(), &[2, 0, 0, 0, 0, 1, 0, 0, 0, 0]);
+
+    let coarse = neurons.compute_stats_with_histogram(2, (-1.0, 1.0)).unwrap();
+    assert_eq!(coarse.get_histogram(), &[1, 2]);
+    assert!(neurons.compute_stats_with_histogram(0, (0.0, 1.0)).is_err());
+    assert!(neurons.compute_stats_with_histogram(4, (1.0, 1.0)).is_err());
+
+    let empty = NeuronXYZPArrays::new().compute_stats();
+    assert_eq!(empty.get_active_count(), 0);
+    assert_eq!(empty.get_mean_potential(), None);
+    assert_eq!(empty.get_bounding_box(), None);
+    assert_eq!(empty.to_string(), "NeuronXYZPStats(active: 0)");
+
+    let camera = CorticalID::from_string("iic400".to_string()).unwrap();
+    let custom = CorticalID::new_custom_cortical_area_id("cAAAAA".to_string()).unwrap();
+    let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    neuron_data.insert(camera, neurons.clone());
+    neuron_data.insert(custom, NeuronXYZPArrays::new());
+    let summary = neuron_data.summarize();
+    assert_eq!(summary.len(), 2);
+    assert_eq!(summary[&camera], stats);
+    assert_eq!(summary[&custom], empty);
+    assert_eq!(neuron_data.summarize_with_histogram(2, (-1.0, 1.0)).unwrap()[&camera], coarse);
+    assert!(summary[&camera].to_string().starts_with("NeuronXYZPStats(active: 4, potential: mean 0.0333 min -0.5000 max 0.5500"));
+}
+
+#[test]
+fn test_burst_diff() {
+    let camera = CorticalID::from_string("iic400".to_string()).unwrap();
+    let added = CorticalID::new_custom_cortical_area_id("cAAAAA".to_string()).unwrap();
+    let removed = CorticalID::new_custom_cortical_area_id("cBBBBB".to_string()).unwrap();
+    let unchanged = CorticalID::new_custom_cortical_area_id("cCCCCC".to_string()).unwrap();
+
+    let mut before = CorticalMappedXYZPNeuronData::new();
+    before.insert(camera, NeuronXYZPArrays::new_from_vectors(vec![2, 0, 1, 1], vec![0, 0, 0, 0], vec![0, 0, 0, 0], vec![0.5, 0.25, 0.0, 0.1]).unwrap());
+    before.insert(removed, NeuronXYZPArrays::new_from_vectors(vec![4], vec![4], vec![4], vec![1.0]).unwrap());
+    before.insert(unchanged, NeuronXYZPArrays::new_from_vectors(vec![0], vec![0], vec![0], vec![f32::NAN]).unwrap());
+    let mut after = CorticalMappedXYZPNeuronData::new();
+    after.insert(camera, NeuronXYZPArrays::new_from_vectors(vec![3, 2, 1], vec![0, 0, 0], vec![0, 0, 0], vec![0.75, 0.505, 0.5]).unwrap());
+    after.insert(added, NeuronXYZPArrays::new());
+    after.insert(unchanged, NeuronXYZPArrays::new_from_vectors(vec![0], vec![0], vec![0], vec![f32::NAN]).unwrap());
+
+    let diff = before.diff(&after, 0.01);
+    assert!(!diff.is_empty());
+    assert_eq!(diff.get_added_areas(), &[added]);
+    assert_eq!(diff.get_removed_areas(), &[removed]);
+    assert_eq!(diff.get_area_diffs().len(), 3);
+    assert!(diff.get_area_diff(&unchanged).is_none());
+    assert_eq!(diff.get_area_diff(&added).unwrap(), &NeuronXYZPArraysDiff::default());
+    assert_eq!(diff.get_area_diff(&removed).unwrap().get_disappeared(), &[NeuronXYZP::new(4, 4, 4, 1.0)]);
+
+    // Golden report, where (1, 0, 0) uses the last of its duplicate potentials
+    assert_eq!(diff.to_string(), "CorticalMappedXYZPNeuronDataDiff(1 areas added, 1 removed, 3 differing)\n\
+        + CorticalID(cAAAAA): 0 appeared, 0 disappeared, 0 potentials changed\n\
+        - CorticalID(cBBBBB): 0 appeared, 1 disappeared, 0 potentials changed\n    \
+        - (4, 4, 4) p=1\n\
+        ~ CorticalID(iic400): 1 appeared, 1 disappeared, 1 potentials changed\n    \
+        + (3, 0, 0) p=0.75\n    \
+        - (0, 0, 0) p=0.25\n    \
+        ~ (1, 0, 0) p=0.1 -> 0.5");
+
+    assert!(before.diff(&before, 0.0).is_empty());
+    assert_eq!(before.diff(&before, 0.0).to_string(), "CorticalMappedXYZPNeuronDataDiff(no differences)");
+    let reversed = after.diff(&before, 0.01);
+    assert_eq!(reversed.get_added_areas(), &[removed]);
+    assert_eq!(reversed.get_area_diff(&camera).unwrap().get_appeared(), diff.get_area_diff(&camera).unwrap().get_disappeared());
+}
@@ -0,0 +1,109 @@
+//! Tests for running branching and merging processor graphs.
+
+use std::time::{Duration, Instant};
+use ndarray::Array3;
+use feagi_core_data_structures_and_processing::io_data::image_descriptors::{ColorChannelLayout, ColorSpace, ImageFrameProperties, MemoryOrderLayout};
+use feagi_core_data_structures_and_processing::io_data::{IOTypeData, IOTypeVariant, ImageFrame, ImageFrameTransformer};
+use feagi_core_data_structures_and_processing::io_processing::processors::{
+    IdentityFloatProcessor, ImageFrameQuickDiffProcessor, ImageFrameTransformerProcessor,
+    LinearAverageRollingWindowProcessor, LinearScaleTo0And1Processor, LinearScaleToM1And1
+};
+use feagi_core_data_structures_and_processing::io_processing::{ProcessorGraphCombinator, ProcessorGraphRunner};
+
+#[test]
+fn test_processor_graph_fan_out_of_camera_frames() -> Result<(), Box<dyn std::error::Error>> {
+    let camera_properties = ImageFrameProperties::new((64, 48), ColorSpace::Linear, ColorChannelLayout::RGB)?;
+    let resized_properties = ImageFrameProperties::new((16, 12), ColorSpace::Linear, ColorChannelLayout::RGB)?;
+    let mut graph = ProcessorGraphRunner::new();
+    let camera = graph.add_input(ImageFrame::from_image_frame_properties(&camera_properties)?.into());
+    let diff = graph.add_processor(camera, Box::new(ImageFrameQuickDiffProcessor::new(camera_properties, 0.1)?))?;
+    let resized = graph.add_processor(camera, Box::new(ImageFrameTransformerProcessor::new(
+        ImageFrameTransformer::new_from_input_output_properties(&camera_properties, &resized_properties)?)?))?;
+    assert_eq!(graph.get_output_data_type(diff)?, IOTypeVariant::ImageFrame(Some(camera_properties)));
+    assert_eq!(graph.get_output_data_type(resized)?, IOTypeVariant::ImageFrame(Some(resized_properties)));
+
+    let frame = ImageFrame::from_array(Array3::from_elem((48, 64, 3), 0.5), &ColorSpace::Linear, &MemoryOrderLayout::HeightsWidthsChannels)?;
+    graph.update_value(camera, &frame.into(), Instant::now())?;
+
+    // Both branches received the same frame
+    let resized_frame: &ImageFrame = graph.get_output(resized)?.try_into()?;
+    assert!(resized_frame.get_internal_data().iter().all(|pixel| (pixel - 0.5).abs() < 1e-5));
+    let diff_frame: &ImageFrame = graph.get_output(diff)?.try_into()?;
+    assert_eq!(diff_frame.get_cartesian_width_height(), (64, 48));
+    Ok(())
+}
+
+#[test]
+fn test_processor_graph_combinators() -> Result<(), Box<dyn std::error::Error>> {
+    let mut graph = ProcessorGraphRunner::new();
+    let left = graph.add_input(IOTypeData::new_f32(0.0)?);
+    let right = graph.add_input(IOTypeData::new_f32(0.0)?);
+    let left_scaled = graph.add_processor(left, Box::new(LinearScaleTo0And1Processor::new(0.0, 10.0, 0.0)?))?;
+    let right_averaged = graph.add_processor_chain(right, vec![
+        Box::new(LinearAverageRollingWindowProcessor::new(2, 0.0)?),
+        Box::new(LinearScaleTo0And1Processor::new(0.0, 100.0, 0.0)?),
+    ])?;
+    assert_eq!(graph.get_node_count(), 5);
+
+    let sum = graph.add_combinator(&[left, left_scaled, right_averaged], ProcessorGraphCombinator::Sum)?;
+    let mix = graph.add_combinator(&[left_scaled, right_averaged], ProcessorGraphCombinator::WeightedMix(vec![3.0, 1.0]))?;
+    let select = graph.add_combinator(&[left_scaled, right_averaged], ProcessorGraphCombinator::Select(0))?;
+    assert_eq!(graph.get_output_data_type(sum)?, IOTypeVariant::F32);
+    assert_eq!(graph.get_output_data_type(mix)?, IOTypeVariant::F32Normalized0To1);
+    assert_eq!(graph.get_output_data_type(select)?, IOTypeVariant::F32Normalized0To1);
+
+    let start = Instant::now();
+    graph.update_values(&[(left, &IOTypeData::new_f32(5.0)?), (right, &IOTypeData::new_f32(80.0)?)], start)?;
+    let read = |graph: &ProcessorGraphRunner, node| -> Result<f32, Box<dyn std::error::Error>> { Ok(f32::try_from(graph.get_output(node)?)?) };
+    assert_eq!(read(&graph, right_averaged)?, 0.4); // Average of 0 and 80
+    assert_eq!(read(&graph, sum)?, 5.0 + 0.5 + 0.4);
+    assert!((read(&graph, mix)? - (0.75 * 0.5 + 0.25 * 0.4)).abs() < 1e-6);
+    assert_eq!(read(&graph, select)?, 0.5);
+
+    // Only nodes downstream of an updated input run, so the right rolling window sees no repeats
+    for i in 1..4 {
+        graph.update_value(left, &IOTypeData::new_f32(i as f32)?, start + Duration::from_millis(i))?;
+    }
+    assert_eq!(read(&graph, right_averaged)?, 0.4);
+    graph.set_select_index(select, 1)?;
+    graph.update_value(right, &IOTypeData::new_f32(20.0)?, start + Duration::from_millis(5))?;
+    assert_eq!(read(&graph, right_averaged)?, 0.5); // Average of 80 and 20
+    assert_eq!(read(&graph, select)?, 0.5);
+    assert_eq!(read(&graph, sum)?, 3.0 + 0.3 + 0.5);
+    Ok(())
+}
+
+#[test]
+fn test_processor_graph_rejects_mismatched_types() -> Result<(), Box<dyn std::error::Error>> {
+    let mut graph = ProcessorGraphRunner::new();
+    let input = graph.add_input(IOTypeData::new_f32(0.0)?);
+    let normalized = graph.add_processor(input, Box::new(LinearScaleTo0And1Processor::new(0.0, 10.0, 0.0)?))?;
+    let signed = graph.add_processor(input, Box::new(LinearScaleToM1And1::new(0.0, 10.0, 5.0)?))?;
+
+    // Processors must accept the type of their source
+    assert!(graph.add_processor(normalized, Box::new(IdentityFloatProcessor::new(0.0)?)).is_err());
+    assert!(graph.add_processor_chain(input, vec![]).is_err());
+    assert!(graph.add_processor_chain(input, vec![
+        Box::new(LinearScaleTo0And1Processor::new(0.0, 10.0, 0.0)?),
+        Box::new(IdentityFloatProcessor::new(0.0)?),
+    ]).is_err());
+
+    // Combinators check their sources and parameters
+    assert!(graph.add_combinator(&[], ProcessorGraphCombinator::Sum).is_err());
+    assert!(graph.add_combinator(&[normalized, signed], ProcessorGraphCombinator::WeightedMix(vec![1.0, 1.0])).is_err());
+    assert!(graph.add_combinator(&[normalized, normalized], ProcessorGraphCombinator::WeightedMix(vec![1.0])).is_err());
+    assert!(graph.add_combinator(&[normalized, normalized], ProcessorGraphCombinator::WeightedMix(vec![1.0, -1.0])).is_err());
+    assert!(graph.add_combinator(&[normalized, normalized], ProcessorGraphCombinator::WeightedMix(vec![0.0, 0.0])).is_err());
+    assert!(graph.add_combinator(&[normalized, signed], ProcessorGraphCombinator::Select(0)).is_err());
+    assert!(graph.add_combinator(&[normalized], ProcessorGraphCombinator::Select(1)).is_err());
+    let sum = graph.add_combinator(&[normalized, signed], ProcessorGraphCombinator::Sum)?;
+    assert!(graph.set_select_index(sum, 0).is_err());
+    assert_eq!(graph.get_node_count(), 4); // Nothing rejected was added
+
+    // Only inputs can be updated, with values of their type
+    assert!(graph.update_value(normalized, &IOTypeData::new_0_1_f32(0.5)?, Instant::now()).is_err());
+    assert!(graph.update_value(input, &IOTypeData::new_0_1_f32(0.5)?, Instant::now()).is_err());
+    graph.update_value(input, &IOTypeData::new_f32(10.0)?, Instant::now())?;
+    assert_eq!(f32::try_from(graph.get_output(sum)?)?, 2.0);
+    Ok(())
+}
@@ -0,0 +1,91 @@
+//! Tests that parallel encoding of a SensorCache matches the serial path. Requires the
+//! `parallel` feature, run with `cargo test --features parallel`.
+#![cfg(feature = "parallel")]
+
+use std::time::Instant;
+use ndarray::Array3;
+use feagi_core_data_structures_and_processing::error::FeagiDataProcessingError;
+use feagi_core_data_structures_and_processing::genomic_structures::{CorticalID, CorticalIOChannelIndex, SensorCorticalType};
+use feagi_core_data_structures_and_processing::io_data::{ImageFrame, IOTypeData, IOTypeVariant};
+use feagi_core_data_structures_and_processing::io_data::image_descriptors::{ColorChannelLayout, ColorSpace, ImageFrameProperties, MemoryOrderLayout};
+use feagi_core_data_structures_and_processing::io_processing::processors::LinearScaleTo0And1Processor;
+use feagi_core_data_structures_and_processing::io_processing::{SensorCache, StreamCacheProcessor};
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPEncoder};
+
+/// Fails to encode any value above 0.5.
+struct FailingAboveHalfEncoder {
+    cortical_id: CorticalID,
+}
+
+impl NeuronXYZPEncoder for FailingAboveHalfEncoder {
+    fn get_encodable_data_type(&self) -> IOTypeVariant {
+        IOTypeVariant::F32Normalized0To1
+    }
+
+    fn write_neuron_data_single_channel(&self, wrapped_value: &IOTypeData, _cortical_channel: CorticalIOChannelIndex, write_target: &mut CorticalMappedXYZPNeuronData) -> Result<(), FeagiDataProcessingError> {
+        if f32::try_from(wrapped_value)? > 0.5 {
+            return Err(FeagiDataProcessingError::InternalError("Value too large!".into()));
+        }
+        _ = write_target.ensure_clear_and_borrow_mut(&self.cortical_id, 0);
+        Ok(())
+    }
+}
+
+fn create_camera_frame(width: usize, height: usize) -> ImageFrame {
+    let pixels = Array3::from_shape_fn((height, width, 3), |(y, x, c)| ((x * 7 + y * 13 + c * 29) % 256) as f32 / 255.0);
+    ImageFrame::from_array(pixels, &ColorSpace::Linear, &MemoryOrderLayout::HeightsWidthsChannels).unwrap()
+}
+
+fn create_sensor_cache() -> Result<SensorCache, FeagiDataProcessingError> {
+    let mut sensor_cache = SensorCache::new();
+    let input_properties = ImageFrameProperties::new((64, 48), ColorSpace::Linear, ColorChannelLayout::RGB)?;
+    let output_properties = ImageFrameProperties::new((32, 24), ColorSpace::Linear, ColorChannelLayout::RGB)?;
+    sensor_cache.register_cortical_group_for_image_camera(0.into(), 1, true, input_properties, output_properties)?;
+    sensor_cache.send_data_for_image_camera(create_camera_frame(64, 48), 0.into(), 0.into())?;
+
+    for group in 0..6u8 {
+        sensor_cache.register_cortical_group_for_proximity(group.into(), 4, true, 10, 0.0, 100.0)?;
+        for channel in 0..4u32 {
+            sensor_cache.send_data_for_proximity((group as u32 * 10 + channel * 3) as f32, group.into(), channel.into())?;
+        }
+    }
+    Ok(sensor_cache)
+}
+
+#[test]
+fn test_parallel_encoding_matches_serial() -> Result<(), FeagiDataProcessingError> {
+    let sensor_cache = create_sensor_cache()?;
+    let mut serial = CorticalMappedXYZPNeuronData::new();
+    sensor_cache.encode_to_neurons(Instant::now(), &mut serial)?;
+    assert_eq!(serial.len(), 7); // The camera area and 6 proximity areas
+
+    // Repeated runs, into empty and previously filled neuron data, always give the same result
+    let mut reused = CorticalMappedXYZPNeuronData::new();
+    for _ in 0..5 {
+        let mut parallel = CorticalMappedXYZPNeuronData::new();
+        sensor_cache.encode_to_neurons_parallel(Instant::now(), &mut parallel)?;
+        sensor_cache.encode_to_neurons_parallel(Instant::now(), &mut reused)?;
+        assert!(serial.diff(&parallel, 0.0).is_empty());
+        assert!(serial.diff(&reused, 0.0).is_empty());
+        for cortical_id in serial.keys() {
+            assert_eq!(serial.get_neurons_of(cortical_id).unwrap().copy_as_neuron_xyzp_vec(), parallel.get_neurons_of(cortical_id).unwrap().copy_as_neuron_xyzp_vec());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parallel_encoding_reports_errors_like_serial() -> Result<(), FeagiDataProcessingError> {
+    let mut sensor_cache = create_sensor_cache()?;
+    let failing_id = CorticalID::new_sensor_cortical_area_id(SensorCorticalType::Proximity, 7.into())?;
+    let chain: Vec<Box<dyn StreamCacheProcessor + Sync + Send>> = vec![Box::new(LinearScaleTo0And1Processor::new(0.0, 1.0, 0.0)?)];
+    sensor_cache.register_cortical_area_with_encoder(SensorCorticalType::Proximity, 7.into(), Box::new(FailingAboveHalfEncoder { cortical_id: failing_id }), vec![chain], true)?;
+    sensor_cache.update_value_by_channel(IOTypeData::new_f32(0.9)?, SensorCorticalType::Proximity, 7.into(), 0.into())?;
+
+    let mut serial = CorticalMappedXYZPNeuronData::new();
+    let mut parallel = CorticalMappedXYZPNeuronData::new();
+    assert!(sensor_cache.encode_to_neurons(Instant::now(), &mut serial).is_err());
+    assert!(sensor_cache.encode_to_neurons_parallel(Instant::now(), &mut parallel).is_err());
+    assert!(serial.diff(&parallel, 0.0).is_empty());
+    Ok(())
+}
@@ -0,0 +1,274 @@
+//! Tests for recording sessions to a file and playing them back.
+
+use std::io::{Cursor, Seek, SeekFrom, Write};
+use std::time::{Duration, Instant};
+use ndarray::Array3;
+use feagi_core_data_structures_and_processing::error::FeagiDataProcessingError;
+use feagi_core_data_structures_and_processing::genomic_structures::{CorticalID, SensorCorticalType};
+use feagi_core_data_structures_and_processing::io_data::{ImageFrame, IOTypeData};
+use feagi_core_data_structures_and_processing::io_data::image_descriptors::{ColorChannelLayout, ColorSpace, ImageFrameProperties, MemoryOrderLayout};
+use feagi_core_data_structures_and_processing::io_processing::recording::{FeagiSessionFrameContent, FeagiSessionPlayer, FeagiSessionRecorder};
+use feagi_core_data_structures_and_processing::io_processing::SensorCache;
+use feagi_core_data_structures_and_processing::neuron_data::xyzp::{CorticalMappedXYZPNeuronData, NeuronXYZPArrays};
+
+fn create_camera_frame(width: usize, height: usize, seed: usize) -> ImageFrame {
+    let pixels = Array3::from_shape_fn((height, width, 3), |(y, x, c)| ((x * 7 + y * 13 + c * 29 + seed) % 256) as f32 / 255.0);
+    ImageFrame::from_array(pixels, &ColorSpace::Linear, &MemoryOrderLayout::HeightsWidthsChannels).unwrap()
+}
+
+fn create_neuron_data(tick: u32) -> CorticalMappedXYZPNeuronData {
+    let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+    let cortical_id = CorticalID::from_string("iic400".to_string()).unwrap();
+    neuron_data.insert(cortical_id, NeuronXYZPArrays::new_from_vectors(vec![tick, tick + 1], vec![0, 1], vec![0, 0], vec![0.25, tick as f32 / 10.0]).unwrap());
+    neuron_data
+}
+
+/// Records 10 ticks, 20ms apart, alternating a proximity reading and a burst of neuron data.
+fn record_session() -> Result<Cursor<Vec<u8>>, FeagiDataProcessingError> {
+    let mut recorder = FeagiSessionRecorder::new(Cursor::new(Vec::new()))?;
+    for tick in 0..10u32 {
+        let timestamp = Duration::from_millis(tick as u64 * 20);
+        recorder.record_sensor_input_at(timestamp, &IOTypeData::F32(tick as f32 * 10.0), SensorCorticalType::Proximity, 0.into(), (tick % 2).into())?;
+        recorder.record_neuron_data_at(timestamp, &create_neuron_data(tick))?;
+    }
+    assert_eq!(recorder.get_frame_count(), 20);
+    finish_and_rewind(recorder)
+}
+
+fn finish_and_rewind(recorder: FeagiSessionRecorder<Cursor<Vec<u8>>>) -> Result<Cursor<Vec<u8>>, FeagiDataProcessingError> {
+    let mut file = recorder.finish()?;
+    file.rewind().unwrap();
+    Ok(file)
+}
+
+#[test]
+fn test_recording_round_trip() -> Result<(), FeagiDataProcessingError> {
+    let mut recorder = FeagiSessionRecorder::new(Cursor::new(Vec::new()))?;
+    let image = create_camera_frame(16, 12, 3);
+    recorder.record_sensor_input_at(Duration::from_millis(5), &IOTypeData::F32NormalizedM1To1(-0.5), SensorCorticalType::Proximity, 2.into(), 3.into())?;
+    recorder.record_sensor_input_at(Duration::from_millis(10), &IOTypeData::ImageFrame(image.clone()), SensorCorticalType::ImageCameraCenter, 1.into(), 0.into())?;
+    recorder.record_neuron_data_at(Duration::from_millis(10), &create_neuron_data(4))?;
+    assert!(recorder.record_neuron_data_at(Duration::from_millis(9), &create_neuron_data(5)).is_err()); // Time cannot go backwards
+    let mut player = FeagiSessionPlayer::new(finish_and_rewind(recorder)?)?;
+    player.set_playback_speed(None)?;
+
+    assert_eq!(player.get_frame_count(), 3);
+    assert_eq!(player.get_duration(), Duration::from_millis(10));
+
+    let frame = player.next_frame()?.unwrap();
+    assert_eq!(frame.get_timestamp(), Duration::from_millis(5));
+    match frame.get_content() {
+        FeagiSessionFrameContent::SensorInput { sensor_cortical_type, cortical_grouping_index, device_channel, value } => {
+            assert_eq!(*sensor_cortical_type, SensorCorticalType::Proximity);
+            assert_eq!(**cortical_grouping_index, 2);
+            assert_eq!(**device_channel, 3);
+            assert!(matches!(value.as_ref(), IOTypeData::F32NormalizedM1To1(-0.5)));
+        }
+        other => panic!("Unexpected frame content {:?}", other),
+    }
+
+    match player.next_frame()?.unwrap().into_content() {
+        FeagiSessionFrameContent::SensorInput { sensor_cortical_type, value, .. } => {
+            assert_eq!(sensor_cortical_type, SensorCorticalType::ImageCameraCenter);
+            let IOTypeData::ImageFrame(read_image) = *value else { panic!("Expected an image frame") };
+            assert_eq!(read_image.get_internal_data(), image.get_internal_data());
+        }
+        other => panic!("Unexpected frame content {:?}", other),
+    }
+
+    match player.next_frame()?.unwrap().into_content() {
+        FeagiSessionFrameContent::NeuronData(neuron_data) => assert!(neuron_data.diff(&create_neuron_data(4), 0.0).is_empty()),
+        other => panic!("Unexpected frame content {:?}", other),
+    }
+    assert!(player.next_frame()?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_recording_seeking() -> Result<(), FeagiDataProcessingError> {
+    let mut player = FeagiSessionPlayer::new(record_session()?)?;
+    player.set_playback_speed(None)?;
+    assert_eq!(player.get_frame_count(), 20);
+    assert_eq!(player.get_duration(), Duration::from_millis(180));
+
+    player.seek_to_time(Duration::from_millis(95))?;
+    assert_eq!(player.get_position(), 10);
+    assert_eq!(player.next_frame()?.unwrap().get_timestamp(), Duration::from_millis(100));
+
+    // Random access does not move the playback position
+    match player.read_frame(19)?.into_content() {
+        FeagiSessionFrameContent::NeuronData(neuron_data) => assert!(neuron_data.diff(&create_neuron_data(9), 0.0).is_empty()),
+        other => panic!("Unexpected frame content {:?}", other),
+    }
+    assert_eq!(player.get_position(), 11);
+
+    player.seek_to_time(Duration::from_secs(10))?;
+    assert!(player.next_frame()?.is_none());
+    assert!(player.seek_to_frame(21).is_err());
+    assert!(player.read_frame(20).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_unfinished_recording_is_recovered() -> Result<(), FeagiDataProcessingError> {
+    // Simulate a crashed agent by removing the index from a finished recording
+    let mut recorder = FeagiSessionRecorder::new(Cursor::new(Vec::new()))?;
+    for tick in 0..5u32 {
+        recorder.record_neuron_data_at(Duration::from_millis(tick as u64), &create_neuron_data(tick))?;
+    }
+    let finished = recorder.finish()?.into_inner();
+    let frames_end = finished.len() - 5 * 17; // Strip the index, leaving the header pointing nowhere
+    let mut unfinished = finished[..frames_end].to_vec();
+    unfinished[16..32].fill(0);
+
+    let player = FeagiSessionPlayer::new(Cursor::new(unfinished.clone()))?;
+    assert_eq!(player.get_frame_count(), 5);
+    assert_eq!(player.get_duration(), Duration::from_millis(4));
+
+    // A frame cut off partway through is dropped
+    unfinished.truncate(unfinished.len() - 3);
+    let mut player = FeagiSessionPlayer::new(Cursor::new(unfinished))?;
+    assert_eq!(player.get_frame_count(), 4);
+    player.set_playback_speed(None)?;
+    let mut count = 0;
+    while player.next_frame()?.is_some() {
+        count += 1;
+    }
+    assert_eq!(count, 4);
+
+    assert!(FeagiSessionPlayer::new(Cursor::new(b"NOTAREC0".repeat(4))).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_unfinished_recording_stops_at_out_of_order_frame() -> Result<(), FeagiDataProcessingError> {
+    let mut recorder = FeagiSessionRecorder::new(Cursor::new(Vec::new()))?;
+    for tick in 0..5u32 {
+        recorder.record_neuron_data_at(Duration::from_millis(tick as u64 + 1), &create_neuron_data(tick))?;
+    }
+    let finished = recorder.finish()?.into_inner();
+    let frames_end = finished.len() - 5 * 17;
+    let third_frame_offset = u64::from_le_bytes(finished[frames_end + 3 * 17..frames_end + 3 * 17 + 8].try_into().unwrap()) as usize;
+    let mut unfinished = finished[..frames_end].to_vec();
+    unfinished[16..32].fill(0);
+    unfinished[third_frame_offset + 4..third_frame_offset + 12].fill(0); // Move the frame back in time
+
+    let mut player = FeagiSessionPlayer::new(Cursor::new(unfinished))?;
+    assert_eq!(player.get_frame_count(), 3);
+    assert_eq!(player.get_duration(), Duration::from_millis(3));
+    player.seek_to_time(Duration::from_millis(2))?;
+    assert_eq!(player.read_frame(1)?.get_timestamp(), Duration::from_millis(2));
+    Ok(())
+}
+
+#[test]
+fn test_corrupt_frame_length_is_rejected() -> Result<(), FeagiDataProcessingError> {
+    let mut file = record_session()?;
+    let mut corrupted = file.get_ref().clone();
+    corrupted[32..36].copy_from_slice(&u32::MAX.to_le_bytes()); // The length prefix of the first frame
+    let mut player = FeagiSessionPlayer::new(Cursor::new(corrupted.clone()))?;
+    assert!(player.read_frame(0).is_err());
+    assert_eq!(player.read_frame(1)?.get_timestamp(), Duration::ZERO);
+
+    // The last frame is bounded by the index
+    let index_offset = u64::from_le_bytes(corrupted[16..24].try_into().unwrap()) as usize;
+    let last_frame_offset = u64::from_le_bytes(corrupted[index_offset + 19 * 17..index_offset + 19 * 17 + 8].try_into().unwrap()) as usize;
+    let last_frame_length = u32::from_le_bytes(corrupted[last_frame_offset..last_frame_offset + 4].try_into().unwrap());
+    corrupted[last_frame_offset..last_frame_offset + 4].copy_from_slice(&(last_frame_length + 1).to_le_bytes());
+    let mut player = FeagiSessionPlayer::new(Cursor::new(corrupted))?;
+    assert!(player.read_frame(19).is_err());
+
+    file.rewind().unwrap();
+    let mut player = FeagiSessionPlayer::new(file)?;
+    assert_eq!(player.read_frame(19)?.get_timestamp(), Duration::from_millis(180));
+    Ok(())
+}
+
+#[test]
+fn test_recording_at_offset_in_file() -> Result<(), FeagiDataProcessingError> {
+    let mut file = Cursor::new(Vec::new());
+    file.write_all(b"prefix").unwrap();
+    let mut recorder = FeagiSessionRecorder::new(file)?;
+    recorder.record_neuron_data_at(Duration::from_millis(1), &create_neuron_data(1))?;
+    let mut file = recorder.finish()?;
+
+    file.seek(SeekFrom::Start(6)).unwrap();
+    let mut player = FeagiSessionPlayer::new(file)?;
+    assert_eq!(player.get_frame_count(), 1);
+    assert!(matches!(player.read_frame(0)?.get_content(), FeagiSessionFrameContent::NeuronData(_)));
+    Ok(())
+}
+
+#[test]
+fn test_accelerated_playback_timing() -> Result<(), FeagiDataProcessingError> {
+    let mut player = FeagiSessionPlayer::new(record_session()?)?;
+    assert!(player.set_playback_speed(Some(0.0)).is_err());
+    assert!(player.set_playback_speed(Some(f32::NAN)).is_err());
+
+    // 180ms of recording at 4x speed should take about 45ms
+    player.set_playback_speed(Some(4.0))?;
+    let start = Instant::now();
+    while player.next_frame()?.is_some() {}
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(45), "Playback took {:?}", elapsed);
+    assert!(elapsed < Duration::from_millis(180), "Playback took {:?}", elapsed);
+
+    // Speeds too slow to schedule a frame are an error rather than a panic, and keep the position
+    player.seek_to_frame(0)?;
+    player.set_playback_speed(Some(1e-30))?;
+    assert!(player.next_frame()?.is_some());
+    assert!(player.next_frame()?.is_some());
+    assert!(player.next_frame().is_err());
+    assert_eq!(player.get_position(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_replay_into_sensor_cache_matches_live() -> Result<(), FeagiDataProcessingError> {
+    let create_sensor_cache = || -> Result<SensorCache, FeagiDataProcessingError> {
+        let mut sensor_cache = SensorCache::new();
+        sensor_cache.register_cortical_group_for_proximity(0.into(), 2, true, 10, 0.0, 100.0)?;
+        let input_properties = ImageFrameProperties::new((32, 24), ColorSpace::Linear, ColorChannelLayout::RGB)?;
+        let output_properties = ImageFrameProperties::new((16, 12), ColorSpace::Linear, ColorChannelLayout::RGB)?;
+        sensor_cache.register_cortical_group_for_image_camera(0.into(), 1, true, input_properties, output_properties)?;
+        Ok(sensor_cache)
+    };
+
+    // Feed a live cache while recording every input, and the neurons it encodes
+    let mut live_cache = create_sensor_cache()?;
+    let mut live_bursts: Vec<CorticalMappedXYZPNeuronData> = Vec::new();
+    let mut recorder = FeagiSessionRecorder::new(Cursor::new(Vec::new()))?;
+    for tick in 0..4u32 {
+        let timestamp = Duration::from_millis(tick as u64);
+        let proximity = IOTypeData::F32(tick as f32 * 20.0);
+        let camera = IOTypeData::ImageFrame(create_camera_frame(32, 24, tick as usize));
+        live_cache.update_value_by_channel(proximity.clone(), SensorCorticalType::Proximity, 0.into(), (tick % 2).into())?;
+        recorder.record_sensor_input_at(timestamp, &proximity, SensorCorticalType::Proximity, 0.into(), (tick % 2).into())?;
+        live_cache.update_value_by_channel(camera.clone(), SensorCorticalType::ImageCameraCenter, 0.into(), 0.into())?;
+        recorder.record_sensor_input_at(timestamp, &camera, SensorCorticalType::ImageCameraCenter, 0.into(), 0.into())?;
+
+        let mut neuron_data = CorticalMappedXYZPNeuronData::new();
+        live_cache.encode_to_neurons(Instant::now(), &mut neuron_data)?;
+        recorder.record_neuron_data_at(timestamp, &neuron_data)?;
+        live_bursts.push(neuron_data);
+    }
+
+    // Replaying the inputs into a fresh cache reproduces the recorded bursts
+    let mut player = FeagiSessionPlayer::new(finish_and_rewind(recorder)?)?;
+    player.set_playback_speed(None)?;
+    let mut replay_cache = create_sensor_cache()?;
+    let mut compared_count = 0;
+    let sent_count = player.replay_into_sensor_cache(&mut replay_cache, |frame, sensor_cache| {
+        if let FeagiSessionFrameContent::NeuronData(recorded) = frame.get_content() {
+            let mut replayed = CorticalMappedXYZPNeuronData::new();
+            sensor_cache.encode_to_neurons(Instant::now(), &mut replayed)?;
+            assert!(recorded.diff(&replayed, 0.0).is_empty(), "{}", recorded.diff(&replayed, 0.0));
+            assert!(live_bursts[compared_count].diff(&replayed, 0.0).is_empty());
+            compared_count += 1;
+        }
+        Ok(())
+    })?;
+    assert_eq!(sent_count, 8);
+    assert_eq!(compared_count, 4);
+    Ok(())
+}